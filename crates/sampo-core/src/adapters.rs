@@ -7,11 +7,46 @@ pub mod packagist;
 pub mod pypi;
 pub(crate) mod scan;
 
-use crate::errors::{Result, WorkspaceError};
+use crate::errors::{Result, SampoError, WorkspaceError};
+use crate::trusted_publishing::TrustedRegistry;
 use crate::types::{ConstraintCheckResult, PackageInfo, PackageKind, Workspace};
+use reqwest::StatusCode;
 use std::collections::BTreeMap;
-use std::path::Path;
-use std::process::Command;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+
+/// Output of publish tools that means the network or the registry failed, not the
+/// package. Adapters add the phrasing of their own tool.
+const TRANSIENT_OUTPUT: &[&str] = &[
+    "429 Too Many Requests",
+    "502 Bad Gateway",
+    "503 Service Unavailable",
+    "500 Internal Server Error",
+    "504 Gateway Time",
+    "Connection reset",
+    "connection reset",
+    "Connection refused",
+    "Connection timed out",
+    "connection timed out",
+    "operation timed out",
+    "Operation timed out",
+    "ETIMEDOUT",
+    "ECONNRESET",
+    "ECONNREFUSED",
+    "EAI_AGAIN",
+    "socket hang up",
+];
+
+/// Output of publish tools refusing a version the registry already has. A retried
+/// publish whose earlier attempt reached the registry ends this way, even while the
+/// registry index does not list the version yet.
+const ALREADY_PUBLISHED_OUTPUT: &[&str] = &[
+    "is already uploaded",
+    "already exists on crates.io index",
+    "cannot publish over the previously published versions",
+    "File already exists",
+];
 
 /// Whether the publish spawned a command at all.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,6 +55,19 @@ pub enum PublishOutcome {
     DryRunSkipped,
 }
 
+/// A published version to pull from its registry, as asked by `sampo yank`.
+#[derive(Debug, Clone, Copy)]
+pub struct YankRequest<'a> {
+    pub name: &'a str,
+    pub version: &'a str,
+    /// Shown to users of the version, where the registry records one.
+    pub reason: Option<&'a str>,
+    /// Remove the version from npm instead of deprecating it.
+    pub unpublish: bool,
+    /// Print the command instead of running it.
+    pub dry_run: bool,
+}
+
 /// Package ecosystem adapter (Cargo, npm, etc.).
 #[derive(Debug, Clone, Copy)]
 pub enum PackageAdapter {
@@ -135,23 +183,100 @@ impl PackageAdapter {
         }
     }
 
-    /// Execute the publish command for a package.
+    /// Whether [`Self::version_exists`] can see a version right after it is published.
+    /// It can't for packages routed to private registries, which it never queries, nor
+    /// for Packagist, which only learns about a release from its pushed tag.
+    pub fn observes_publish(&self, manifest_path: &Path) -> bool {
+        match self {
+            Self::Cargo | Self::Npm | Self::Hex => true,
+            Self::PyPI => pypi::PyPIAdapter.observes_publish(manifest_path),
+            Self::Maven => maven::MavenAdapter.observes_publish(manifest_path),
+            Self::Packagist => false,
+        }
+    }
+
+    /// Publish arguments selecting `registry`, one of the package's
+    /// `[packages."<package>"] registries`. Cargo takes registry names from
+    /// `.cargo/config.toml` (`crates-io` for crates.io), npm takes `npmjs` or a registry URL.
+    pub fn registry_args(&self, package: &PackageInfo, registry: &str) -> Result<Vec<String>> {
+        match self {
+            Self::Cargo => Ok(cargo::registry_args(registry)),
+            Self::Npm => npm::registry_args(&package.name, registry),
+            Self::Hex | Self::PyPI | Self::Packagist | Self::Maven => {
+                Err(SampoError::Publish(format!(
+                    "{} cannot be published to several registries: {} packages do not support `registries`",
+                    package.display_name(true),
+                    package.kind.display_name()
+                )))
+            }
+        }
+    }
+
+    /// Whether `registry`, one of the package's `registries`, serves the version.
+    pub fn version_exists_on(
+        &self,
+        package_name: &str,
+        version: &str,
+        manifest_path: &Path,
+        registry: &str,
+    ) -> Result<bool> {
+        match self {
+            Self::Cargo => cargo::version_exists_on(package_name, version, manifest_path, registry),
+            Self::Npm => npm::version_exists_on(package_name, version, manifest_path, registry),
+            Self::Hex | Self::PyPI | Self::Packagist | Self::Maven => Err(SampoError::Publish(
+                format!("{package_name} does not support `registries`"),
+            )),
+        }
+    }
+
+    /// The public registry that would mint a trusted publishing token for this package,
+    /// or `None` when it publishes elsewhere.
+    pub fn trusted_registry(
+        &self,
+        manifest_path: &Path,
+        extra_args: &[String],
+    ) -> Option<TrustedRegistry> {
+        match self {
+            Self::Cargo => cargo::CargoAdapter
+                .publishes_to_crates_io(manifest_path, extra_args)
+                .then_some(TrustedRegistry::CratesIo),
+            Self::PyPI => pypi::PyPIAdapter
+                .publishes_to_pypi(manifest_path, extra_args)
+                .then_some(TrustedRegistry::PyPI),
+            Self::Npm | Self::Hex | Self::Packagist | Self::Maven => None,
+        }
+    }
+
+    /// Artifacts the last publish of `package` left on disk, for its provenance statement.
+    pub fn published_artifacts(&self, manifest_path: &Path, package: &PackageInfo) -> Vec<PathBuf> {
+        match self {
+            Self::Cargo => cargo::packaged_crate(manifest_path, &package.name, &package.version)
+                .into_iter()
+                .collect(),
+            Self::PyPI => pypi::built_distributions(manifest_path),
+            Self::Npm | Self::Hex | Self::Packagist | Self::Maven => Vec::new(),
+        }
+    }
+
+    /// Execute the publish command for a package. `token` is a publish token minted
+    /// through trusted publishing, used instead of the configured credentials.
     pub fn publish(
         &self,
         manifest_path: &Path,
         dry_run: bool,
         extra_args: &[String],
+        token: Option<&str>,
     ) -> Result<PublishOutcome> {
         match self {
             Self::Cargo => cargo::CargoAdapter
-                .publish(manifest_path, dry_run, extra_args)
+                .publish(manifest_path, dry_run, extra_args, token)
                 .map(|()| PublishOutcome::Ran),
             Self::Npm => npm::NpmAdapter.publish(manifest_path, dry_run, extra_args),
             Self::Hex => hex::HexAdapter
                 .publish(manifest_path, dry_run, extra_args)
                 .map(|()| PublishOutcome::Ran),
             Self::PyPI => pypi::PyPIAdapter
-                .publish(manifest_path, dry_run, extra_args)
+                .publish(manifest_path, dry_run, extra_args, token)
                 .map(|()| PublishOutcome::Ran),
             Self::Packagist => packagist::PackagistAdapter
                 .publish(manifest_path, dry_run, extra_args)
@@ -162,6 +287,32 @@ impl PackageAdapter {
         }
     }
 
+    /// Yank, deprecate or retire a published version, whichever its registry offers.
+    pub fn yank(&self, manifest_path: &Path, request: &YankRequest) -> Result<()> {
+        if request.unpublish && !matches!(self, Self::Npm) {
+            return Err(SampoError::Yank(
+                "only npm packages can be unpublished; drop --unpublish to yank this version"
+                    .to_string(),
+            ));
+        }
+        match self {
+            Self::Cargo => cargo::CargoAdapter.yank(manifest_path, request),
+            Self::Npm => npm::NpmAdapter.yank(manifest_path, request),
+            Self::Hex => hex::HexAdapter.yank(manifest_path, request),
+            // Warehouse has no API for yanking, only its web interface.
+            Self::PyPI => Err(SampoError::Yank(format!(
+                "PyPI cannot yank releases through its API; yank {} {} from https://pypi.org/manage/project/{}/release/{}/",
+                request.name, request.version, request.name, request.version
+            ))),
+            Self::Packagist => Err(SampoError::Yank(
+                "Packagist serves versions straight from git tags and cannot yank one".to_string(),
+            )),
+            Self::Maven => Err(SampoError::Yank(
+                "Maven Central never removes or deprecates a released version".to_string(),
+            )),
+        }
+    }
+
     /// Execute dry-run publish validation for the provided packages.
     /// Adapters can choose the most appropriate strategy (workspace-level or per-package).
     /// Returns the packages it could not validate, as kind-qualified
@@ -400,7 +551,7 @@ pub(crate) fn require_on_path(program: &str) -> Result<()> {
     if crate::process::is_on_path(program) {
         Ok(())
     } else {
-        Err(crate::errors::SampoError::Release(format!(
+        Err(SampoError::Release(format!(
             "{program} not found in PATH; install it to regenerate the lockfile, \
              or delete the lockfile to opt out of regeneration"
         )))
@@ -417,6 +568,127 @@ pub(crate) fn has_flag(args: &[String], flag: &str) -> bool {
         .any(|arg| arg == flag || value_prefix.as_deref().is_some_and(|p| arg.starts_with(p)))
 }
 
+/// Marks `error`, raised for a registry answering with `status`, as retryable when the
+/// registry was down or rate limiting.
+pub(crate) fn classify_response_status(status: StatusCode, error: SampoError) -> SampoError {
+    if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+        error.retryable()
+    } else {
+        error
+    }
+}
+
+/// Marks `error`, raised by a failed registry request, as retryable when the request
+/// timed out or could not connect.
+pub(crate) fn classify_request_error(request: &reqwest::Error, error: SampoError) -> SampoError {
+    if request.is_timeout() || request.is_connect() {
+        error.retryable()
+    } else {
+        error
+    }
+}
+
+/// Exit status and stderr of a command run with [`run_publish_command`].
+pub(crate) struct PublishRun {
+    pub(crate) status: ExitStatus,
+    output: String,
+}
+
+impl PublishRun {
+    /// Whether the command published the package, or failed only because the registry
+    /// already has this version.
+    pub(crate) fn succeeded(&self) -> bool {
+        self.status.success() || self.already_published()
+    }
+
+    fn already_published(&self) -> bool {
+        ALREADY_PUBLISHED_OUTPUT
+            .iter()
+            .any(|pattern| self.output.contains(pattern))
+    }
+
+    /// Marks `error`, raised because the command failed, as retryable when its output
+    /// shows a network or registry failure. `patterns` adds the tool's own phrasing.
+    pub(crate) fn classify(&self, error: SampoError, patterns: &[&str]) -> SampoError {
+        if TRANSIENT_OUTPUT
+            .iter()
+            .chain(patterns)
+            .any(|pattern| self.output.contains(pattern))
+        {
+            error.retryable()
+        } else {
+            error
+        }
+    }
+}
+
+/// Runs a publish command, passing its stderr through while keeping a copy to classify
+/// failures with [`PublishRun::classify`]. Stdin and stdout stay attached to the
+/// terminal, so interactive prompts (such as npm's one-time password) keep working.
+pub(crate) fn run_publish_command(cmd: &mut Command) -> io::Result<PublishRun> {
+    let mut child = cmd.stderr(Stdio::piped()).spawn()?;
+    let output = child
+        .stderr
+        .take()
+        .map(|pipe| tee(pipe, io::stderr()))
+        .unwrap_or_default();
+    let run = PublishRun {
+        status: child.wait()?,
+        output: String::from_utf8_lossy(&output).into_owned(),
+    };
+    if !run.status.success() && run.already_published() {
+        println!("The registry already has this version; nothing left to publish.");
+    }
+    Ok(run)
+}
+
+/// Runs the registry command built by an adapter's `yank`, or only prints it in a dry run.
+pub(crate) fn run_yank_command(cmd: &mut Command, dry_run: bool) -> Result<()> {
+    let display = format_command_display(cmd);
+    if dry_run {
+        println!("Dry-run: would run {display}");
+        return Ok(());
+    }
+
+    println!("Running: {display}");
+    let status = cmd.status().map_err(|err| {
+        if err.kind() == io::ErrorKind::NotFound {
+            SampoError::Yank(format!(
+                "{} not found in PATH",
+                cmd.get_program().to_string_lossy()
+            ))
+        } else {
+            SampoError::Io(err)
+        }
+    })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(SampoError::Yank(format!(
+            "`{display}` failed with status {status}"
+        )))
+    }
+}
+
+/// Copies `source` to `sink` as it comes, so prompts and progress still show up.
+fn tee(mut source: impl Read, mut sink: impl Write) -> Vec<u8> {
+    let mut copy = Vec::new();
+    let mut buffer = [0u8; 4096];
+    loop {
+        match source.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => {
+                let _ = sink.write_all(&buffer[..n]);
+                let _ = sink.flush();
+                copy.extend_from_slice(&buffer[..n]);
+            }
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        }
+    }
+    copy
+}
+
 /// Formats a command as a `program arg1 arg2` string for logging.
 pub(crate) fn format_command_display(cmd: &Command) -> String {
     let mut text = cmd.get_program().to_string_lossy().into_owned();
@@ -429,8 +701,99 @@ pub(crate) fn format_command_display(cmd: &Command) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{format_command_display, has_flag};
-    use std::process::Command;
+    use super::{
+        PublishRun, SampoError, StatusCode, classify_response_status, format_command_display,
+        has_flag, run_publish_command,
+    };
+    use std::process::{Command, ExitStatus};
+
+    #[test]
+    fn classifies_registry_statuses() {
+        let error = || SampoError::Publish("registry failure".into());
+        assert!(classify_response_status(StatusCode::BAD_GATEWAY, error()).is_retryable());
+        assert!(classify_response_status(StatusCode::TOO_MANY_REQUESTS, error()).is_retryable());
+        assert!(!classify_response_status(StatusCode::FORBIDDEN, error()).is_retryable());
+    }
+
+    #[test]
+    fn classifies_publish_output() {
+        let run = |output: &str| PublishRun {
+            status: ExitStatus::default(),
+            output: output.to_string(),
+        };
+        let error = || SampoError::Publish("publish failed".into());
+        assert!(
+            run("npm error code ECONNRESET")
+                .classify(error(), &[])
+                .is_retryable()
+        );
+        assert!(
+            run("npm error code E503")
+                .classify(error(), &["code E503"])
+                .is_retryable()
+        );
+        assert!(
+            !run("error: crate version `1.0.0` is already uploaded")
+                .classify(error(), &[])
+                .is_retryable()
+        );
+        assert!(
+            !run("error: reading manifest: operation on test_timed_out failed")
+                .classify(error(), &[])
+                .is_retryable()
+        );
+        assert!(
+            run("curl: (28) Connection timed out after 30000 milliseconds")
+                .classify(error(), &[])
+                .is_retryable()
+        );
+    }
+
+    #[test]
+    fn treats_already_published_output_as_success() {
+        let run = |output: &str| PublishRun {
+            status: failed_status(),
+            output: output.to_string(),
+        };
+        assert!(run("error: crate version `1.0.0` is already uploaded").succeeded());
+        assert!(
+            run("npm error 403 You cannot publish over the previously published versions: 1.0.0.")
+                .succeeded()
+        );
+        assert!(run("error: File already exists ('foo-1.0.0.tar.gz')").succeeded());
+        assert!(!run("error: failed to verify package tarball").succeeded());
+    }
+
+    #[cfg(unix)]
+    fn failed_status() -> ExitStatus {
+        use std::os::unix::process::ExitStatusExt;
+        ExitStatus::from_raw(1 << 8)
+    }
+
+    #[cfg(windows)]
+    fn failed_status() -> ExitStatus {
+        use std::os::windows::process::ExitStatusExt;
+        ExitStatus::from_raw(1)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn publish_command_captures_stderr_only() {
+        let mut cmd = Command::new("sh");
+        cmd.args([
+            "-c",
+            "echo uploading; echo 'Connection reset by peer' >&2; exit 1",
+        ]);
+        let run = run_publish_command(&mut cmd).unwrap();
+        assert!(!run.status.success());
+        // Stdout stays on the terminal for interactive prompts.
+        assert!(!run.output.contains("uploading"));
+        assert!(run.output.contains("Connection reset by peer"));
+        assert!(
+            run.classify(SampoError::Publish("failed".into()), &[])
+                .is_retryable()
+        );
+    }
 
     #[test]
     fn has_flag_matches_long_flags_with_optional_value() {
@@ -1,5 +1,8 @@
 /// Cargo ecosystem adapter for all Cargo operations.
-use crate::adapters::format_command_display;
+use crate::adapters::{
+    YankRequest, classify_request_error, classify_response_status, format_command_display,
+    has_flag, run_publish_command, run_yank_command,
+};
 use crate::errors::{Result, SampoError, WorkspaceError};
 use crate::types::{PackageInfo, PackageKind, Workspace};
 use cargo_metadata::MetadataCommand;
@@ -14,6 +17,13 @@ use std::process::Command;
 use std::time::Duration;
 use toml_edit::{DocumentMut, InlineTable, Item, Table, Value};
 
+/// How Cargo reports network failures worth a retry.
+const CARGO_TRANSIENT_OUTPUT: &[&str] = &[
+    "spurious network error",
+    "Timeout was reached",
+    "Couldn't connect to server",
+];
+
 const CARGO_MANIFEST: &str = "Cargo.toml";
 
 #[cfg(test)]
@@ -91,6 +101,7 @@ impl CargoAdapter {
         manifest_path: &Path,
         dry_run: bool,
         extra_args: &[String],
+        token: Option<&str>,
     ) -> Result<()> {
         let mut cmd = Command::new("cargo");
         cmd.arg("publish").arg("--manifest-path").arg(manifest_path);
@@ -103,25 +114,87 @@ impl CargoAdapter {
             cmd.args(extra_args);
         }
 
+        if let Some(token) = token {
+            cmd.env("CARGO_REGISTRY_TOKEN", token);
+        }
+
         println!("Running: {}", format_command_display(&cmd));
 
-        let status = cmd.status()?;
-        if !status.success() {
-            return Err(SampoError::Publish(format!(
+        let run = run_publish_command(&mut cmd)?;
+        if !run.succeeded() {
+            let error = SampoError::Publish(format!(
                 "cargo publish failed for {} with status {}",
                 manifest_path.display(),
-                status
-            )));
+                run.status
+            ));
+            return Err(run.classify(error, CARGO_TRANSIENT_OUTPUT));
         }
 
         Ok(())
     }
 
+    /// Whether `cargo publish` targets crates.io: neither the manifest nor the
+    /// extra arguments select another registry.
+    pub(super) fn publishes_to_crates_io(
+        &self,
+        manifest_path: &Path,
+        extra_args: &[String],
+    ) -> bool {
+        if has_flag(extra_args, "--index") {
+            return false;
+        }
+        // The last occurrence wins, as with Cargo's own parsing.
+        let registry_arg = extra_args
+            .iter()
+            .enumerate()
+            .filter_map(|(i, arg)| match arg.strip_prefix("--registry") {
+                Some("") => extra_args.get(i + 1).map(String::as_str),
+                Some(value) => value.strip_prefix('='),
+                None => None,
+            })
+            .next_back();
+        match registry_arg {
+            Some(registry) => registry == CRATES_IO_REGISTRY,
+            None => publish_target_registry(manifest_path).is_none(),
+        }
+    }
+
+    pub(super) fn yank(&self, manifest_path: &Path, request: &YankRequest) -> Result<()> {
+        if request.reason.is_some() {
+            println!("Note: Cargo registries do not record why a version was yanked.");
+        }
+        let mut cmd = Command::new("cargo");
+        cmd.args(yank_args(
+            request.name,
+            request.version,
+            publish_target_registry(manifest_path).as_deref(),
+        ));
+        if let Some(dir) = manifest_path.parent() {
+            cmd.current_dir(dir);
+        }
+        run_yank_command(&mut cmd, request.dry_run)
+    }
+
     pub(super) fn regenerate_lockfile(&self, workspace_root: &Path) -> Result<()> {
         regenerate_cargo_lockfile(workspace_root)
     }
 }
 
+/// `cargo yank` arguments, aimed at the registry the crate publishes to.
+fn yank_args(crate_name: &str, version: &str, registry: Option<&str>) -> Vec<String> {
+    let mut args = vec![
+        "yank".to_string(),
+        "--version".to_string(),
+        version.to_string(),
+    ];
+    if let Some(registry) = registry {
+        args.push("--registry".to_string());
+        args.push(registry.to_string());
+    }
+    args.push(crate_name.to_string());
+    args
+}
+
 /// Check if a Cargo dependency version constraint is satisfied by a new version.
 pub(super) fn check_dependency_constraint(
     _dep_name: &str,
@@ -309,7 +382,7 @@ fn run_cargo_dry_run(
     extra_args: &[String],
 ) -> Result<()> {
     CargoAdapter
-        .publish(manifest_path, true, extra_args)
+        .publish(manifest_path, true, extra_args, None)
         .map_err(|err| match err {
             SampoError::Publish(message) => SampoError::Publish(format!(
                 "Dry-run publish failed for {}: {}",
@@ -360,6 +433,29 @@ fn manifest_publish_target(value: &toml::Value) -> Option<String> {
     (name != "crates-io").then(|| name.to_string())
 }
 
+/// Cargo's name for crates.io, usable wherever it expects a registry name.
+const CRATES_IO_REGISTRY: &str = "crates-io";
+
+/// `cargo publish` arguments selecting `registry`, a name from `.cargo/config.toml`
+/// or `crates-io`.
+pub(super) fn registry_args(registry: &str) -> Vec<String> {
+    vec!["--registry".to_string(), registry.to_string()]
+}
+
+/// Whether `registry`, one of the crate's `registries`, serves `crate_name@version`.
+pub(super) fn version_exists_on(
+    crate_name: &str,
+    version: &str,
+    manifest_path: &Path,
+    registry: &str,
+) -> Result<bool> {
+    if registry == CRATES_IO_REGISTRY {
+        version_exists_on_crates_io(crate_name, version)
+    } else {
+        version_exists_via_cargo_info(crate_name, version, registry, manifest_path)
+    }
+}
+
 /// Alternative registries go through `cargo info` to reuse Cargo's config resolution
 /// and stored credentials; crates.io uses its HTTP API directly.
 fn cargo_version_exists(
@@ -375,6 +471,24 @@ fn cargo_version_exists(
     version_exists_on_crates_io(crate_name, version)
 }
 
+/// The `.crate` file `cargo publish` packaged for `name@version`, found in the
+/// `target/package` directory of `CARGO_TARGET_DIR` or of the nearest ancestor.
+pub(super) fn packaged_crate(manifest_path: &Path, name: &str, version: &str) -> Option<PathBuf> {
+    let file_name = format!("{name}-{version}.crate");
+    let target_dirs = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .into_iter()
+        .chain(
+            manifest_path
+                .ancestors()
+                .skip(1)
+                .map(|dir| dir.join("target")),
+        );
+    target_dirs
+        .map(|target| target.join("package").join(&file_name))
+        .find(|path| path.is_file())
+}
+
 fn publish_target_registry(manifest_path: &Path) -> Option<String> {
     let text = fs::read_to_string(manifest_path).ok()?;
     let value: toml::Value = toml::from_str(&text).ok()?;
@@ -452,10 +566,12 @@ fn version_exists_on_crates_io(crate_name: &str, version: &str) -> Result<bool>
         .build()
         .map_err(|e| SampoError::Publish(format!("failed to build HTTP client: {}", e)))?;
 
-    let res = client
-        .get(&url)
-        .send()
-        .map_err(|e| SampoError::Publish(format!("HTTP request failed: {}", e)))?;
+    let res = client.get(&url).send().map_err(|e| {
+        classify_request_error(
+            &e,
+            SampoError::Publish(format!("HTTP request failed: {}", e)),
+        )
+    })?;
 
     let status = res.status();
     if status == reqwest::StatusCode::OK {
@@ -474,10 +590,10 @@ fn version_exists_on_crates_io(crate_name: &str, version: &str) -> Result<bool>
             format!(" body=\"{}\"", snippet)
         };
 
-        Err(SampoError::Publish(format!(
-            "Crates.io {} response:{}",
-            status, body_part
-        )))
+        Err(classify_response_status(
+            status,
+            SampoError::Publish(format!("Crates.io {} response:{}", status, body_part)),
+        ))
     }
 }
 
@@ -1088,6 +1088,25 @@ mod private_registries {
         );
     }
 
+    #[test]
+    fn yank_args_target_the_publish_registry() {
+        assert_eq!(
+            yank_args("my-crate", "1.2.3", None),
+            ["yank", "--version", "1.2.3", "my-crate"]
+        );
+        assert_eq!(
+            yank_args("my-crate", "1.2.3", Some("my-registry")),
+            [
+                "yank",
+                "--version",
+                "1.2.3",
+                "--registry",
+                "my-registry",
+                "my-crate"
+            ]
+        );
+    }
+
     #[test]
     fn interpret_cargo_info_reports_existing_version() {
         assert!(interpret_cargo_info(true, "", "my-crate@1.2.3", "my-registry").unwrap());
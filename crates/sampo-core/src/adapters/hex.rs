@@ -1,6 +1,10 @@
-use crate::adapters::require_on_path;
 use crate::adapters::scan::LazyScan;
+use crate::adapters::{
+    YankRequest, classify_request_error, classify_response_status, require_on_path,
+    run_yank_command,
+};
 use crate::errors::{Result, SampoError, WorkspaceError};
+use crate::process::command;
 use crate::types::PackageInfo;
 use reqwest::StatusCode;
 use reqwest::blocking::Client;
@@ -10,6 +14,9 @@ use std::sync::{Mutex, OnceLock};
 use std::thread;
 use std::time::{Duration, Instant};
 
+/// How the Hex clients report network and registry failures worth a retry.
+const HEX_TRANSIENT_OUTPUT: &[&str] = &["failed_connect", ":timeout", "Service Unavailable"];
+
 mod gleam;
 mod mix;
 mod rebar3;
@@ -126,10 +133,13 @@ impl HexAdapter {
         let response = version_check_request(&client, &url, api_key.as_deref())
             .send()
             .map_err(|e| {
-                SampoError::Publish(format!(
-                    "failed to query Hex registry for '{}': {}",
-                    name, e
-                ))
+                classify_request_error(
+                    &e,
+                    SampoError::Publish(format!(
+                        "failed to query Hex registry for '{}': {}",
+                        name, e
+                    )),
+                )
             })?;
 
         let status_code = response.status();
@@ -164,7 +174,8 @@ impl HexAdapter {
                 Err(SampoError::Publish(format!(
                     "Hex registry returned 429 Too Many Requests for '{}@{}'.{}",
                     name, version, retry_after
-                )))
+                ))
+                .retryable())
             }
             StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
                 // With a key present the request was authenticated and still rejected, so the
@@ -188,10 +199,13 @@ impl HexAdapter {
                 } else {
                     format!(" body=\"{}\"", snippet)
                 };
-                Err(SampoError::Publish(format!(
-                    "Hex registry returned {} for '{}@{}'{}",
-                    other, name, version, body_part
-                )))
+                Err(classify_response_status(
+                    other,
+                    SampoError::Publish(format!(
+                        "Hex registry returned {} for '{}@{}'{}",
+                        other, name, version, body_part
+                    )),
+                ))
             }
         }
     }
@@ -211,6 +225,22 @@ impl HexAdapter {
         }
     }
 
+    pub(super) fn yank(&self, manifest_path: &Path, request: &YankRequest) -> Result<()> {
+        let (program, args) = retire_command(manifest_path, request);
+        let mut cmd = command(program);
+        cmd.args(args);
+        // rebar3 runs from the application root, above `src/`.
+        let dir = if is_rebar_manifest(manifest_path) {
+            manifest_path.parent().and_then(Path::parent)
+        } else {
+            manifest_path.parent()
+        };
+        if let Some(dir) = dir {
+            cmd.current_dir(dir);
+        }
+        run_yank_command(&mut cmd, request.dry_run)
+    }
+
     /// Must mirror the tool selection in `regenerate_lockfile` below.
     pub(super) fn preflight_lockfile_regen(&self, workspace_root: &Path) -> Result<()> {
         if workspace_root.join("mix.exs").exists() {
@@ -247,6 +277,31 @@ fn is_rebar_manifest(manifest_path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+/// The Hex client retiring a release, and its arguments. Hex only takes a reason from a
+/// fixed list, so the release is retired as `other` with the reason as its message.
+fn retire_command(manifest_path: &Path, request: &YankRequest) -> (&'static str, Vec<String>) {
+    let (program, mut args) = if is_gleam_manifest(manifest_path) {
+        ("gleam", vec!["hex".to_string(), "retire".to_string()])
+    } else if is_rebar_manifest(manifest_path) {
+        ("rebar3", vec!["hex".to_string(), "retire".to_string()])
+    } else {
+        ("mix", vec!["hex.retire".to_string()])
+    };
+    args.extend([
+        request.name.to_string(),
+        request.version.to_string(),
+        "other".to_string(),
+    ]);
+    if let Some(reason) = request.reason {
+        // Gleam takes the message as a positional argument.
+        if program != "gleam" {
+            args.push("--message".to_string());
+        }
+        args.push(reason.to_string());
+    }
+    (program, args)
+}
+
 fn registry_url(organization: Option<&str>, name: &str, version: &str) -> String {
     match organization {
         Some(org) => format!("{HEX_API_BASE}/repos/{org}/packages/{name}/releases/{version}"),
@@ -1,5 +1,5 @@
 use crate::adapters::scan::{LazyScan, ScanIndex};
-use crate::adapters::{format_command_display, has_flag};
+use crate::adapters::{format_command_display, has_flag, run_publish_command};
 use crate::errors::{Result, SampoError, WorkspaceError};
 use crate::process::command;
 use crate::types::{PackageInfo, PackageKind};
@@ -8,7 +8,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use toml_edit::DocumentMut;
 
-use super::{GLEAM_MANIFEST, compute_requirement, normalize_path};
+use super::{GLEAM_MANIFEST, HEX_TRANSIENT_OUTPUT, compute_requirement, normalize_path};
 
 /// Gleam's lockfile, regenerated by `gleam deps download`.
 const GLEAM_LOCKFILE: &str = "manifest.toml";
@@ -171,7 +171,7 @@ pub(super) fn publish(manifest_path: &Path, dry_run: bool, extra_args: &[String]
 
     println!("Running: {}", format_command_display(&cmd));
 
-    let status = cmd.status().map_err(|err| {
+    let run = run_publish_command(&mut cmd).map_err(|err| {
         if err.kind() == std::io::ErrorKind::NotFound {
             SampoError::Publish(
                 "gleam not found in PATH; ensure Gleam is installed to publish packages"
@@ -181,18 +181,19 @@ pub(super) fn publish(manifest_path: &Path, dry_run: bool, extra_args: &[String]
             SampoError::Io(err)
         }
     })?;
-    if !status.success() {
+    if !run.succeeded() {
         let action = if dry_run {
             "gleam export hex-tarball"
         } else {
             "gleam publish"
         };
-        return Err(SampoError::Publish(format!(
+        let error = SampoError::Publish(format!(
             "{} failed for {} with status {}",
             action,
             manifest_path.display(),
-            status
-        )));
+            run.status
+        ));
+        return Err(run.classify(error, HEX_TRANSIENT_OUTPUT));
     }
 
     Ok(())
@@ -22,6 +22,46 @@ fn registry_url_uses_public_path_without_organization() {
     assert_eq!(url, "https://hex.pm/api/packages/example/releases/1.0.0");
 }
 
+#[test]
+fn retire_command_follows_the_manifest_tool() {
+    let request = YankRequest {
+        name: "my_pkg",
+        version: "1.2.0",
+        reason: Some("broken build"),
+        unpublish: false,
+        dry_run: false,
+    };
+    assert_eq!(
+        retire_command(Path::new("pkg/mix.exs"), &request),
+        (
+            "mix",
+            vec![
+                "hex.retire".to_string(),
+                "my_pkg".to_string(),
+                "1.2.0".to_string(),
+                "other".to_string(),
+                "--message".to_string(),
+                "broken build".to_string(),
+            ]
+        )
+    );
+
+    let (program, args) = retire_command(Path::new("pkg/gleam.toml"), &request);
+    assert_eq!(program, "gleam");
+    assert_eq!(
+        args,
+        ["hex", "retire", "my_pkg", "1.2.0", "other", "broken build"]
+    );
+
+    let request = YankRequest {
+        reason: None,
+        ..request
+    };
+    let (program, args) = retire_command(Path::new("pkg/src/my_pkg.app.src"), &request);
+    assert_eq!(program, "rebar3");
+    assert_eq!(args, ["hex", "retire", "my_pkg", "1.2.0", "other"]);
+}
+
 #[test]
 fn resolve_hex_api_key_returns_trimmed_value() {
     let key =
@@ -1,4 +1,4 @@
-use crate::adapters::{format_command_display, has_flag};
+use crate::adapters::{format_command_display, has_flag, run_publish_command};
 use crate::errors::{Result, SampoError, WorkspaceError};
 use crate::process::command;
 use crate::types::{PackageInfo, PackageKind};
@@ -7,7 +7,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
-use super::{compute_requirement, normalize_path};
+use super::{HEX_TRANSIENT_OUTPUT, compute_requirement, normalize_path};
 use tree_sitter::{Language, Node, Parser, Tree};
 
 const MIX_MANIFEST: &str = "mix.exs";
@@ -220,7 +220,7 @@ pub(super) fn publish(manifest_path: &Path, dry_run: bool, extra_args: &[String]
 
     println!("Running: {}", format_command_display(&cmd));
 
-    let status = cmd.status().map_err(|err| {
+    let run = run_publish_command(&mut cmd).map_err(|err| {
         if err.kind() == std::io::ErrorKind::NotFound {
             SampoError::Publish(
                 "mix not found in PATH; ensure mix is installed to publish packages".to_string(),
@@ -229,13 +229,14 @@ pub(super) fn publish(manifest_path: &Path, dry_run: bool, extra_args: &[String]
             SampoError::Io(err)
         }
     })?;
-    if !status.success() {
-        return Err(SampoError::Publish(format!(
+    if !run.succeeded() {
+        let error = SampoError::Publish(format!(
             "mix hex.publish failed for {} (package '{}') with status {}",
             manifest_path.display(),
             package,
-            status
-        )));
+            run.status
+        ));
+        return Err(run.classify(error, HEX_TRANSIENT_OUTPUT));
     }
 
     Ok(())
@@ -1,5 +1,5 @@
 use crate::adapters::scan::{LazyScan, ScanIndex};
-use crate::adapters::{format_command_display, has_flag};
+use crate::adapters::{format_command_display, has_flag, run_publish_command};
 use crate::errors::{Result, SampoError, WorkspaceError};
 use crate::process::command;
 use crate::types::{PackageInfo, PackageKind};
@@ -199,7 +199,7 @@ pub(super) fn publish(manifest_path: &Path, dry_run: bool, extra_args: &[String]
 
     println!("Running: {}", format_command_display(&cmd));
 
-    let status = cmd.status().map_err(|err| {
+    let run = run_publish_command(&mut cmd).map_err(|err| {
         if err.kind() == std::io::ErrorKind::NotFound {
             SampoError::Publish(
                 "rebar3 not found in PATH; ensure rebar3 (with the rebar3_hex plugin) is \
@@ -211,12 +211,13 @@ pub(super) fn publish(manifest_path: &Path, dry_run: bool, extra_args: &[String]
         }
     })?;
 
-    if !status.success() {
-        return Err(SampoError::Publish(format!(
+    if !run.succeeded() {
+        let error = SampoError::Publish(format!(
             "rebar3 hex publish failed for {} with status {}",
             manifest_path.display(),
-            status
-        )));
+            run.status
+        ));
+        return Err(run.classify(error, super::HEX_TRANSIENT_OUTPUT));
     }
 
     Ok(())
@@ -1,3 +1,4 @@
+use crate::adapters::{classify_request_error, classify_response_status};
 use crate::errors::{Result, SampoError, WorkspaceError};
 use crate::types::{PackageInfo, PackageKind};
 use reqwest::StatusCode;
@@ -45,6 +46,10 @@ impl MavenAdapter {
         pom::is_publishable(manifest_path)
     }
 
+    pub(super) fn observes_publish(&self, manifest_path: &Path) -> bool {
+        !pom::has_private_deploy_repository(manifest_path)
+    }
+
     pub(super) fn version_exists(
         &self,
         package_name: &str,
@@ -82,10 +87,13 @@ impl MavenAdapter {
         enforce_maven_rate_limit();
 
         let response = client.get(&url).send().map_err(|e| {
-            SampoError::Publish(format!(
-                "failed to query the Maven registry for '{}': {}",
-                name, e
-            ))
+            classify_request_error(
+                &e,
+                SampoError::Publish(format!(
+                    "failed to query the Maven registry for '{}': {}",
+                    name, e
+                )),
+            )
         })?;
 
         let status_code = response.status();
@@ -102,7 +110,8 @@ impl MavenAdapter {
                 Err(SampoError::Publish(format!(
                     "Maven registry returned 429 Too Many Requests for '{}@{}'.{}",
                     name, version, retry_after
-                )))
+                ))
+                .retryable())
             }
             other => {
                 let body = response.text().unwrap_or_default();
@@ -113,10 +122,13 @@ impl MavenAdapter {
                 } else {
                     format!(" body=\"{}\"", snippet)
                 };
-                Err(SampoError::Publish(format!(
-                    "Maven registry returned {} for '{}@{}'{}",
-                    other, name, version, body_part
-                )))
+                Err(classify_response_status(
+                    other,
+                    SampoError::Publish(format!(
+                        "Maven registry returned {} for '{}@{}'{}",
+                        other, name, version, body_part
+                    )),
+                ))
             }
         }
     }
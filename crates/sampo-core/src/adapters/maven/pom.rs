@@ -1,4 +1,4 @@
-use crate::adapters::{format_command_display, has_flag};
+use crate::adapters::{format_command_display, has_flag, run_publish_command};
 use crate::errors::{Result, SampoError, WorkspaceError};
 use crate::process::command;
 use crate::types::{PackageInfo, PackageKind};
@@ -9,6 +9,15 @@ use std::sync::OnceLock;
 
 use tree_sitter::{Language, Node, Parser, Tree};
 
+/// How Maven reports repository responses worth a retry.
+const MAVEN_TRANSIENT_OUTPUT: &[&str] = &[
+    "status code: 429",
+    "status code: 500",
+    "status code: 502",
+    "status code: 503",
+    "status code: 504",
+];
+
 const POM_FILE: &str = "pom.xml";
 
 /// Maven's mutable dev-version marker. Central rejects it on release deployments, and
@@ -258,7 +267,7 @@ pub(super) fn publish(manifest_path: &Path, dry_run: bool, extra_args: &[String]
 
     println!("Running: {}", format_command_display(&cmd));
 
-    let status = cmd.status().map_err(|err| {
+    let run = run_publish_command(&mut cmd).map_err(|err| {
         if err.kind() == std::io::ErrorKind::NotFound {
             SampoError::Publish(
                 "mvn not found in PATH; ensure Apache Maven is installed to publish packages"
@@ -269,14 +278,15 @@ pub(super) fn publish(manifest_path: &Path, dry_run: bool, extra_args: &[String]
         }
     })?;
 
-    if !status.success() {
+    if !run.succeeded() {
         let action = if dry_run { "mvn verify" } else { "mvn deploy" };
-        return Err(SampoError::Publish(format!(
+        let error = SampoError::Publish(format!(
             "{} failed for {} with status {}",
             action,
             manifest_path.display(),
-            status
-        )));
+            run.status
+        ));
+        return Err(run.classify(error, MAVEN_TRANSIENT_OUTPUT));
     }
 
     Ok(())
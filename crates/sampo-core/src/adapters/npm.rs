@@ -1,4 +1,7 @@
-use crate::adapters::{PublishOutcome, format_command_display, has_flag, require_on_path};
+use crate::adapters::{
+    PublishOutcome, YankRequest, classify_request_error, classify_response_status,
+    format_command_display, has_flag, require_on_path, run_publish_command, run_yank_command,
+};
 use crate::errors::{Result, SampoError, WorkspaceError};
 use crate::process::command;
 use crate::types::{PackageInfo, PackageKind};
@@ -14,6 +17,16 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 const DEFAULT_NPM_REGISTRY: &str = "https://registry.npmjs.org/";
+/// npm error codes of registry and network failures worth a retry.
+const NPM_TRANSIENT_OUTPUT: &[&str] = &[
+    "code E429",
+    "code E500",
+    "code E502",
+    "code E503",
+    "code E504",
+    "network request to",
+];
+
 const REGISTRY_RATE_LIMIT: Duration = Duration::from_millis(300);
 
 static REGISTRY_LAST_CALL: OnceLock<Mutex<Option<Instant>>> = OnceLock::new();
@@ -145,7 +158,7 @@ impl NpmAdapter {
 
         println!("Running: {}", format_command_display(&cmd));
 
-        let status = cmd.status().map_err(|err| {
+        let run = run_publish_command(&mut cmd).map_err(|err| {
             if err.kind() == std::io::ErrorKind::NotFound {
                 SampoError::Publish(format!(
                     "{} not found in PATH; ensure {} is installed to publish packages",
@@ -155,23 +168,38 @@ impl NpmAdapter {
                 SampoError::Io(err)
             }
         })?;
-        if !status.success() {
+        if !run.succeeded() {
             let attempted = match yarn {
                 Some(YarnPublish::Berry { .. }) => "yarn npm publish".to_string(),
                 _ => format!("{manager_name} publish"),
             };
-            return Err(SampoError::Publish(format!(
+            let error = SampoError::Publish(format!(
                 "{} failed for {} (package '{}') with status {}",
                 attempted,
                 manifest_path.display(),
                 info.name,
-                status
-            )));
+                run.status
+            ));
+            return Err(run.classify(error, NPM_TRANSIENT_OUTPUT));
         }
 
         Ok(PublishOutcome::Ran)
     }
 
+    /// Deprecates the version, or unpublishes it, which npm only allows within 72 hours
+    /// of the publish. Always runs npm: the other package managers lack one or the other.
+    pub(super) fn yank(&self, manifest_path: &Path, request: &YankRequest) -> Result<()> {
+        let manifest = load_package_json(manifest_path)?;
+        let info = parse_manifest_info(manifest_path, &manifest)?;
+
+        let mut cmd = command("npm");
+        cmd.args(yank_command_args(&info, request));
+        if let Some(dir) = manifest_path.parent() {
+            cmd.current_dir(dir);
+        }
+        run_yank_command(&mut cmd, request.dry_run)
+    }
+
     pub(super) fn regenerate_lockfile(&self, workspace_root: &Path) -> Result<()> {
         regenerate_npm_lockfile(workspace_root)
     }
@@ -654,6 +682,70 @@ fn wants_dry_run(dry_run: bool, extra_args: &[String]) -> bool {
             })
 }
 
+/// Shorthand for the public npm registry in `registries`.
+const NPMJS_REGISTRY: &str = "npmjs";
+
+/// The URL of `registry`, one of the package's `registries`: `npmjs` or a registry URL.
+fn registry_url(registry: &str) -> Result<&str> {
+    if registry == NPMJS_REGISTRY {
+        return Ok(DEFAULT_NPM_REGISTRY);
+    }
+    if registry.starts_with("https://") || registry.starts_with("http://") {
+        return Ok(registry);
+    }
+    Err(SampoError::Publish(format!(
+        "npm registry '{registry}' must be \"{NPMJS_REGISTRY}\" or a registry URL"
+    )))
+}
+
+/// Publish arguments selecting `registry`. A scoped package also overrides its scope's
+/// registry, which npm would otherwise prefer over `--registry`.
+pub(super) fn registry_args(package_name: &str, registry: &str) -> Result<Vec<String>> {
+    let url = registry_url(registry)?;
+    let mut args = vec!["--registry".to_string(), url.to_string()];
+    if let Some((scope, _)) = package_name.split_once('/')
+        && scope.starts_with('@')
+    {
+        args.push(format!("--{scope}:registry={url}"));
+    }
+    Ok(args)
+}
+
+/// Whether `registry`, one of the package's `registries`, serves `package_name@version`.
+pub(super) fn version_exists_on(
+    package_name: &str,
+    version: &str,
+    manifest_path: &Path,
+    registry: &str,
+) -> Result<bool> {
+    version_exists_on_registry(
+        package_name,
+        version,
+        Some(registry_url(registry)?),
+        manifest_path.parent(),
+    )
+}
+
+fn yank_command_args(info: &NpmManifestInfo, request: &YankRequest) -> Vec<String> {
+    let spec = format!("{}@{}", request.name, request.version);
+    let mut args = if request.unpublish {
+        vec!["unpublish".to_string(), spec]
+    } else {
+        // An empty message would lift the deprecation instead.
+        let message = request
+            .reason
+            .map(str::trim)
+            .filter(|reason| !reason.is_empty())
+            .unwrap_or("This version was yanked.");
+        vec!["deprecate".to_string(), spec, message.to_string()]
+    };
+    if let Some(registry) = info.publish_config.registry.as_deref() {
+        args.push("--registry".to_string());
+        args.push(registry.to_string());
+    }
+    args
+}
+
 fn publish_command_args(
     yarn: Option<&YarnPublish>,
     dry_run: bool,
@@ -933,7 +1025,12 @@ fn version_exists_on_registry(
     let token = npm_auth_token();
     let response = version_check_request(&client, url.clone(), token.as_deref())
         .send()
-        .map_err(|err| SampoError::Publish(format!("HTTP request to {} failed: {}", url, err)))?;
+        .map_err(|err| {
+            classify_request_error(
+                &err,
+                SampoError::Publish(format!("HTTP request to {} failed: {}", url, err)),
+            )
+        })?;
 
     let status = response.status();
 
@@ -976,14 +1073,14 @@ fn version_exists_on_registry(
             url,
             retry_after.unwrap_or_default()
         );
-        Err(SampoError::Publish(msg))
+        Err(SampoError::Publish(msg).retryable())
     } else {
         let body = response.text().unwrap_or_default();
         let snippet: String = body.trim().chars().take(400).collect();
-        Err(SampoError::Publish(format!(
-            "Registry {} returned {}: {}",
-            url, status, snippet
-        )))
+        Err(classify_response_status(
+            status,
+            SampoError::Publish(format!("Registry {} returned {}: {}", url, status, snippet)),
+        ))
     }
 }
 
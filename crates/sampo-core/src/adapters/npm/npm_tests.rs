@@ -1099,6 +1099,49 @@ fn dry_run_gap_is_reported_only_when_yarn_cannot_simulate() {
     );
 }
 
+#[test]
+fn yank_command_args_deprecate_or_unpublish() {
+    let mut info = manifest_info("@scope/pkg");
+    let request = YankRequest {
+        name: "@scope/pkg",
+        version: "1.2.0",
+        reason: Some("broken build"),
+        unpublish: false,
+        dry_run: false,
+    };
+    assert_eq!(
+        super::yank_command_args(&info, &request),
+        ["deprecate", "@scope/pkg@1.2.0", "broken build"]
+    );
+
+    for blank in ["", "  \n"] {
+        let request = YankRequest {
+            reason: Some(blank),
+            ..request
+        };
+        assert_eq!(
+            super::yank_command_args(&info, &request),
+            ["deprecate", "@scope/pkg@1.2.0", "This version was yanked."]
+        );
+    }
+
+    info.publish_config.registry = Some("https://npm.example.com/".to_string());
+    let request = YankRequest {
+        reason: None,
+        unpublish: true,
+        ..request
+    };
+    assert_eq!(
+        super::yank_command_args(&info, &request),
+        [
+            "unpublish",
+            "@scope/pkg@1.2.0",
+            "--registry",
+            "https://npm.example.com/"
+        ]
+    );
+}
+
 #[test]
 fn publish_command_args_namespace_publishing_under_npm_on_berry_only() {
     let info = manifest_info("pkg");
@@ -1,4 +1,4 @@
-use crate::adapters::format_command_display;
+use crate::adapters::{classify_request_error, classify_response_status, format_command_display};
 use crate::errors::{Result, SampoError, WorkspaceError};
 use crate::process::command;
 use crate::types::{PackageInfo, PackageKind};
@@ -135,10 +135,13 @@ impl PackagistAdapter {
         let url = format!("{}/{}.json", PACKAGIST_API_BASE, name);
 
         let response = client.get(&url).send().map_err(|e| {
-            SampoError::Publish(format!(
-                "failed to query Packagist registry for '{}': {}",
-                name, e
-            ))
+            classify_request_error(
+                &e,
+                SampoError::Publish(format!(
+                    "failed to query Packagist registry for '{}': {}",
+                    name, e
+                )),
+            )
         })?;
 
         let status_code = response.status();
@@ -178,7 +181,8 @@ impl PackagistAdapter {
                 Err(SampoError::Publish(format!(
                     "Packagist registry returned 429 Too Many Requests for '{}@{}'.{}",
                     name, version, retry_after
-                )))
+                ))
+                .retryable())
             }
             other => {
                 let body = response.text().unwrap_or_default();
@@ -189,10 +193,13 @@ impl PackagistAdapter {
                 } else {
                     format!(" body=\"{}\"", snippet)
                 };
-                Err(SampoError::Publish(format!(
-                    "Packagist registry returned {} for '{}@{}'{}",
-                    other, name, version, body_part
-                )))
+                Err(classify_response_status(
+                    other,
+                    SampoError::Publish(format!(
+                        "Packagist registry returned {} for '{}@{}'{}",
+                        other, name, version, body_part
+                    )),
+                ))
             }
         }
     }
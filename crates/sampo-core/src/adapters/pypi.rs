@@ -1,4 +1,5 @@
 use crate::adapters::scan::LazyScan;
+use crate::adapters::{classify_request_error, classify_response_status};
 use crate::errors::{Result, SampoError, WorkspaceError};
 use crate::types::PackageInfo;
 use reqwest::StatusCode;
@@ -61,6 +62,10 @@ impl PyPIAdapter {
         pip::is_publishable(manifest_path)
     }
 
+    pub(super) fn observes_publish(&self, manifest_path: &Path) -> bool {
+        !pip::has_private_publish_index(manifest_path)
+    }
+
     pub(super) fn version_exists(
         &self,
         package_name: &str,
@@ -97,10 +102,13 @@ impl PyPIAdapter {
         enforce_pypi_rate_limit();
 
         let response = client.get(&url).send().map_err(|e| {
-            SampoError::Publish(format!(
-                "failed to query PyPI registry for '{}': {}",
-                name, e
-            ))
+            classify_request_error(
+                &e,
+                SampoError::Publish(format!(
+                    "failed to query PyPI registry for '{}': {}",
+                    name, e
+                )),
+            )
         })?;
 
         let status_code = response.status();
@@ -136,7 +144,8 @@ impl PyPIAdapter {
                 Err(SampoError::Publish(format!(
                     "PyPI registry returned 429 Too Many Requests for '{}@{}'.{}",
                     name, version, retry_after
-                )))
+                ))
+                .retryable())
             }
             StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(SampoError::Publish(format!(
                 "PyPI registry returned {} for '{}@{}'; authentication may be required",
@@ -151,10 +160,13 @@ impl PyPIAdapter {
                 } else {
                     format!(" body=\"{}\"", snippet)
                 };
-                Err(SampoError::Publish(format!(
-                    "PyPI registry returned {} for '{}@{}'{}",
-                    other, name, version, body_part
-                )))
+                Err(classify_response_status(
+                    other,
+                    SampoError::Publish(format!(
+                        "PyPI registry returned {} for '{}@{}'{}",
+                        other, name, version, body_part
+                    )),
+                ))
             }
         }
     }
@@ -164,8 +176,15 @@ impl PyPIAdapter {
         manifest_path: &Path,
         dry_run: bool,
         extra_args: &[String],
+        token: Option<&str>,
     ) -> Result<()> {
-        pip::publish(manifest_path, dry_run, extra_args)
+        pip::publish(manifest_path, dry_run, extra_args, token)
+    }
+
+    /// Whether `uv publish` uploads to PyPI: neither the manifest nor the extra
+    /// arguments select another index.
+    pub(super) fn publishes_to_pypi(&self, manifest_path: &Path, extra_args: &[String]) -> bool {
+        !pip::has_private_publish_index(manifest_path) && !pip::extra_args_select_index(extra_args)
     }
 
     pub(super) fn regenerate_lockfile(&self, workspace_root: &Path) -> Result<()> {
@@ -196,13 +215,35 @@ pub(super) fn check_dependency_constraint(
     pip::check_pep440_constraint(&constraint, new_version)
 }
 
+/// The distributions `uv build` left in the package's `dist` directory.
+pub(super) fn built_distributions(manifest_path: &Path) -> Vec<PathBuf> {
+    let Some(dist) = manifest_path.parent().map(|dir| dir.join("dist")) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dist) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.ends_with(".whl") || name.ends_with(".tar.gz"))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
 pub(super) fn publish_dry_run(
     packages: &[(&PackageInfo, &Path)],
     extra_args: &[String],
 ) -> Result<Vec<String>> {
     for (package, manifest) in packages {
         PyPIAdapter
-            .publish(manifest, true, extra_args)
+            .publish(manifest, true, extra_args, None)
             .map_err(|err| match err {
                 SampoError::Publish(message) => SampoError::Publish(format!(
                     "Dry-run publish failed for {}: {}",
@@ -1,5 +1,5 @@
-use crate::adapters::format_command_display;
 use crate::adapters::scan::{LazyScan, ScanIndex};
+use crate::adapters::{format_command_display, run_publish_command};
 use crate::errors::{Result, SampoError, WorkspaceError};
 use crate::types::{PackageInfo, PackageKind};
 use std::collections::{BTreeMap, BTreeSet};
@@ -8,6 +8,9 @@ use std::path::{Component, Path, PathBuf};
 use std::process::Command;
 use toml_edit::{DocumentMut, Item, Value};
 
+/// How uv reports upload failures worth a retry.
+const UV_TRANSIENT_OUTPUT: &[&str] = &["error sending request", "Upload failed with status code 5"];
+
 const PYPROJECT_MANIFEST: &str = "pyproject.toml";
 
 /// Names that exclude a whole subtree from pip's scan, beyond the shared set:
@@ -333,7 +336,12 @@ pub(super) fn is_publishable(manifest_path: &Path) -> Result<bool> {
     Ok(true)
 }
 
-pub(super) fn publish(manifest_path: &Path, dry_run: bool, extra_args: &[String]) -> Result<()> {
+pub(super) fn publish(
+    manifest_path: &Path,
+    dry_run: bool,
+    extra_args: &[String],
+    token: Option<&str>,
+) -> Result<()> {
     let manifest_dir = manifest_path.parent().ok_or_else(|| {
         SampoError::Publish(format!(
             "Manifest {} does not have a parent directory",
@@ -409,10 +417,13 @@ pub(super) fn publish(manifest_path: &Path, dry_run: bool, extra_args: &[String]
     publish_cmd.arg("publish").current_dir(manifest_dir);
 
     // uv resolves the upload URL, check-url, and credentials from its own
-    // configuration; Sampo handles no secrets.
+    // configuration; Sampo only hands over a token minted by trusted publishing.
     if let Some(index) = &publish_index {
         publish_cmd.arg("--index").arg(index);
     }
+    if let Some(token) = token {
+        publish_cmd.env("UV_PUBLISH_TOKEN", token);
+    }
 
     if !extra_args.is_empty() {
         publish_cmd.args(extra_args);
@@ -420,7 +431,7 @@ pub(super) fn publish(manifest_path: &Path, dry_run: bool, extra_args: &[String]
 
     println!("Running: {}", format_command_display(&publish_cmd));
 
-    let publish_status = publish_cmd.status().map_err(|err| {
+    let run = run_publish_command(&mut publish_cmd).map_err(|err| {
         if err.kind() == std::io::ErrorKind::NotFound {
             SampoError::Publish("uv not found in PATH; install uv to publish packages".to_string())
         } else {
@@ -428,13 +439,14 @@ pub(super) fn publish(manifest_path: &Path, dry_run: bool, extra_args: &[String]
         }
     })?;
 
-    if !publish_status.success() {
-        return Err(SampoError::Publish(format!(
+    if !run.succeeded() {
+        let error = SampoError::Publish(format!(
             "uv publish failed for {} (package '{}') with status {}",
             manifest_path.display(),
             package,
-            publish_status
-        )));
+            run.status
+        ));
+        return Err(run.classify(error, UV_TRANSIENT_OUTPUT));
     }
 
     Ok(())
@@ -828,7 +840,7 @@ fn publish_index_arg(source: &str, manifest_path: &Path) -> Result<Option<String
 ///
 /// `--index` is mutually exclusive with `--publish-url`/`--check-url` in uv, so
 /// Sampo must not inject its own when any of them is present.
-fn extra_args_select_index(extra_args: &[String]) -> bool {
+pub(super) fn extra_args_select_index(extra_args: &[String]) -> bool {
     const INDEX_FLAGS: [&str; 3] = ["--index", "--publish-url", "--check-url"];
     extra_args.iter().any(|arg| {
         INDEX_FLAGS
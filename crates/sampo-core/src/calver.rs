@@ -0,0 +1,145 @@
+use crate::errors::SampoError;
+use chrono::{Datelike, NaiveDate};
+
+/// Date segment of a calendar version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DatePart {
+    /// `YYYY`: full year (`2026`).
+    FullYear,
+    /// `YY`: year since 2000 (`26`).
+    ShortYear,
+    /// `MM`: month without padding (`1` to `12`).
+    Month,
+    /// `WW`: ISO week without padding (`1` to `53`).
+    Week,
+}
+
+impl DatePart {
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "YYYY" => Some(Self::FullYear),
+            "YY" => Some(Self::ShortYear),
+            "MM" => Some(Self::Month),
+            "WW" => Some(Self::Week),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed CalVer format (e.g. `"YYYY.MM.MICRO"`).
+///
+/// Versions must stay valid semver, so a format is a year (`YYYY` or `YY`), a period
+/// (`MM` or `WW`) and the `MICRO` counter, without zero-padded segments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalverFormat {
+    raw: String,
+    year: DatePart,
+    period: DatePart,
+}
+
+impl CalverFormat {
+    pub fn parse(input: &str) -> Result<Self, SampoError> {
+        let trimmed = input.trim();
+        let invalid = || {
+            SampoError::Config(format!(
+                "Unsupported CalVer format '{trimmed}'. Use YYYY or YY, then MM or WW, then MICRO (e.g. 'YYYY.MM.MICRO')."
+            ))
+        };
+
+        let parts: Vec<&str> = trimmed.split('.').collect();
+        let [year, period, "MICRO"] = parts.as_slice() else {
+            return Err(invalid());
+        };
+        let year = DatePart::from_token(year)
+            .filter(|part| matches!(part, DatePart::FullYear | DatePart::ShortYear))
+            .ok_or_else(invalid)?;
+        let period = DatePart::from_token(period)
+            .filter(|part| matches!(part, DatePart::Month | DatePart::Week))
+            .ok_or_else(invalid)?;
+
+        Ok(Self {
+            raw: trimmed.to_string(),
+            year,
+            period,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// The `(major, minor)` pair of versions released on `date`.
+    ///
+    /// Weekly formats use the ISO week-numbering year so the pair never goes backwards
+    /// around New Year.
+    pub fn period(&self, date: NaiveDate) -> (u64, u64) {
+        let (year, period) = match self.period {
+            DatePart::Week => {
+                let week = date.iso_week();
+                (week.year(), week.week())
+            }
+            _ => (date.year(), date.month()),
+        };
+        let year = match self.year {
+            DatePart::ShortYear => year - 2000,
+            _ => year,
+        };
+        (year.max(0) as u64, u64::from(period))
+    }
+}
+
+impl std::fmt::Display for CalverFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_supported_formats() {
+        for format in [
+            "YYYY.MM.MICRO",
+            "YY.MM.MICRO",
+            "YYYY.WW.MICRO",
+            "YY.WW.MICRO",
+        ] {
+            assert_eq!(CalverFormat::parse(format).unwrap().as_str(), format);
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_formats() {
+        for format in [
+            "YYYY.0M.MICRO",
+            "YYYY.MM",
+            "MM.YYYY.MICRO",
+            "YYYY.MM.DD",
+            "",
+        ] {
+            let err = CalverFormat::parse(format).unwrap_err();
+            assert!(err.to_string().contains("Unsupported CalVer format"));
+        }
+    }
+
+    #[test]
+    fn computes_period_from_date() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let monthly = CalverFormat::parse("YYYY.MM.MICRO").unwrap();
+        assert_eq!(monthly.period(date), (2026, 10));
+        let short = CalverFormat::parse("YY.MM.MICRO").unwrap();
+        assert_eq!(short.period(date), (26, 10));
+        let weekly = CalverFormat::parse("YYYY.WW.MICRO").unwrap();
+        assert_eq!(weekly.period(date), (2026, 42));
+    }
+
+    #[test]
+    fn weekly_period_uses_iso_year() {
+        // 2027-01-01 still belongs to ISO week 53 of 2026.
+        let weekly = CalverFormat::parse("YYYY.WW.MICRO").unwrap();
+        let date = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap();
+        assert_eq!(weekly.period(date), (2026, 53));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::errors::{Result, SampoError};
 use crate::types::{Bump, PackageSpecifier, ParsedChangeType};
 use changesets::Change;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -11,6 +12,9 @@ pub struct ChangesetInfo {
     /// (package, bump, optional_tag) tuples parsed from frontmatter.
     /// The tag is used for changelog categorization when custom tags are enabled.
     pub entries: Vec<(PackageSpecifier, Bump, Option<String>)>,
+    /// Exact next versions requested with `package: =<version>`. Those entries carry a
+    /// placeholder bump in `entries`, inferred from the current version at release time.
+    pub target_versions: BTreeMap<PackageSpecifier, String>,
     pub message: String,
 }
 
@@ -52,15 +56,19 @@ pub fn parse_changeset(
 
     // Convert Change.versioning -> Vec<(PackageSpecifier, Bump, Option<String>)>
     let mut entries: Vec<(PackageSpecifier, Bump, Option<String>)> = Vec::new();
+    let mut target_versions = BTreeMap::new();
     for (package_name, change_type) in change.versioning.iter() {
-        let (bump, tag) = parse_change_type(change_type, package_name, allowed_tags)?;
+        let parsed = parse_change_type(change_type, package_name, allowed_tags)?;
         let spec = PackageSpecifier::parse(package_name).map_err(|reason| {
             SampoError::Changeset(format!(
                 "Invalid package reference '{}': {reason}",
                 package_name
             ))
         })?;
-        entries.push((spec, bump, tag));
+        if let Some(version) = parsed.version {
+            target_versions.insert(spec.clone(), version);
+        }
+        entries.push((spec, parsed.bump, parsed.tag));
     }
     if entries.is_empty() {
         return Ok(None);
@@ -74,27 +82,33 @@ pub fn parse_changeset(
     Ok(Some(ChangesetInfo {
         path: path.to_path_buf(),
         entries,
+        target_versions,
         message,
     }))
 }
 
-/// Parse a changesets::ChangeType into (Bump, Option<String>).
+/// Parse a changesets::ChangeType into a [`ParsedChangeType`].
 ///
 /// For standard types (Patch, Minor, Major), returns the corresponding bump with no tag.
-/// For Custom types, attempts to parse as "bump (Tag)" format.
+/// For Custom types, attempts to parse as "bump (Tag)" or "=version" format.
 fn parse_change_type(
     change_type: &changesets::ChangeType,
     package_name: &str,
     allowed_tags: &[String],
-) -> Result<(Bump, Option<String>)> {
+) -> Result<ParsedChangeType> {
+    let standard = |bump| ParsedChangeType {
+        bump,
+        tag: None,
+        version: None,
+    };
     match change_type {
-        changesets::ChangeType::Patch => Ok((Bump::Patch, None)),
-        changesets::ChangeType::Minor => Ok((Bump::Minor, None)),
-        changesets::ChangeType::Major => Ok((Bump::Major, None)),
+        changesets::ChangeType::Patch => Ok(standard(Bump::Patch)),
+        changesets::ChangeType::Minor => Ok(standard(Bump::Minor)),
+        changesets::ChangeType::Major => Ok(standard(Bump::Major)),
         changesets::ChangeType::Custom(custom_str) => {
             // Try to parse as "bump (Tag)" format
             match ParsedChangeType::parse(custom_str, allowed_tags) {
-                Ok(parsed) => Ok((parsed.bump, parsed.tag)),
+                Ok(parsed) => Ok(parsed),
                 Err(parse_err) => {
                     // If custom tags are enabled, give a helpful error
                     if !allowed_tags.is_empty() {
@@ -188,6 +202,36 @@ pub fn render_changeset_markdown_with_tags(
     out
 }
 
+/// Render a subset of a changeset's entries (all of them, or the ones kept pending),
+/// preserving the explicit target versions of `changeset`.
+pub fn render_changeset_subset(
+    changeset: &ChangesetInfo,
+    entries: &[(PackageSpecifier, Bump, Option<String>)],
+) -> String {
+    use std::fmt::Write as _;
+    let mut out = String::new();
+    out.push_str("---\n");
+    for (package, bump, tag) in entries {
+        let canonical = package.to_canonical_string();
+        let level = match changeset.target_versions.get(package) {
+            Some(version) => format!("={}", version),
+            None => bump.to_string(),
+        };
+        match tag {
+            Some(t) => {
+                let _ = writeln!(out, "{}: {} ({})", canonical, level, t);
+            }
+            None => {
+                let _ = writeln!(out, "{}: {}", canonical, level);
+            }
+        }
+    }
+    out.push_str("---\n\n");
+    out.push_str(&changeset.message);
+    out.push('\n');
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -423,11 +467,35 @@ mod tests {
     #[test]
     fn parse_changeset_missing_release() {
         // Non-semver change type should be rejected by our wrapper
-        let text = "---\n\"test\": none\n---\n\nNo release type\n";
+        let text = "---\n\"test\": huge\n---\n\nNo release type\n";
         let path = Path::new("/tmp/no-release.md");
         assert!(parse_changeset(text, path, &[]).is_err());
     }
 
+    #[test]
+    fn parse_changeset_none_release() {
+        let text = "---\ntest: none\n---\n\ndocs: clarify usage\n";
+        let path = Path::new("/tmp/none-release.md");
+        let changeset = parse_changeset(text, path, &[]).unwrap().unwrap();
+        assert_eq!(changeset.entries[0].1, Bump::None);
+    }
+
+    #[test]
+    fn parse_changeset_explicit_version_round_trips() {
+        let text = "---\ncargo/test: =1.0.0\n---\n\nfeat: stable API\n";
+        let path = Path::new("/tmp/explicit-version.md");
+        let changeset = parse_changeset(text, path, &[]).unwrap().unwrap();
+        let spec = PackageSpecifier::parse("cargo/test").unwrap();
+        assert_eq!(
+            changeset.target_versions.get(&spec).map(String::as_str),
+            Some("1.0.0")
+        );
+        assert_eq!(
+            render_changeset_subset(&changeset, &changeset.entries),
+            text
+        );
+    }
+
     #[test]
     fn parse_changeset_empty_message() {
         let text = "---\ntest: patch\n---\n\n";
@@ -461,6 +529,11 @@ mod tests {
         assert_eq!(Bump::try_from(ChangeType::Minor), Ok(Bump::Minor));
         assert_eq!(Bump::try_from(ChangeType::Major), Ok(Bump::Major));
 
+        assert_eq!(
+            Bump::try_from(ChangeType::Custom("none".to_string())),
+            Ok(Bump::None)
+        );
+
         // Test rejection of custom types
         assert!(Bump::try_from(ChangeType::Custom("custom".to_string())).is_err());
     }
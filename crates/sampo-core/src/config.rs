@@ -1,17 +1,460 @@
+use crate::calver::CalverFormat;
 use crate::errors::SampoError;
-use crate::tag_template::TagTemplate;
-use crate::types::PackageKind;
+use crate::hooks::Hooks;
+use crate::tag_template::{TagMatch, TagTemplate, package_slug};
+use crate::types::{PackageInfo, PackageKind, PackageSpecifier};
+use crate::version_files::VersionFile;
 use rustc_hash::FxHashSet;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
+use std::time::Duration;
 
 /// Stays narrow by default; cross-ecosystem same-name conflicts are caught at
 /// publish time and surface a hint suggesting `{ecosystem}` in the template.
 pub const DEFAULT_TAG_FORMAT: &str = "{package_name}-v{version}";
 
+/// Changelog file written in each package directory.
+pub const DEFAULT_CHANGELOG_PATH: &str = "CHANGELOG.md";
+
 /// Used by packages selected via `git.short_tags` (Packagist requires `vX.Y.Z`).
 pub const DEFAULT_SHORT_TAGS_FORMAT: &str = "v{version}";
 
+/// Message of the annotated tags created on publish.
+pub const DEFAULT_TAG_MESSAGE: &str = "Release {package_name} {version}";
+
+/// Message of the commits created by `sampo release --commit`.
+pub const DEFAULT_RELEASE_COMMIT_MESSAGE: &str = "chore(release): {releases}";
+
+/// Seconds `sampo publish` waits for a published version to become visible on its registry.
+pub const DEFAULT_PUBLISH_VERIFY_TIMEOUT: u64 = 300;
+
+/// How many times `sampo publish` retries a registry request or publish that failed
+/// for a transient reason.
+pub const DEFAULT_PUBLISH_RETRIES: u32 = 3;
+
+/// Directory, relative to the workspace root, where `sampo publish` writes provenance statements.
+pub const DEFAULT_PROVENANCE_DIR: &str = ".sampo/provenance";
+
+/// How bump levels apply to `0.x` versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ZeroMajorPolicy {
+    /// Bump levels apply as-is: a `major` changeset on `0.4.2` releases `1.0.0`.
+    #[default]
+    Major,
+    /// Stay on `0.x`: breaking changes bump minor (`0.5.0`), features bump patch.
+    Minor,
+}
+
+impl ZeroMajorPolicy {
+    fn parse(value: &toml::Value, key: &str) -> Result<Self, SampoError> {
+        match value.as_str().map(str::trim) {
+            Some("major") => Ok(Self::Major),
+            Some("minor") => Ok(Self::Minor),
+            _ => Err(SampoError::Config(format!(
+                "{key} must be \"major\" or \"minor\", found {value}"
+            ))),
+        }
+    }
+}
+
+/// How a release propagates to the packages that depend on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CascadePolicy {
+    /// Dependents get a patch bump.
+    #[default]
+    Patch,
+    /// Dependents are bumped only when their constraint no longer accepts the new version.
+    None,
+    /// Dependents get the same bump level as the dependency.
+    Match,
+}
+
+impl CascadePolicy {
+    fn parse(value: &toml::Value, key: &str) -> Result<Self, SampoError> {
+        match value.as_str().map(str::trim) {
+            Some("patch") => Ok(Self::Patch),
+            Some("none") => Ok(Self::None),
+            Some("match") => Ok(Self::Match),
+            _ => Err(SampoError::Config(format!(
+                "{key} must be \"patch\", \"none\" or \"match\", found {value}"
+            ))),
+        }
+    }
+}
+
+/// How `sampo publish` finds out whether a package version was already published,
+/// from `publish.existence_check`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExistenceCheck {
+    /// Ask the package registry.
+    #[default]
+    Registry,
+    /// Trust the release tags: a tagged version counts as published, and no registry
+    /// is queried.
+    Tags,
+    /// Ask the registry, and warn when it disagrees with the release tags.
+    Both,
+}
+
+impl ExistenceCheck {
+    fn parse(value: &toml::Value, key: &str) -> Result<Self, SampoError> {
+        match value.as_str().map(str::trim) {
+            Some("registry") => Ok(Self::Registry),
+            Some("tags") => Ok(Self::Tags),
+            Some("both") => Ok(Self::Both),
+            _ => Err(SampoError::Config(format!(
+                "{key} must be \"registry\", \"tags\" or \"both\", found {value}"
+            ))),
+        }
+    }
+}
+
+/// How release tags are signed, from `git.sign_tags`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagSigning {
+    /// `true`: whatever git is configured for (`gpg.format`, `user.signingkey`).
+    Git,
+    /// OpenPGP signatures, regardless of `gpg.format`.
+    Gpg,
+    /// SSH signatures, regardless of `gpg.format`.
+    Ssh,
+}
+
+impl TagSigning {
+    fn parse(value: &toml::Value) -> Result<Option<Self>, SampoError> {
+        let signing = match value {
+            toml::Value::Boolean(true) => Some(Some(Self::Git)),
+            toml::Value::Boolean(false) => Some(None),
+            toml::Value::String(format) => match format.trim() {
+                "gpg" => Some(Some(Self::Gpg)),
+                "ssh" => Some(Some(Self::Ssh)),
+                _ => None,
+            },
+            _ => None,
+        };
+        signing.ok_or_else(|| {
+            SampoError::Config(format!(
+                "git.sign_tags must be true, false, \"gpg\" or \"ssh\", found {value}"
+            ))
+        })
+    }
+
+    /// The `gpg.format` git should sign with, or `None` to keep the configured one.
+    pub fn gpg_format(&self) -> Option<&'static str> {
+        match self {
+            Self::Git => None,
+            Self::Gpg => Some("openpgp"),
+            Self::Ssh => Some("ssh"),
+        }
+    }
+}
+
+/// A moving tag kept on the latest release of a version line, from `git.floating_tags`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatingTag {
+    /// `v2`, following the latest `2.x.y`.
+    Major,
+    /// `v2.3`, following the latest `2.3.y`.
+    Minor,
+}
+
+impl FloatingTag {
+    fn parse_list(value: &toml::Value, key: &str) -> Result<Vec<Self>, SampoError> {
+        let invalid = || {
+            SampoError::Config(format!(
+                "{key} must be an array of \"major\" and \"minor\", found {value}"
+            ))
+        };
+        let items = value.as_array().ok_or_else(invalid)?;
+        let mut tags = Vec::new();
+        for item in items {
+            let tag = match item.as_str().map(str::trim) {
+                Some("major") => Self::Major,
+                Some("minor") => Self::Minor,
+                _ => return Err(invalid()),
+            };
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        Ok(tags)
+    }
+
+    /// The version this tag floats on for `version` (`2` or `2.3` for `2.3.1`), or
+    /// `None` for pre-releases, which never move floating tags.
+    pub fn version(&self, version: &str) -> Option<String> {
+        let release = version.split('+').next().unwrap_or_default();
+        if release.contains('-') {
+            return None;
+        }
+        let mut parts = release.split('.').filter(|part| !part.is_empty());
+        let major = parts.next()?;
+        match self {
+            Self::Major => Some(major.to_string()),
+            Self::Minor => parts.next().map(|minor| format!("{major}.{minor}")),
+        }
+    }
+}
+
+/// A `[[packages.cascade_groups]]` entry: dependents that follow their own cascade policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CascadeGroup {
+    pub packages: Vec<String>,
+    pub cascade: CascadePolicy,
+}
+
+/// How the next version of a package is computed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum VersioningScheme {
+    /// Semantic versioning: changeset bump levels drive the next version.
+    #[default]
+    Semver,
+    /// Calendar versioning: the release date drives the next version, and bump levels
+    /// only group changelog entries.
+    Calver(CalverFormat),
+}
+
+impl VersioningScheme {
+    fn parse(value: &toml::Value, key: &str) -> Result<Self, SampoError> {
+        let raw = value
+            .as_str()
+            .map(str::trim)
+            .ok_or_else(|| SampoError::Config(format!("{key} must be a string")))?;
+        if raw == "semver" {
+            return Ok(Self::Semver);
+        }
+        match raw.strip_prefix("calver:") {
+            Some(format) => Ok(Self::Calver(CalverFormat::parse(format)?)),
+            None => Err(SampoError::Config(format!(
+                "{key} must be \"semver\" or \"calver:<format>\", found \"{raw}\""
+            ))),
+        }
+    }
+}
+
+/// Settings a `[packages."<package>"]` table overrides for one package.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageOverrides {
+    pub changelog_path: Option<String>,
+    pub changelog_show_commit_hash: Option<bool>,
+    pub changelog_show_acknowledgments: Option<bool>,
+    pub changelog_show_release_date: Option<bool>,
+    pub changelog_release_date_format: Option<String>,
+    pub git_default_branch: Option<String>,
+    pub git_release_branches: Option<Vec<String>>,
+    pub git_tag_format: Option<TagTemplate>,
+    pub git_floating_tags: Option<Vec<FloatingTag>>,
+    pub prerelease_label: Option<String>,
+    pub publish_registries: Option<Vec<String>>,
+    pub versioning_zero_major: Option<ZeroMajorPolicy>,
+    pub versioning_scheme: Option<VersioningScheme>,
+}
+
+impl PackageOverrides {
+    fn parse(package: &str, table: &toml::value::Table) -> Result<Self, SampoError> {
+        let key = |section: &str, name: &str| format!("packages.\"{package}\".{section}.{name}");
+        let section = |name: &str| -> Result<Option<&toml::value::Table>, SampoError> {
+            match table.get(name) {
+                Some(value) => value.as_table().map(Some).ok_or_else(|| {
+                    SampoError::Config(format!("packages.\"{package}\".{name} must be a table"))
+                }),
+                None => Ok(None),
+            }
+        };
+        let string = |t: Option<&toml::value::Table>, section: &str, name: &str| {
+            t.and_then(|t| t.get(name))
+                .map(|v| {
+                    v.as_str().map(|s| s.trim().to_string()).ok_or_else(|| {
+                        SampoError::Config(format!("{} must be a string", key(section, name)))
+                    })
+                })
+                .transpose()
+        };
+        let boolean = |t: Option<&toml::value::Table>, name: &str| {
+            t.and_then(|t| t.get(name))
+                .map(|v| {
+                    v.as_bool().ok_or_else(|| {
+                        SampoError::Config(format!("{} must be a boolean", key("changelog", name)))
+                    })
+                })
+                .transpose()
+        };
+
+        let reject_unknown = |t: &toml::value::Table, prefix: &str, known: &[&str]| match t
+            .keys()
+            .find(|name| !known.contains(&name.as_str()))
+        {
+            Some(name) => Err(SampoError::Config(format!(
+                "Unknown setting {prefix}.{name}; supported settings are {}",
+                known.join(", ")
+            ))),
+            None => Ok(()),
+        };
+        reject_unknown(table, &format!("packages.\"{package}\""), OVERRIDE_SETTINGS)?;
+
+        let changelog = section("changelog")?;
+        let git = section("git")?;
+        if let Some(changelog) = changelog {
+            reject_unknown(
+                changelog,
+                &format!("packages.\"{package}\".changelog"),
+                OVERRIDE_CHANGELOG_SETTINGS,
+            )?;
+        }
+        if let Some(git) = git {
+            reject_unknown(
+                git,
+                &format!("packages.\"{package}\".git"),
+                OVERRIDE_GIT_SETTINGS,
+            )?;
+        }
+
+        let git_release_branches = git
+            .and_then(|t| t.get("release_branches"))
+            .map(|v| {
+                v.as_array()
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(|item| item.as_str())
+                            .map(|s| s.trim())
+                            .filter(|s| !s.is_empty())
+                            .map(|s| s.to_string())
+                            .collect::<Vec<String>>()
+                    })
+                    .ok_or_else(|| {
+                        SampoError::Config(format!(
+                            "{} must be an array",
+                            key("git", "release_branches")
+                        ))
+                    })
+            })
+            .transpose()?;
+
+        let git_tag_format = string(git, "git", "tag_format")?
+            .map(|raw| TagTemplate::parse(&raw))
+            .transpose()?;
+
+        let git_floating_tags = git
+            .and_then(|t| t.get("floating_tags"))
+            .map(|v| FloatingTag::parse_list(v, &key("git", "floating_tags")))
+            .transpose()?;
+
+        let prerelease_label = match table.get("prerelease") {
+            Some(value) => Some(
+                value
+                    .as_str()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .ok_or_else(|| {
+                        SampoError::Config(format!(
+                            "packages.\"{package}\".prerelease must be a non-empty string"
+                        ))
+                    })?,
+            ),
+            None => None,
+        };
+
+        let publish_registries = table
+            .get("registries")
+            .map(|value| {
+                let key = format!("packages.\"{package}\".registries");
+                let items = value.as_array().ok_or_else(|| {
+                    SampoError::Config(format!("{key} must be an array of registry names"))
+                })?;
+                let mut registries: Vec<String> = Vec::new();
+                for item in items {
+                    let name = item
+                        .as_str()
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .ok_or_else(|| {
+                            SampoError::Config(format!("{key} must only contain non-empty strings"))
+                        })?;
+                    if registries.iter().any(|r| r == name) {
+                        return Err(SampoError::Config(format!(
+                            "{key} lists '{name}' more than once"
+                        )));
+                    }
+                    registries.push(name.to_string());
+                }
+                if let Ok(PackageSpecifier {
+                    kind: Some(kind), ..
+                }) = PackageSpecifier::parse(package)
+                    && !kind.supports_registries()
+                {
+                    return Err(SampoError::Config(format!(
+                        "{key} is not supported: {} packages are published to a single registry",
+                        kind.display_name()
+                    )));
+                }
+                Ok(registries)
+            })
+            .transpose()?;
+
+        let versioning_zero_major = table
+            .get("zero_major")
+            .map(|v| ZeroMajorPolicy::parse(v, &format!("packages.\"{package}\".zero_major")))
+            .transpose()?;
+
+        let versioning_scheme = table
+            .get("versioning")
+            .map(|v| VersioningScheme::parse(v, &format!("packages.\"{package}\".versioning")))
+            .transpose()?;
+
+        Ok(Self {
+            changelog_path: string(changelog, "changelog", "path")?,
+            changelog_show_commit_hash: boolean(changelog, "show_commit_hash")?,
+            changelog_show_acknowledgments: boolean(changelog, "show_acknowledgments")?,
+            changelog_show_release_date: boolean(changelog, "show_release_date")?,
+            changelog_release_date_format: string(changelog, "changelog", "release_date_format")?,
+            git_default_branch: string(git, "git", "default_branch")?,
+            git_release_branches,
+            git_tag_format,
+            git_floating_tags,
+            prerelease_label,
+            publish_registries,
+            versioning_zero_major,
+            versioning_scheme,
+        })
+    }
+}
+
+/// Settings a `[packages."<package>"]` table accepts, then those of its `changelog` and
+/// `git` sub-tables.
+const OVERRIDE_SETTINGS: &[&str] = &[
+    "changelog",
+    "git",
+    "prerelease",
+    "registries",
+    "zero_major",
+    "versioning",
+];
+const OVERRIDE_CHANGELOG_SETTINGS: &[&str] = &[
+    "path",
+    "show_commit_hash",
+    "show_acknowledgments",
+    "show_release_date",
+    "release_date_format",
+];
+const OVERRIDE_GIT_SETTINGS: &[&str] = &[
+    "default_branch",
+    "release_branches",
+    "tag_format",
+    "floating_tags",
+];
+
+/// Keys of the `[packages]` table that are not per-package overrides.
+const PACKAGES_SETTINGS: &[&str] = &[
+    "fixed",
+    "linked",
+    "ignore",
+    "ignore_unpublished",
+    "version_files",
+    "cascade",
+    "cascade_groups",
+    "cascade_dev_dependencies",
+];
+
 /// Configuration for Sampo
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -28,6 +471,10 @@ pub struct Config {
     pub changesets_tags: Vec<String>,
     pub fixed_dependencies: Vec<Vec<String>>,
     pub linked_dependencies: Vec<Vec<String>>,
+    pub cascade: CascadePolicy,
+    pub cascade_groups: Vec<CascadeGroup>,
+    /// Whether releasing a package also bumps the packages that only dev-depend on it.
+    pub cascade_dev_dependencies: bool,
     pub ignore_unpublished: bool,
     pub ignore: Vec<String>,
     pub git_default_branch: Option<String>,
@@ -36,6 +483,55 @@ pub struct Config {
     pub git_short_tags: Option<String>,
     pub git_tag_format: TagTemplate,
     pub git_short_tags_format: TagTemplate,
+    /// Whether and how release tags are signed.
+    pub git_sign_tags: Option<TagSigning>,
+    /// Message of release tags; `{changelog}` expands to the package's release notes.
+    pub git_tag_message: String,
+    /// Message of release commits; see [`crate::render_release_commit_message`].
+    pub git_release_commit_message: String,
+    /// Moving tags updated on publish; use [`Config::for_package`] for per-package values.
+    pub git_floating_tags: Vec<FloatingTag>,
+    /// Use [`Config::for_package`] for per-package values.
+    pub versioning_zero_major: ZeroMajorPolicy,
+    /// Use [`Config::for_package`] for per-package values.
+    pub versioning_scheme: VersioningScheme,
+    /// Changelog file, relative to the package directory.
+    pub changelog_path: String,
+    /// Repository-wide changelog listing every release, relative to the repository root.
+    pub changelog_aggregate: Option<String>,
+    /// Package whose version heads the sections of the aggregate changelog.
+    pub changelog_aggregate_version: Option<String>,
+    /// Pre-release label `sampo pre enter` applies when none is given.
+    pub prerelease_label: Option<String>,
+    /// `[packages."<package>"]` tables, keyed by the package reference as written.
+    /// Use [`Config::for_package`] to resolve the settings of one package.
+    pub package_overrides: BTreeMap<String, PackageOverrides>,
+    /// `[[packages.version_files]]` entries, rewritten alongside the manifests.
+    pub version_files: Vec<VersionFile>,
+    /// `[hooks]` commands run around releases and publishes.
+    pub hooks: Hooks,
+    /// `[publish] verify_timeout`, in seconds; see [`Config::publish_verify_timeout`].
+    pub publish_verify_timeout: Option<u64>,
+    /// `[publish.<ecosystem>] verify_timeout` overrides, in seconds.
+    pub publish_ecosystem_verify_timeout: BTreeMap<PackageKind, u64>,
+    /// `[publish] retries`; see [`Config::publish_retries`].
+    pub publish_retries: Option<u32>,
+    /// `[publish.<ecosystem>] retries` overrides.
+    pub publish_ecosystem_retries: BTreeMap<PackageKind, u32>,
+    /// `[publish] existence_check`.
+    pub publish_existence_check: ExistenceCheck,
+    /// `[publish] trusted_publishing`: exchange the CI's OIDC token for short-lived
+    /// crates.io and PyPI publish tokens.
+    pub publish_trusted_publishing: bool,
+    /// `[publish] provenance`: publish npm packages with `--provenance` and write an
+    /// in-toto provenance statement for every published package.
+    pub publish_provenance: bool,
+    /// `[publish] provenance_dir`, relative to the workspace root.
+    pub publish_provenance_dir: String,
+    /// `[packages."<package>"] registries`, once resolved with [`Config::for_package`]:
+    /// the registries the package is published to. Empty publishes to the one its
+    /// manifest selects.
+    pub publish_registries: Vec<String>,
 }
 
 impl Default for Config {
@@ -51,6 +547,9 @@ impl Default for Config {
             changesets_tags: Vec::new(),
             fixed_dependencies: Vec::new(),
             linked_dependencies: Vec::new(),
+            cascade: CascadePolicy::default(),
+            cascade_groups: Vec::new(),
+            cascade_dev_dependencies: true,
             ignore_unpublished: false,
             ignore: Vec::new(),
             git_default_branch: None,
@@ -58,6 +557,28 @@ impl Default for Config {
             git_short_tags: None,
             git_tag_format: TagTemplate::from_static(DEFAULT_TAG_FORMAT),
             git_short_tags_format: TagTemplate::from_static(DEFAULT_SHORT_TAGS_FORMAT),
+            git_sign_tags: None,
+            git_tag_message: DEFAULT_TAG_MESSAGE.to_string(),
+            git_release_commit_message: DEFAULT_RELEASE_COMMIT_MESSAGE.to_string(),
+            git_floating_tags: Vec::new(),
+            versioning_zero_major: ZeroMajorPolicy::default(),
+            versioning_scheme: VersioningScheme::default(),
+            changelog_path: DEFAULT_CHANGELOG_PATH.to_string(),
+            changelog_aggregate: None,
+            changelog_aggregate_version: None,
+            prerelease_label: None,
+            package_overrides: BTreeMap::new(),
+            version_files: Vec::new(),
+            hooks: Hooks::default(),
+            publish_verify_timeout: None,
+            publish_ecosystem_verify_timeout: BTreeMap::new(),
+            publish_retries: None,
+            publish_ecosystem_retries: BTreeMap::new(),
+            publish_existence_check: ExistenceCheck::default(),
+            publish_trusted_publishing: false,
+            publish_provenance: false,
+            publish_provenance_dir: DEFAULT_PROVENANCE_DIR.to_string(),
+            publish_registries: Vec::new(),
         }
     }
 }
@@ -120,6 +641,28 @@ impl Config {
             .filter(|s| !s.is_empty())
             .map(|s| s.to_string());
 
+        let changelog_path = changelog_table
+            .and_then(|t| t.get("path"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| DEFAULT_CHANGELOG_PATH.to_string());
+
+        let changelog_aggregate = changelog_table
+            .and_then(|t| t.get("aggregate"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+
+        let changelog_aggregate_version = changelog_table
+            .and_then(|t| t.get("aggregate_version"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+
         let changesets_table = value.get("changesets").and_then(|v| v.as_table());
 
         let changesets_tags = changesets_table
@@ -190,6 +733,58 @@ impl Config {
             .map_err(SampoError::Config)?
             .unwrap_or_default();
 
+        let packages_table = value.get("packages").and_then(|v| v.as_table());
+
+        let cascade = packages_table
+            .and_then(|t| t.get("cascade"))
+            .map(|v| CascadePolicy::parse(v, "packages.cascade"))
+            .transpose()?
+            .unwrap_or_default();
+
+        let mut cascade_groups = Vec::new();
+        if let Some(groups) = packages_table.and_then(|t| t.get("cascade_groups")) {
+            let groups = groups.as_array().ok_or_else(|| {
+                SampoError::Config("packages.cascade_groups must be an array of tables".into())
+            })?;
+            let mut seen_packages = BTreeSet::new();
+            for (index, group) in groups.iter().enumerate() {
+                let key = format!("packages.cascade_groups[{index}]");
+                let packages = group
+                    .get("packages")
+                    .and_then(|v| v.as_array())
+                    .and_then(|arr| {
+                        arr.iter()
+                            .map(|item| item.as_str().map(|s| s.trim().to_string()))
+                            .collect::<Option<Vec<String>>>()
+                    })
+                    .ok_or_else(|| {
+                        SampoError::Config(format!("{key}.packages must be an array of strings"))
+                    })?;
+                for package in &packages {
+                    if !seen_packages.insert(package.clone()) {
+                        return Err(SampoError::Config(format!(
+                            "Package '{package}' appears in multiple cascade groups. Each package can only belong to one group."
+                        )));
+                    }
+                }
+                let cascade = group
+                    .get("cascade")
+                    .ok_or_else(|| SampoError::Config(format!("{key}.cascade is required")))
+                    .and_then(|v| CascadePolicy::parse(v, &format!("{key}.cascade")))?;
+                cascade_groups.push(CascadeGroup { packages, cascade });
+            }
+        }
+
+        let cascade_dev_dependencies = packages_table
+            .and_then(|t| t.get("cascade_dev_dependencies"))
+            .map(|v| {
+                v.as_bool().ok_or_else(|| {
+                    SampoError::Config("packages.cascade_dev_dependencies must be a boolean".into())
+                })
+            })
+            .transpose()?
+            .unwrap_or(true);
+
         let ignore_unpublished = value
             .get("packages")
             .and_then(|v| v.as_table())
@@ -334,6 +929,181 @@ impl Config {
             None => TagTemplate::from_static(DEFAULT_SHORT_TAGS_FORMAT),
         };
 
+        let git_sign_tags = match git_table.and_then(|t| t.get("sign_tags")) {
+            Some(value) => TagSigning::parse(value)?,
+            None => None,
+        };
+
+        let git_tag_message = match git_table.and_then(|t| t.get("tag_message")) {
+            Some(value) => value
+                .as_str()
+                .ok_or_else(|| SampoError::Config("git.tag_message must be a string".into()))?
+                .to_string(),
+            None => DEFAULT_TAG_MESSAGE.to_string(),
+        };
+
+        let git_release_commit_message =
+            match git_table.and_then(|t| t.get("release_commit_message")) {
+                Some(value) => value
+                    .as_str()
+                    .filter(|message| !message.trim().is_empty())
+                    .ok_or_else(|| {
+                        SampoError::Config(
+                            "git.release_commit_message must be a non-empty string".into(),
+                        )
+                    })?
+                    .to_string(),
+                None => DEFAULT_RELEASE_COMMIT_MESSAGE.to_string(),
+            };
+
+        let git_floating_tags = git_table
+            .and_then(|t| t.get("floating_tags"))
+            .map(|v| FloatingTag::parse_list(v, "git.floating_tags"))
+            .transpose()?
+            .unwrap_or_default();
+
+        let versioning_table = value.get("versioning").and_then(|v| v.as_table());
+        if let Some(package) = versioning_table
+            .and_then(|t| t.get("packages"))
+            .and_then(|v| v.as_table())
+            .and_then(|packages| packages.keys().next())
+        {
+            return Err(SampoError::Config(format!(
+                "versioning.packages is no longer supported; move the settings of \
+                 [versioning.packages.\"{package}\"] to [packages.\"{package}\"] \
+                 (zero_major, and versioning for the scheme)"
+            )));
+        }
+
+        let versioning_zero_major = versioning_table
+            .and_then(|t| t.get("zero_major"))
+            .map(|v| ZeroMajorPolicy::parse(v, "versioning.zero_major"))
+            .transpose()?
+            .unwrap_or_default();
+
+        let versioning_scheme = versioning_table
+            .and_then(|t| t.get("scheme"))
+            .map(|v| VersioningScheme::parse(v, "versioning.scheme"))
+            .transpose()?
+            .unwrap_or_default();
+
+        let mut package_overrides = BTreeMap::new();
+        if let Some(packages) = value.get("packages").and_then(|v| v.as_table()) {
+            for (package, settings) in packages {
+                if PACKAGES_SETTINGS.contains(&package.as_str()) {
+                    continue;
+                }
+                // Overrides are keyed by canonical identifier, which keeps them apart from
+                // the settings above whatever the packages are named.
+                let canonical =
+                    PackageSpecifier::parse(package).is_ok_and(|spec| spec.kind.is_some());
+                if !canonical {
+                    return Err(SampoError::Config(format!(
+                        "Unknown setting packages.{package}; per-package settings go in a \
+                         [packages.\"<ecosystem>/<name>\"] table such as [packages.\"cargo/{package}\"]"
+                    )));
+                }
+                let table = settings.as_table().ok_or_else(|| {
+                    SampoError::Config(format!("packages.\"{package}\" must be a table"))
+                })?;
+                package_overrides.insert(package.clone(), PackageOverrides::parse(package, table)?);
+            }
+        }
+
+        let version_files = match value.get("packages").and_then(|v| v.get("version_files")) {
+            Some(entries) => entries
+                .as_array()
+                .ok_or_else(|| {
+                    SampoError::Config("packages.version_files must be an array of tables".into())
+                })?
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| VersionFile::parse(entry, index))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        let hooks = match value.get("hooks") {
+            Some(hooks) => Hooks::parse(
+                hooks
+                    .as_table()
+                    .ok_or_else(|| SampoError::Config("hooks must be a table".into()))?,
+            )?,
+            None => Hooks::default(),
+        };
+
+        let publish_table = value.get("publish").and_then(|v| v.as_table());
+
+        let publish_verify_timeout = publish_table
+            .and_then(|t| t.get("verify_timeout"))
+            .map(|v| parse_seconds(v, "publish.verify_timeout"))
+            .transpose()?;
+
+        let publish_retries = publish_table
+            .and_then(|t| t.get("retries"))
+            .map(|v| parse_retries(v, "publish.retries"))
+            .transpose()?;
+
+        let publish_existence_check = publish_table
+            .and_then(|t| t.get("existence_check"))
+            .map(|v| ExistenceCheck::parse(v, "publish.existence_check"))
+            .transpose()?
+            .unwrap_or_default();
+
+        let publish_trusted_publishing = publish_table
+            .and_then(|t| t.get("trusted_publishing"))
+            .map(|v| {
+                v.as_bool().ok_or_else(|| {
+                    SampoError::Config("publish.trusted_publishing must be a boolean".into())
+                })
+            })
+            .transpose()?
+            .unwrap_or(false);
+
+        let publish_provenance = publish_table
+            .and_then(|t| t.get("provenance"))
+            .map(|v| {
+                v.as_bool().ok_or_else(|| {
+                    SampoError::Config("publish.provenance must be a boolean".into())
+                })
+            })
+            .transpose()?
+            .unwrap_or(false);
+
+        let publish_provenance_dir = publish_table
+            .and_then(|t| t.get("provenance_dir"))
+            .map(|v| {
+                v.as_str()
+                    .filter(|dir| !dir.trim().is_empty())
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        SampoError::Config(
+                            "publish.provenance_dir must be a non-empty string".into(),
+                        )
+                    })
+            })
+            .transpose()?
+            .unwrap_or_else(|| DEFAULT_PROVENANCE_DIR.to_string());
+
+        let mut publish_ecosystem_verify_timeout = BTreeMap::new();
+        let mut publish_ecosystem_retries = BTreeMap::new();
+        for (ecosystem, settings) in publish_table.into_iter().flatten() {
+            let Some(settings) = settings.as_table() else {
+                continue;
+            };
+            let kind = PackageKind::parse(ecosystem).ok_or_else(|| {
+                SampoError::Config(format!("publish.{ecosystem} is not a known ecosystem"))
+            })?;
+            if let Some(value) = settings.get("verify_timeout") {
+                let key = format!("publish.{ecosystem}.verify_timeout");
+                publish_ecosystem_verify_timeout.insert(kind, parse_seconds(value, &key)?);
+            }
+            if let Some(value) = settings.get("retries") {
+                let key = format!("publish.{ecosystem}.retries");
+                publish_ecosystem_retries.insert(kind, parse_retries(value, &key)?);
+            }
+        }
+
         Ok(Self {
             version,
             github_repository,
@@ -345,6 +1115,9 @@ impl Config {
             changesets_tags,
             fixed_dependencies,
             linked_dependencies,
+            cascade,
+            cascade_groups,
+            cascade_dev_dependencies,
             ignore_unpublished,
             ignore,
             git_default_branch,
@@ -352,9 +1125,114 @@ impl Config {
             git_short_tags,
             git_tag_format,
             git_short_tags_format,
+            git_sign_tags,
+            git_tag_message,
+            git_release_commit_message,
+            git_floating_tags,
+            versioning_zero_major,
+            versioning_scheme,
+            changelog_path,
+            changelog_aggregate,
+            changelog_aggregate_version,
+            prerelease_label: None,
+            package_overrides,
+            version_files,
+            hooks,
+            publish_verify_timeout,
+            publish_ecosystem_verify_timeout,
+            publish_retries,
+            publish_ecosystem_retries,
+            publish_existence_check,
+            publish_trusted_publishing,
+            publish_provenance,
+            publish_provenance_dir,
+            publish_registries: Vec::new(),
+        })
+    }
+
+    /// How long `sampo publish` polls the registry for a version it just published
+    /// before tagging it; zero skips the check. Maven Central can take half an hour
+    /// to serve a release, so Maven packages are not checked unless configured.
+    pub fn publish_verify_timeout(&self, kind: PackageKind) -> Duration {
+        let default = match kind {
+            PackageKind::Maven => 0,
+            _ => DEFAULT_PUBLISH_VERIFY_TIMEOUT,
+        };
+        let seconds = self
+            .publish_ecosystem_verify_timeout
+            .get(&kind)
+            .copied()
+            .or(self.publish_verify_timeout)
+            .unwrap_or(default);
+        Duration::from_secs(seconds)
+    }
+
+    /// How many times `sampo publish` retries a registry lookup or a publish command of
+    /// the ecosystem after a transient failure, such as a 5xx answer or a timeout.
+    pub fn publish_retries(&self, kind: PackageKind) -> u32 {
+        self.publish_ecosystem_retries
+            .get(&kind)
+            .copied()
+            .or(self.publish_retries)
+            .unwrap_or(DEFAULT_PUBLISH_RETRIES)
+    }
+
+    /// The `[packages."<ecosystem>/<name>"]` overrides of a package, if any.
+    fn overrides_for(&self, kind: PackageKind, name: &str) -> Option<&PackageOverrides> {
+        self.package_overrides.iter().find_map(|(key, overrides)| {
+            let spec = PackageSpecifier::parse(key).ok()?;
+            (spec.kind == Some(kind) && spec.name == name).then_some(overrides)
         })
     }
 
+    /// Resolve the settings of one package, with its `[packages."<package>"]` overrides
+    /// applied on top of the global configuration.
+    pub fn for_package(&self, info: &PackageInfo) -> Config {
+        let mut resolved = self.clone();
+        if let Some(overrides) = self.overrides_for(info.kind, &info.name) {
+            if let Some(path) = &overrides.changelog_path {
+                resolved.changelog_path = path.clone();
+            }
+            if let Some(value) = overrides.changelog_show_commit_hash {
+                resolved.changelog_show_commit_hash = value;
+            }
+            if let Some(value) = overrides.changelog_show_acknowledgments {
+                resolved.changelog_show_acknowledgments = value;
+            }
+            if let Some(value) = overrides.changelog_show_release_date {
+                resolved.changelog_show_release_date = value;
+            }
+            if let Some(format) = &overrides.changelog_release_date_format {
+                resolved.changelog_release_date_format = format.clone();
+            }
+            if let Some(branch) = &overrides.git_default_branch {
+                resolved.git_default_branch = Some(branch.clone());
+            }
+            if let Some(branches) = &overrides.git_release_branches {
+                resolved.git_release_branches = branches.clone();
+            }
+            if let Some(template) = &overrides.git_tag_format {
+                resolved.git_tag_format = template.clone();
+            }
+            if let Some(tags) = &overrides.git_floating_tags {
+                resolved.git_floating_tags = tags.clone();
+            }
+            if let Some(label) = &overrides.prerelease_label {
+                resolved.prerelease_label = Some(label.clone());
+            }
+            if let Some(registries) = &overrides.publish_registries {
+                resolved.publish_registries = registries.clone();
+            }
+            if let Some(policy) = overrides.versioning_zero_major {
+                resolved.versioning_zero_major = policy;
+            }
+            if let Some(scheme) = &overrides.versioning_scheme {
+                resolved.versioning_scheme = scheme.clone();
+            }
+        }
+        resolved
+    }
+
     pub fn default_branch(&self) -> &str {
         self.git_default_branch.as_deref().unwrap_or("main")
     }
@@ -381,26 +1259,120 @@ impl Config {
             .is_some_and(|name| name == package_name)
     }
 
-    /// Returns the template that applies to the given package.
-    pub fn tag_template_for(&self, package_name: &str) -> &TagTemplate {
-        if self.uses_short_tags(package_name) {
+    /// Returns the template that applies to the given package: its own
+    /// `[packages."<package>".git] tag_format`, else the short or regular template.
+    pub fn tag_template_for(&self, kind: PackageKind, package_name: &str) -> &TagTemplate {
+        if let Some(template) = self
+            .overrides_for(kind, package_name)
+            .and_then(|overrides| overrides.git_tag_format.as_ref())
+        {
+            template
+        } else if self.uses_short_tags(package_name) {
             &self.git_short_tags_format
         } else {
             &self.git_tag_format
         }
     }
 
-    /// Builds a git tag name for the given package and version.
+    /// Builds a git tag name for the given package and version. `{path}` renders empty;
+    /// use [`Config::package_tag_name`] for workspace packages.
     pub fn build_tag_name(&self, kind: PackageKind, package_name: &str, version: &str) -> String {
-        self.tag_template_for(package_name)
+        self.tag_template_for(kind, package_name)
             .render(kind, package_name, version)
     }
 
+    /// Builds the git tag of a workspace package, with `{path}` set to its directory
+    /// relative to `root` (empty for the root package).
+    pub fn package_tag_name(&self, root: &Path, package: &PackageInfo, version: &str) -> String {
+        let path = package
+            .path
+            .strip_prefix(root)
+            .unwrap_or(&package.path)
+            .components()
+            .filter_map(|component| match component {
+                std::path::Component::Normal(part) => Some(part.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        self.tag_template_for(package.kind, &package.name)
+            .render_with_path(package.kind, &package.name, &path, version)
+    }
+
+    /// Matches a tag against the configured templates, per-package ones first, and
+    /// returns what it captured. Unlike [`Config::parse_tag`], it also accepts tags that
+    /// don't carry the package name.
+    pub fn match_tag(&self, tag: &str) -> Option<TagMatch> {
+        let short = self
+            .git_short_tags
+            .is_some()
+            .then_some(&self.git_short_tags_format);
+        self.package_overrides
+            .values()
+            .filter_map(|overrides| overrides.git_tag_format.as_ref())
+            .chain(short)
+            .chain([&self.git_tag_format])
+            .find_map(|template| template.match_tag(tag).filter(|m| m.version.is_some()))
+    }
+
+    /// Finds the package a tag was created for among `packages`, with its version. This
+    /// also resolves templates that identify packages by `{path}` or `{package_slug}`.
+    pub fn find_tagged_package<'a>(
+        &self,
+        root: &Path,
+        packages: &'a [PackageInfo],
+        tag: &str,
+    ) -> Option<(&'a PackageInfo, String)> {
+        packages.iter().find_map(|package| {
+            let version = self
+                .tag_template_for(package.kind, &package.name)
+                .match_tag(tag)?
+                .version?;
+            (self.package_tag_name(root, package, &version) == tag).then_some((package, version))
+        })
+    }
+
     /// Parses a tag and returns `(package_name, version)`.
     ///
-    /// Tries the short-tag template first (it typically lacks `{package_name}`
-    /// and only matches the one configured package), then the regular template.
-    pub fn parse_tag(&self, tag: &str) -> Option<(String, String)> {
+    /// Tries the per-package templates first, then the short-tag template (it
+    /// typically lacks `{package_name}` and only matches the one configured
+    /// package), then the regular template. A tag that per-package templates of
+    /// different packages all match is rejected rather than attributed to either.
+    pub fn parse_tag(&self, tag: &str) -> Result<Option<(String, String)>, SampoError> {
+        let mut matched: Option<(&str, String, String)> = None;
+        for (key, overrides) in &self.package_overrides {
+            let (Some(template), Ok(spec)) =
+                (&overrides.git_tag_format, PackageSpecifier::parse(key))
+            else {
+                continue;
+            };
+            if let Some(captured) = template.match_tag(tag)
+                && let Some(version) = captured.version
+                && captured
+                    .package_name
+                    .as_deref()
+                    .is_none_or(|name| name == spec.name)
+                && captured
+                    .package_slug
+                    .as_deref()
+                    .is_none_or(|slug| slug == package_slug(&spec.name))
+            {
+                match &matched {
+                    Some((other, name, _)) if *name != spec.name => {
+                        return Err(SampoError::Config(format!(
+                            "Tag '{tag}' matches the tag_format of both packages.\"{other}\" \
+                             and packages.\"{key}\"; make their templates distinct"
+                        )));
+                    }
+                    Some(_) => {}
+                    None => matched = Some((key, spec.name, version)),
+                }
+            }
+        }
+        if let Some((_, name, version)) = matched {
+            return Ok(Some((name, version)));
+        }
+
         if let Some(short_pkg) = self.git_short_tags.as_deref()
             && let Some(captured) = self.git_short_tags_format.match_tag(tag)
             && let Some(version) = captured.version
@@ -408,19 +1380,41 @@ impl Config {
             let name = captured
                 .package_name
                 .unwrap_or_else(|| short_pkg.to_string());
-            return Some((name, version));
+            return Ok(Some((name, version)));
         }
 
         if let Some(captured) = self.git_tag_format.match_tag(tag)
             && let (Some(name), Some(version)) = (captured.package_name, captured.version)
         {
-            return Some((name, version));
+            return Ok(Some((name, version)));
         }
 
-        None
+        Ok(None)
     }
 }
 
+fn parse_seconds(value: &toml::Value, key: &str) -> Result<u64, SampoError> {
+    value
+        .as_integer()
+        .and_then(|seconds| u64::try_from(seconds).ok())
+        .ok_or_else(|| {
+            SampoError::Config(format!(
+                "{key} must be a non-negative number of seconds, found {value}"
+            ))
+        })
+}
+
+fn parse_retries(value: &toml::Value, key: &str) -> Result<u32, SampoError> {
+    value
+        .as_integer()
+        .and_then(|retries| u32::try_from(retries).ok())
+        .ok_or_else(|| {
+            SampoError::Config(format!(
+                "{key} must be a non-negative number, found {value}"
+            ))
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -442,6 +1436,7 @@ mod tests {
         assert!(config.is_release_branch("main"));
         assert_eq!(config.git_release_branches, Vec::<String>::new());
         assert!(config.git_short_tags.is_none());
+        assert_eq!(config.versioning_zero_major, ZeroMajorPolicy::Major);
     }
 
     #[test]
@@ -466,63 +1461,192 @@ mod tests {
     }
 
     #[test]
-    fn reads_changesets_tags() {
+    fn reads_versioning_zero_major() {
         let temp = tempfile::tempdir().unwrap();
         fs::create_dir_all(temp.path().join(".sampo")).unwrap();
         fs::write(
             temp.path().join(".sampo/config.toml"),
-            "[changesets]\ntags = [\"Added\", \"Changed\", \"Fixed\", \"Deprecated\", \"Removed\", \"Security\"]\n",
+            "[versioning]\nzero_major = \"minor\"\n\n[packages.\"cargo/legacy\"]\nzero_major = \"major\"\n",
         )
         .unwrap();
 
         let config = Config::load(temp.path()).unwrap();
+        assert_eq!(config.versioning_zero_major, ZeroMajorPolicy::Minor);
         assert_eq!(
-            config.changesets_tags,
-            vec![
-                "Added",
-                "Changed",
-                "Fixed",
-                "Deprecated",
-                "Removed",
-                "Security"
-            ]
+            config
+                .for_package(&package(PackageKind::Cargo, "legacy"))
+                .versioning_zero_major,
+            ZeroMajorPolicy::Major
+        );
+        assert_eq!(
+            config
+                .for_package(&package(PackageKind::Cargo, "other"))
+                .versioning_zero_major,
+            ZeroMajorPolicy::Minor
         );
-    }
 
-    #[test]
-    fn reads_github_repository() {
-        let temp = tempfile::tempdir().unwrap();
-        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
         fs::write(
             temp.path().join(".sampo/config.toml"),
-            "[github]\nrepository = \"owner/repo\"\n",
+            "[versioning]\nzero_major = \"patch\"\n",
         )
         .unwrap();
-
-        let config = Config::load(temp.path()).unwrap();
-        assert_eq!(config.github_repository.as_deref(), Some("owner/repo"));
+        let err = Config::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("versioning.zero_major"));
     }
 
     #[test]
-    fn reads_both_changelog_and_github() {
+    fn reads_versioning_scheme() {
         let temp = tempfile::tempdir().unwrap();
         fs::create_dir_all(temp.path().join(".sampo")).unwrap();
         fs::write(
             temp.path().join(".sampo/config.toml"),
-            "[changelog]\nshow_commit_hash = false\n[github]\nrepository = \"owner/repo\"\n",
+            "[packages.\"npm/app\"]\nversioning = \"calver:YYYY.MM.MICRO\"\n",
         )
         .unwrap();
 
         let config = Config::load(temp.path()).unwrap();
-        assert!(!config.changelog_show_commit_hash);
-        assert!(config.changelog_show_release_date);
-        assert_eq!(config.changelog_release_date_format, "%Y-%m-%d");
-        assert!(config.changelog_release_date_timezone.is_none());
-        assert_eq!(config.github_repository.as_deref(), Some("owner/repo"));
-    }
+        assert_eq!(config.versioning_scheme, VersioningScheme::Semver);
+        assert_eq!(
+            config
+                .for_package(&package(PackageKind::Npm, "app"))
+                .versioning_scheme,
+            VersioningScheme::Calver(CalverFormat::parse("YYYY.MM.MICRO").unwrap())
+        );
 
-    #[test]
-    fn reads_git_section() {
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[packages.\"npm/app\"]\nversioning = \"calendar\"\n",
+        )
+        .unwrap();
+        let err = Config::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("packages.\"npm/app\".versioning"));
+
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[versioning]\nscheme = \"calver:YYYY.0M.MICRO\"\n",
+        )
+        .unwrap();
+        let err = Config::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("Unsupported CalVer format"));
+    }
+
+    #[test]
+    fn reads_cascade_policy() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[packages]\ncascade = \"none\"\ncascade_dev_dependencies = false\n\n[[packages.cascade_groups]]\npackages = [\"cargo/cli\"]\ncascade = \"match\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(config.cascade, CascadePolicy::None);
+        assert!(!config.cascade_dev_dependencies);
+        assert_eq!(
+            config.cascade_groups,
+            vec![CascadeGroup {
+                packages: vec!["cargo/cli".to_string()],
+                cascade: CascadePolicy::Match,
+            }]
+        );
+        assert!(config.package_overrides.is_empty());
+
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[packages]\ncascade = \"minor\"\n",
+        )
+        .unwrap();
+        let err = Config::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("packages.cascade must be"));
+    }
+
+    #[test]
+    fn reads_version_files() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            r#"[[packages.version_files]]
+path = "docs/**/*.md"
+package = "cargo/sampo"
+template = 'sampo = "{major}.{minor}"'
+
+[[packages.version_files]]
+path = "install.sh"
+package = "sampo"
+regex = 'VERSION="([^"]+)"'
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(config.version_files.len(), 2);
+        assert_eq!(config.version_files[0].path, "docs/**/*.md");
+        assert_eq!(config.version_files[0].package, "cargo/sampo");
+        assert!(config.version_files[0].regex.is_none());
+        assert!(config.version_files[1].regex.is_some());
+        assert!(config.package_overrides.is_empty());
+    }
+
+    #[test]
+    fn reads_changesets_tags() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[changesets]\ntags = [\"Added\", \"Changed\", \"Fixed\", \"Deprecated\", \"Removed\", \"Security\"]\n",
+        )
+        .unwrap();
+
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(
+            config.changesets_tags,
+            vec![
+                "Added",
+                "Changed",
+                "Fixed",
+                "Deprecated",
+                "Removed",
+                "Security"
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_github_repository() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[github]\nrepository = \"owner/repo\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(config.github_repository.as_deref(), Some("owner/repo"));
+    }
+
+    #[test]
+    fn reads_both_changelog_and_github() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[changelog]\nshow_commit_hash = false\n[github]\nrepository = \"owner/repo\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load(temp.path()).unwrap();
+        assert!(!config.changelog_show_commit_hash);
+        assert!(config.changelog_show_release_date);
+        assert_eq!(config.changelog_release_date_format, "%Y-%m-%d");
+        assert!(config.changelog_release_date_timezone.is_none());
+        assert_eq!(config.github_repository.as_deref(), Some("owner/repo"));
+    }
+
+    #[test]
+    fn reads_git_section() {
         let temp = tempfile::tempdir().unwrap();
         fs::create_dir_all(temp.path().join(".sampo")).unwrap();
         fs::write(
@@ -761,6 +1885,291 @@ mod tests {
         assert_eq!(config.git_short_tags.as_deref(), Some("my-package"));
     }
 
+    #[test]
+    fn reads_tag_signing_and_message() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        let config_path = temp.path().join(".sampo/config.toml");
+
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(config.git_sign_tags, None);
+        assert_eq!(config.git_tag_message, DEFAULT_TAG_MESSAGE);
+
+        fs::write(
+            &config_path,
+            "[git]\nsign_tags = true\ntag_message = \"{package_name} {version}\\n\\n{changelog}\"\n",
+        )
+        .unwrap();
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(config.git_sign_tags, Some(TagSigning::Git));
+        assert_eq!(
+            config.git_tag_message,
+            "{package_name} {version}\n\n{changelog}"
+        );
+
+        fs::write(&config_path, "[git]\nsign_tags = \"ssh\"\n").unwrap();
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(config.git_sign_tags, Some(TagSigning::Ssh));
+
+        fs::write(&config_path, "[git]\nsign_tags = \"x509\"\n").unwrap();
+        let err = Config::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("git.sign_tags"));
+    }
+
+    #[test]
+    fn reads_release_commit_message() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        let config_path = temp.path().join(".sampo/config.toml");
+
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(
+            config.git_release_commit_message,
+            DEFAULT_RELEASE_COMMIT_MESSAGE
+        );
+
+        fs::write(
+            &config_path,
+            "[git]\nrelease_commit_message = \"Release\\n\\n{release_list}\"\n",
+        )
+        .unwrap();
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(
+            config.git_release_commit_message,
+            "Release\n\n{release_list}"
+        );
+
+        fs::write(&config_path, "[git]\nrelease_commit_message = \"\"\n").unwrap();
+        assert!(Config::load(temp.path()).is_err());
+    }
+
+    #[test]
+    fn reads_floating_tags() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[git]\nfloating_tags = [\"major\", \"minor\"]\n\n[packages.\"npm/ui\".git]\nfloating_tags = [\"major\"]\n",
+        )
+        .unwrap();
+
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(
+            config.git_floating_tags,
+            vec![FloatingTag::Major, FloatingTag::Minor]
+        );
+        assert_eq!(
+            config
+                .for_package(&package(PackageKind::Npm, "ui"))
+                .git_floating_tags,
+            vec![FloatingTag::Major]
+        );
+
+        assert_eq!(FloatingTag::Major.version("2.3.1").as_deref(), Some("2"));
+        assert_eq!(
+            FloatingTag::Minor.version("2.3.1+build.5").as_deref(),
+            Some("2.3")
+        );
+        assert_eq!(FloatingTag::Major.version("3.0.0-rc.1"), None);
+
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[git]\nfloating_tags = [\"patch\"]\n",
+        )
+        .unwrap();
+        let err = Config::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("git.floating_tags"));
+    }
+
+    #[test]
+    fn tags_of_the_root_package_and_stable_versions_drop_empty_placeholders() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[git]\ntag_format = \"{path}/v{major}.{minor}.{patch}-{prerelease}\"\n",
+        )
+        .unwrap();
+        let config = Config::load(temp.path()).unwrap();
+
+        let root = Path::new("/repo");
+        let mut app = package(PackageKind::Cargo, "app");
+        app.path = root.to_path_buf();
+        let mut core = package(PackageKind::Cargo, "core");
+        core.path = root.join("crates/core");
+        let packages = vec![app, core];
+
+        assert_eq!(
+            config.package_tag_name(root, &packages[0], "1.2.0"),
+            "v1.2.0"
+        );
+        assert_eq!(
+            config.package_tag_name(root, &packages[0], "1.3.0-rc.1"),
+            "v1.3.0-rc.1"
+        );
+        assert_eq!(
+            config.package_tag_name(root, &packages[1], "2.0.0"),
+            "crates/core/v2.0.0"
+        );
+
+        let (found, version) = config
+            .find_tagged_package(root, &packages, "v1.2.0")
+            .unwrap();
+        assert_eq!(found.name, "app");
+        assert_eq!(version, "1.2.0");
+        let (found, version) = config
+            .find_tagged_package(root, &packages, "crates/core/v2.0.0")
+            .unwrap();
+        assert_eq!(found.name, "core");
+        assert_eq!(version, "2.0.0");
+    }
+
+    #[test]
+    fn resolves_tags_rendered_from_path_and_slug() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[git]\ntag_format = \"{path}/v{major}.{minor}.{patch}\"\n\n[packages.\"npm/@acme/ui\".git]\ntag_format = \"{package_slug}-v{version}\"\n",
+        )
+        .unwrap();
+        let config = Config::load(temp.path()).unwrap();
+
+        let root = Path::new("/repo");
+        let mut core = package(PackageKind::Cargo, "core");
+        core.path = root.join("crates/core");
+        let mut ui = package(PackageKind::Npm, "@acme/ui");
+        ui.path = root.join("packages/ui");
+        let packages = vec![core, ui];
+
+        assert_eq!(
+            config.package_tag_name(root, &packages[0], "1.2.0"),
+            "crates/core/v1.2.0"
+        );
+        assert_eq!(
+            config.package_tag_name(root, &packages[1], "2.0.0-rc.1"),
+            "acme-ui-v2.0.0-rc.1"
+        );
+
+        let (found, version) = config
+            .find_tagged_package(root, &packages, "crates/core/v1.2.0")
+            .unwrap();
+        assert_eq!(found.name, "core");
+        assert_eq!(version, "1.2.0");
+        let (found, version) = config
+            .find_tagged_package(root, &packages, "acme-ui-v2.0.0-rc.1")
+            .unwrap();
+        assert_eq!(found.name, "@acme/ui");
+        assert_eq!(version, "2.0.0-rc.1");
+        assert!(
+            config
+                .find_tagged_package(root, &packages, "crates/other/v1.2.0")
+                .is_none()
+        );
+
+        assert_eq!(
+            config.parse_tag("acme-ui-v2.0.0-rc.1").unwrap(),
+            Some(("@acme/ui".to_string(), "2.0.0-rc.1".to_string()))
+        );
+        let captured = config.match_tag("crates/core/v1.2.0").unwrap();
+        assert_eq!(captured.path.as_deref(), Some("crates/core"));
+        assert_eq!(captured.version.as_deref(), Some("1.2.0"));
+    }
+
+    #[test]
+    fn reads_publish_settings() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(
+            config.publish_verify_timeout(PackageKind::Cargo),
+            Duration::from_secs(DEFAULT_PUBLISH_VERIFY_TIMEOUT)
+        );
+        assert_eq!(
+            config.publish_verify_timeout(PackageKind::Maven),
+            Duration::ZERO
+        );
+        assert_eq!(
+            config.publish_retries(PackageKind::Npm),
+            DEFAULT_PUBLISH_RETRIES
+        );
+
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[publish]\nverify_timeout = 120\nretries = 5\n\n[publish.npm]\nverify_timeout = 0\nretries = 0\n",
+        )
+        .unwrap();
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(
+            config.publish_verify_timeout(PackageKind::Cargo),
+            Duration::from_secs(120)
+        );
+        assert_eq!(
+            config.publish_verify_timeout(PackageKind::Maven),
+            Duration::from_secs(120)
+        );
+        assert_eq!(
+            config.publish_verify_timeout(PackageKind::Npm),
+            Duration::ZERO
+        );
+        assert_eq!(config.publish_retries(PackageKind::Cargo), 5);
+        assert_eq!(config.publish_retries(PackageKind::Npm), 0);
+        assert_eq!(config.publish_existence_check, ExistenceCheck::Registry);
+
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[publish]\nexistence_check = \"tags\"\n",
+        )
+        .unwrap();
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(config.publish_existence_check, ExistenceCheck::Tags);
+        assert!(!config.publish_trusted_publishing);
+        assert!(!config.publish_provenance);
+        assert_eq!(config.publish_provenance_dir, DEFAULT_PROVENANCE_DIR);
+
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[publish]\ntrusted_publishing = true\nprovenance = true\nprovenance_dir = \"attestations\"\n",
+        )
+        .unwrap();
+        let config = Config::load(temp.path()).unwrap();
+        assert!(config.publish_trusted_publishing);
+        assert!(config.publish_provenance);
+        assert_eq!(config.publish_provenance_dir, "attestations");
+
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[publish]\nprovenance = \"yes\"\n",
+        )
+        .unwrap();
+        let err = Config::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("publish.provenance"));
+
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[publish]\nexistence_check = \"git\"\n",
+        )
+        .unwrap();
+        let err = Config::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("publish.existence_check"));
+
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[publish.rubygems]\nverify_timeout = 60\n",
+        )
+        .unwrap();
+        let err = Config::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("publish.rubygems"));
+
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[publish]\nverify_timeout = -1\n",
+        )
+        .unwrap();
+        let err = Config::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("publish.verify_timeout"));
+    }
+
     #[test]
     fn defaults_short_tags_to_none() {
         let temp = tempfile::tempdir().unwrap();
@@ -783,6 +2192,227 @@ mod tests {
         assert!(!config.uses_short_tags("other-package"));
     }
 
+    fn package(kind: PackageKind, name: &str) -> PackageInfo {
+        PackageInfo {
+            name: name.to_string(),
+            identifier: format!("{}/{}", kind.as_str(), name),
+            version: "1.0.0".to_string(),
+            path: std::path::PathBuf::from(name),
+            internal_deps: BTreeSet::new(),
+            internal_dev_deps: BTreeSet::new(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn for_package_applies_overrides() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            r#"[changelog]
+show_commit_hash = true
+
+[packages]
+ignore = ["internal-*"]
+
+[packages."cargo/ui"]
+prerelease = "beta"
+
+[packages."npm/ui"]
+prerelease = "rc"
+changelog = { path = "docs/CHANGELOG.md", show_commit_hash = false }
+registries = ["npmjs", "https://npm.pkg.github.com"]
+git = { tag_format = "ui@{version}", release_branches = ["ui-next"] }
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(config.ignore, vec!["internal-*".to_string()]);
+        assert_eq!(config.package_overrides.len(), 2);
+
+        let npm_ui = config.for_package(&package(PackageKind::Npm, "ui"));
+        assert_eq!(npm_ui.changelog_path, "docs/CHANGELOG.md");
+        assert!(!npm_ui.changelog_show_commit_hash);
+        assert_eq!(npm_ui.prerelease_label.as_deref(), Some("rc"));
+        assert!(npm_ui.is_release_branch("ui-next"));
+        assert_eq!(
+            npm_ui.publish_registries,
+            vec![
+                "npmjs".to_string(),
+                "https://npm.pkg.github.com".to_string()
+            ]
+        );
+        assert_eq!(
+            npm_ui.build_tag_name(PackageKind::Npm, "ui", "2.0.0"),
+            "ui@2.0.0"
+        );
+
+        let cargo_ui = config.for_package(&package(PackageKind::Cargo, "ui"));
+        assert_eq!(cargo_ui.prerelease_label.as_deref(), Some("beta"));
+        assert_eq!(cargo_ui.changelog_path, DEFAULT_CHANGELOG_PATH);
+        assert!(!cargo_ui.is_release_branch("ui-next"));
+        assert_eq!(
+            config.build_tag_name(PackageKind::Cargo, "ui", "2.0.0"),
+            "ui-v2.0.0"
+        );
+        assert_eq!(
+            config.build_tag_name(PackageKind::Npm, "ui", "2.0.0"),
+            "ui@2.0.0"
+        );
+
+        let other = config.for_package(&package(PackageKind::Cargo, "core"));
+        assert_eq!(other.changelog_path, DEFAULT_CHANGELOG_PATH);
+        assert!(other.changelog_show_commit_hash);
+        assert!(other.prerelease_label.is_none());
+        assert!(other.publish_registries.is_empty());
+    }
+
+    #[test]
+    fn rejects_versioning_package_tables() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[versioning.packages.\"cargo/foo\"]\nzero_major = \"minor\"\n",
+        )
+        .unwrap();
+
+        let err = Config::load(temp.path()).unwrap_err();
+        assert!(
+            err.to_string().contains("to [packages.\"cargo/foo\"]"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn package_overrides_need_a_canonical_identifier() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[packages.ui]\nprerelease = \"beta\"\n",
+        )
+        .unwrap();
+
+        let err = Config::load(temp.path()).unwrap_err();
+        assert!(
+            err.to_string().contains("[packages.\"cargo/ui\"]"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_package_override_settings() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        for (body, setting) in [
+            ("prerelase = \"beta\"\n", "packages.\"npm/ui\".prerelase"),
+            (
+                "changelog = { show_hash = true }\n",
+                "packages.\"npm/ui\".changelog.show_hash",
+            ),
+            (
+                "git = { tag_fromat = \"ui@{version}\" }\n",
+                "packages.\"npm/ui\".git.tag_fromat",
+            ),
+        ] {
+            fs::write(
+                temp.path().join(".sampo/config.toml"),
+                format!("[packages.\"npm/ui\"]\n{body}"),
+            )
+            .unwrap();
+            let err = Config::load(temp.path()).unwrap_err();
+            assert!(
+                matches!(&err, SampoError::Config(message) if message.contains(setting)),
+                "unexpected error: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_tag_rejects_ambiguous_package_templates() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[packages.\"npm/web\".git]\ntag_format = \"v{version}\"\n\n\
+             [packages.\"cargo/api\".git]\ntag_format = \"v{version}\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load(temp.path()).unwrap();
+        let err = config.parse_tag("v1.2.0").unwrap_err();
+        assert!(
+            err.to_string().contains("matches the tag_format of both"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_package_registries() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        for (registries, message) in [
+            ("\"internal\"", "must be an array"),
+            ("[\"\"]", "non-empty strings"),
+            ("[\"internal\", \"internal\"]", "more than once"),
+        ] {
+            fs::write(
+                temp.path().join(".sampo/config.toml"),
+                format!("[packages.\"cargo/foo\"]\nregistries = {registries}\n"),
+            )
+            .unwrap();
+            let err = Config::load(temp.path()).unwrap_err();
+            assert!(err.to_string().contains(message), "{registries}: {err}");
+        }
+    }
+
+    #[test]
+    fn rejects_registries_of_single_registry_ecosystems() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        for package in [
+            "hex/foo",
+            "pypi/foo",
+            "packagist/acme/foo",
+            "maven/com.acme:foo",
+        ] {
+            fs::write(
+                temp.path().join(".sampo/config.toml"),
+                format!("[packages.\"{package}\"]\nregistries = [\"internal\"]\n"),
+            )
+            .unwrap();
+            let err = Config::load(temp.path()).unwrap_err();
+            assert!(
+                err.to_string().contains("is not supported"),
+                "{package}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_tag_uses_package_tag_format() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[packages.\"npm/web\".git]\ntag_format = \"web@{version}\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load(temp.path()).unwrap();
+        assert_eq!(
+            config.parse_tag("web@1.2.0").unwrap(),
+            Some(("web".to_string(), "1.2.0".to_string()))
+        );
+        assert_eq!(
+            config.parse_tag("core-v0.3.0").unwrap(),
+            Some(("core".to_string(), "0.3.0".to_string()))
+        );
+    }
+
     #[test]
     fn build_tag_name_uses_short_format_for_configured_package() {
         let temp = tempfile::tempdir().unwrap();
@@ -861,15 +2491,15 @@ mod tests {
 
         let config = Config::load(temp.path()).unwrap();
         assert_eq!(
-            config.parse_tag("v1.2.3"),
+            config.parse_tag("v1.2.3").unwrap(),
             Some(("my-package".to_string(), "1.2.3".to_string()))
         );
         assert_eq!(
-            config.parse_tag("v1.2.3-alpha.1"),
+            config.parse_tag("v1.2.3-alpha.1").unwrap(),
             Some(("my-package".to_string(), "1.2.3-alpha.1".to_string()))
         );
         assert_eq!(
-            config.parse_tag("other-package-v1.2.3"),
+            config.parse_tag("other-package-v1.2.3").unwrap(),
             Some(("other-package".to_string(), "1.2.3".to_string()))
         );
     }
@@ -888,23 +2518,23 @@ mod tests {
 
         // Prerelease containing -v (the bug case)
         assert_eq!(
-            config.parse_tag("v1.2.3-v1"),
+            config.parse_tag("v1.2.3-v1").unwrap(),
             Some(("my-package".to_string(), "1.2.3-v1".to_string()))
         );
         assert_eq!(
-            config.parse_tag("v1.0.0-preview1"),
+            config.parse_tag("v1.0.0-preview1").unwrap(),
             Some(("my-package".to_string(), "1.0.0-preview1".to_string()))
         );
         assert_eq!(
-            config.parse_tag("v2.0.0-v2-beta"),
+            config.parse_tag("v2.0.0-v2-beta").unwrap(),
             Some(("my-package".to_string(), "2.0.0-v2-beta".to_string()))
         );
         assert_eq!(
-            config.parse_tag("v1.2.3+build.123"),
+            config.parse_tag("v1.2.3+build.123").unwrap(),
             Some(("my-package".to_string(), "1.2.3+build.123".to_string()))
         );
         assert_eq!(
-            config.parse_tag("v1.2.3-alpha.1+build.456"),
+            config.parse_tag("v1.2.3-alpha.1+build.456").unwrap(),
             Some((
                 "my-package".to_string(),
                 "1.2.3-alpha.1+build.456".to_string()
@@ -924,10 +2554,10 @@ mod tests {
 
         let config = Config::load(temp.path()).unwrap();
 
-        assert_eq!(config.parse_tag("v1.2"), None);
-        assert_eq!(config.parse_tag("vfoo"), None);
-        assert_eq!(config.parse_tag("v01.2.3"), None);
-        assert_eq!(config.parse_tag("v"), None);
+        assert_eq!(config.parse_tag("v1.2").unwrap(), None);
+        assert_eq!(config.parse_tag("vfoo").unwrap(), None);
+        assert_eq!(config.parse_tag("v01.2.3").unwrap(), None);
+        assert_eq!(config.parse_tag("v").unwrap(), None);
     }
 
     #[test]
@@ -936,22 +2566,22 @@ mod tests {
         let config = Config::load(temp.path()).unwrap();
 
         assert_eq!(
-            config.parse_tag("my-package-v1.2.3"),
+            config.parse_tag("my-package-v1.2.3").unwrap(),
             Some(("my-package".to_string(), "1.2.3".to_string()))
         );
         assert_eq!(
-            config.parse_tag("my-package-v1.2.3-alpha.1"),
+            config.parse_tag("my-package-v1.2.3-alpha.1").unwrap(),
             Some(("my-package".to_string(), "1.2.3-alpha.1".to_string()))
         );
         // -v in prerelease still parses correctly thanks to semver validation.
         assert_eq!(
-            config.parse_tag("my-package-v1.2.3-v1"),
+            config.parse_tag("my-package-v1.2.3-v1").unwrap(),
             Some(("my-package".to_string(), "1.2.3-v1".to_string()))
         );
         // Tags without `{package_name}` are rejected by the default template.
-        assert_eq!(config.parse_tag("v1.2.3"), None);
-        assert_eq!(config.parse_tag("my-package-vfoo"), None);
-        assert_eq!(config.parse_tag("my-package-v1.2"), None);
+        assert_eq!(config.parse_tag("v1.2.3").unwrap(), None);
+        assert_eq!(config.parse_tag("my-package-vfoo").unwrap(), None);
+        assert_eq!(config.parse_tag("my-package-v1.2").unwrap(), None);
     }
 
     #[test]
@@ -977,11 +2607,11 @@ mod tests {
             "release-1.0.0"
         );
         assert_eq!(
-            config.parse_tag("core-v1.0.0"),
+            config.parse_tag("core-v1.0.0").unwrap(),
             Some(("core".to_string(), "1.0.0".to_string()))
         );
         assert_eq!(
-            config.parse_tag("release-1.0.0"),
+            config.parse_tag("release-1.0.0").unwrap(),
             Some(("my-php-pkg".to_string(), "1.0.0".to_string()))
         );
     }
@@ -998,10 +2628,10 @@ mod tests {
 
         let config = Config::load(temp.path()).unwrap();
         assert_eq!(
-            config.parse_tag("cargo-my-package-v1.2.3"),
+            config.parse_tag("cargo-my-package-v1.2.3").unwrap(),
             Some(("my-package".to_string(), "1.2.3".to_string()))
         );
         // Legacy-shape tags are rejected once the user opts into the disambiguating template.
-        assert_eq!(config.parse_tag("my-package-v1.2.3"), None);
+        assert_eq!(config.parse_tag("my-package-v1.2.3").unwrap(), None);
     }
 }
@@ -34,6 +34,9 @@ pub enum SampoError {
     #[error("Pre-release error: {0}")]
     Prerelease(String),
 
+    #[error("Yank error: {0}")]
+    Yank(String),
+
     #[error("Constraint violation: {0}")]
     ConstraintViolation(String),
 
@@ -42,6 +45,41 @@ pub enum SampoError {
 
     #[error("Not found: {0}")]
     NotFound(String),
+
+    /// A publish that failed part way: `output` reports what it did before `source`, such
+    /// as the tags it created and the status of each registry.
+    #[error("{source}")]
+    PublishIncomplete {
+        output: Box<crate::types::PublishOutput>,
+        source: Box<SampoError>,
+    },
+
+    /// A failure that can go away on its own, like a registry outage or a dropped
+    /// connection. `sampo publish` retries these before giving up.
+    #[error(transparent)]
+    Retryable(Box<SampoError>),
+}
+
+impl SampoError {
+    /// Marks the error as worth retrying.
+    pub fn retryable(self) -> Self {
+        match self {
+            Self::Retryable(_) => self,
+            other => Self::Retryable(Box::new(other)),
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Retryable(_))
+    }
+
+    /// The error without its [`SampoError::Retryable`] marker.
+    pub fn into_inner(self) -> Self {
+        match self {
+            Self::Retryable(inner) => *inner,
+            other => other,
+        }
+    }
 }
 
 /// Errors that can occur when working with workspaces
@@ -1,7 +1,9 @@
 use crate::errors::{Result, SampoError};
 #[cfg(test)]
 use std::cell::RefCell;
-use std::process::Command;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
 
 #[cfg(test)]
 thread_local! {
@@ -89,3 +91,207 @@ pub fn current_branch() -> Result<String> {
 
     Ok(branch)
 }
+
+/// Paths with uncommitted changes, staged or not and including untracked files, relative
+/// to the root of the repository containing `dir`.
+pub fn working_tree_changes(dir: &Path) -> Result<BTreeSet<String>> {
+    let output = git_in(
+        dir,
+        &["status", "--porcelain", "-z", "--untracked-files=all"],
+    )?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut entries = stdout.split('\0');
+    let mut paths = BTreeSet::new();
+    while let Some(entry) = entries.next() {
+        let Some((status, path)) = entry.split_at_checked(3) else {
+            continue;
+        };
+        paths.insert(path.to_string());
+        // Renames and copies are followed by their source path.
+        if status.starts_with(['R', 'C'])
+            && let Some(source) = entries.next()
+        {
+            paths.insert(source.to_string());
+        }
+    }
+    Ok(paths)
+}
+
+/// Stage `paths`, relative to the repository root as [`working_tree_changes`] reports them,
+/// and commit them alone: anything else already staged stays staged, out of the commit.
+/// `sign` passes `-S` so git signs the commit with its configured key.
+pub fn commit_paths(dir: &Path, paths: &BTreeSet<String>, message: &str, sign: bool) -> Result<()> {
+    let pathspecs: Vec<String> = paths
+        .iter()
+        .map(|path| format!(":(top,literal){path}"))
+        .collect();
+    let mut add = vec!["add", "-A", "--"];
+    add.extend(pathspecs.iter().map(String::as_str));
+    git_in(dir, &add)?;
+
+    // A path added to the index and removed since (e.g. a staged changeset the release
+    // consumed) is unknown to `git commit --only`: keep only those that differ from HEAD.
+    let mut diff = vec!["diff", "--cached", "--name-only", "-z", "--"];
+    diff.extend(pathspecs.iter().map(String::as_str));
+    let output = git_in(dir, &diff)?;
+    let changed: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(|path| format!(":(top,literal){path}"))
+        .collect();
+    if changed.is_empty() {
+        return Ok(());
+    }
+
+    let mut commit = vec!["commit", "--only", "-m", message];
+    if sign {
+        commit.push("-S");
+    }
+    commit.push("--");
+    commit.extend(changed.iter().map(String::as_str));
+    git_in(dir, &commit)?;
+    Ok(())
+}
+
+/// `path`, which may no longer exist, relative to the root of the repository containing
+/// `dir`, as [`working_tree_changes`] reports paths.
+pub fn repository_path(dir: &Path, path: &Path) -> Result<String> {
+    let output = git_in(dir, &["rev-parse", "--show-toplevel"])?;
+    let top = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    let top = top.canonicalize().unwrap_or(top);
+
+    // Resolve the deepest existing ancestor, so symlinked roots (e.g. `/tmp` on macOS)
+    // compare equal to the toplevel git reports.
+    let mut existing = path;
+    let mut rest = Vec::new();
+    while !existing.exists() {
+        let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+            break;
+        };
+        rest.push(name);
+        existing = parent;
+    }
+    let mut resolved = existing
+        .canonicalize()
+        .unwrap_or_else(|_| existing.to_path_buf());
+    resolved.extend(rest.into_iter().rev());
+
+    let relative = resolved.strip_prefix(&top).map_err(|_| {
+        SampoError::Git(format!(
+            "{} is outside the repository at {}",
+            path.display(),
+            top.display()
+        ))
+    })?;
+    Ok(relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Full hash of the commit checked out in the repository containing `dir`.
+pub(crate) fn head_commit(dir: &Path) -> Result<String> {
+    let output = git_in(dir, &["rev-parse", "HEAD"])?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn git_in(dir: &Path, args: &[&str]) -> Result<Output> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(SampoError::Io)?;
+    if !output.status.success() {
+        return Err(SampoError::Git(format!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn git(dir: &Path, args: &[&str]) {
+        git_in(dir, args).unwrap();
+    }
+
+    #[test]
+    fn commits_only_the_given_paths() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q"]);
+        git(root, &["config", "user.email", "test@example.com"]);
+        git(root, &["config", "user.name", "Test User"]);
+        fs::write(root.join("tracked.txt"), "one").unwrap();
+        git(root, &["add", "-A"]);
+        git(root, &["commit", "-q", "-m", "Initial commit"]);
+
+        fs::create_dir_all(root.join("nested dir")).unwrap();
+        fs::write(root.join("nested dir/new.txt"), "new").unwrap();
+        fs::write(root.join("tracked.txt"), "two").unwrap();
+        fs::write(root.join("other.txt"), "other").unwrap();
+        assert_eq!(
+            working_tree_changes(&root.join("nested dir")).unwrap(),
+            BTreeSet::from(["nested dir/new.txt", "other.txt", "tracked.txt"].map(String::from))
+        );
+
+        let paths = BTreeSet::from(["nested dir/new.txt", "tracked.txt"].map(String::from));
+        commit_paths(&root.join("nested dir"), &paths, "Release", false).unwrap();
+        assert_eq!(
+            working_tree_changes(root).unwrap(),
+            BTreeSet::from(["other.txt".to_string()])
+        );
+        let subject = git_in(root, &["log", "-1", "--format=%s"]).unwrap();
+        assert_eq!(String::from_utf8_lossy(&subject.stdout).trim(), "Release");
+    }
+
+    #[test]
+    fn leaves_other_staged_changes_out_of_the_commit() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q"]);
+        git(root, &["config", "user.email", "test@example.com"]);
+        git(root, &["config", "user.name", "Test User"]);
+        fs::write(root.join("tracked.txt"), "one").unwrap();
+        git(root, &["add", "-A"]);
+        git(root, &["commit", "-q", "-m", "Initial commit"]);
+
+        // Staged before the release: one file it consumes, one it leaves alone.
+        fs::write(root.join("consumed.md"), "consumed").unwrap();
+        fs::write(root.join("pending.md"), "pending").unwrap();
+        git(root, &["add", "consumed.md", "pending.md"]);
+        fs::remove_file(root.join("consumed.md")).unwrap();
+        fs::write(root.join("tracked.txt"), "two").unwrap();
+
+        let paths = BTreeSet::from(["consumed.md", "tracked.txt"].map(String::from));
+        commit_paths(root, &paths, "Release", false).unwrap();
+
+        let committed = git_in(root, &["show", "--name-only", "--format=", "HEAD"]).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&committed.stdout).trim(),
+            "tracked.txt"
+        );
+        let staged = git_in(root, &["diff", "--cached", "--name-only"]).unwrap();
+        assert_eq!(String::from_utf8_lossy(&staged.stdout).trim(), "pending.md");
+    }
+
+    #[test]
+    fn repository_path_handles_removed_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q"]);
+        fs::create_dir_all(root.join(".sampo/changesets")).unwrap();
+
+        assert_eq!(
+            repository_path(root, &root.join(".sampo/changesets/gone.md")).unwrap(),
+            ".sampo/changesets/gone.md"
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use crate::errors::{SampoError, io_error_with_path};
+use crate::process::command;
+use crate::types::PackageInfo;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Stdio;
+
+/// Points of the release and publish flows where `[hooks]` commands run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    /// Once the release plan is known, before any file is written.
+    PreRelease,
+    /// After manifests, changelogs and lockfiles are updated.
+    PostRelease,
+    /// Once the publish plan is known, before the first package is published.
+    PrePublish,
+    /// After every package is published and tagged.
+    PostPublish,
+    /// Before each package is published.
+    PrePublishPackage,
+    /// After each package is published.
+    PostPublishPackage,
+}
+
+impl Hook {
+    pub const ALL: [Hook; 6] = [
+        Hook::PreRelease,
+        Hook::PostRelease,
+        Hook::PrePublish,
+        Hook::PostPublish,
+        Hook::PrePublishPackage,
+        Hook::PostPublishPackage,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Hook::PreRelease => "pre_release",
+            Hook::PostRelease => "post_release",
+            Hook::PrePublish => "pre_publish",
+            Hook::PostPublish => "post_publish",
+            Hook::PrePublishPackage => "pre_publish_package",
+            Hook::PostPublishPackage => "post_publish_package",
+        }
+    }
+
+    fn error(&self, message: String) -> SampoError {
+        match self {
+            Hook::PreRelease | Hook::PostRelease => SampoError::Release(message),
+            _ => SampoError::Publish(message),
+        }
+    }
+}
+
+/// Commands of the `[hooks]` section, each a program followed by its arguments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Hooks {
+    commands: Vec<(Hook, Vec<String>)>,
+}
+
+impl Hooks {
+    pub(crate) fn parse(table: &toml::value::Table) -> Result<Self, SampoError> {
+        let mut commands = Vec::new();
+        for (key, value) in table {
+            let hook = Hook::ALL
+                .into_iter()
+                .find(|hook| hook.as_str() == key)
+                .ok_or_else(|| {
+                    SampoError::Config(format!(
+                        "Unknown hook 'hooks.{key}'. Supported: {}.",
+                        Hook::ALL.map(|hook| hook.as_str()).join(", ")
+                    ))
+                })?;
+            let argv = value
+                .as_array()
+                .and_then(|items| {
+                    items
+                        .iter()
+                        .map(|item| item.as_str().map(str::to_string))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .filter(|argv| argv.first().is_some_and(|program| !program.trim().is_empty()))
+                .ok_or_else(|| {
+                    SampoError::Config(format!(
+                        "hooks.{key} must be a non-empty array of strings (the program, then its arguments)"
+                    ))
+                })?;
+            commands.push((hook, argv));
+        }
+        Ok(Self { commands })
+    }
+
+    pub fn command(&self, hook: Hook) -> Option<&[String]> {
+        self.commands
+            .iter()
+            .find(|(candidate, _)| *candidate == hook)
+            .map(|(_, argv)| argv.as_slice())
+    }
+}
+
+/// A package as hooks see it, in the JSON plan and the `SAMPO_*` variables.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct HookPackage {
+    pub package: String,
+    pub name: String,
+    pub ecosystem: &'static str,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_version: Option<String>,
+    pub new_version: String,
+}
+
+impl HookPackage {
+    pub fn new(root: &Path, info: &PackageInfo, old: Option<&str>, new: &str) -> Self {
+        Self {
+            package: info.canonical_identifier().to_string(),
+            name: info.name.clone(),
+            ecosystem: info.kind.as_str(),
+            path: info
+                .path
+                .strip_prefix(root)
+                .unwrap_or(&info.path)
+                .to_string_lossy()
+                .into_owned(),
+            old_version: old.map(str::to_string),
+            new_version: new.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct HookInput<'a> {
+    hook: &'static str,
+    dry_run: bool,
+    packages: &'a [HookPackage],
+}
+
+/// Run `hook` if configured. The plan is written as JSON on stdin; `package` also sets
+/// the `SAMPO_PACKAGE`-style variables for per-package hooks. A failing command aborts
+/// the operation.
+pub(crate) fn run_hook(
+    root: &Path,
+    hooks: &Hooks,
+    hook: Hook,
+    dry_run: bool,
+    packages: &[HookPackage],
+    package: Option<&HookPackage>,
+) -> Result<(), SampoError> {
+    let Some(argv) = hooks.command(hook) else {
+        return Ok(());
+    };
+    let (program, args) = argv.split_first().expect("hooks are never empty");
+    println!("Running {} hook: {}", hook.as_str(), argv.join(" "));
+
+    let mut cmd = command(program);
+    cmd.args(args)
+        .current_dir(root)
+        .env("SAMPO_HOOK", hook.as_str())
+        .env("SAMPO_DRY_RUN", dry_run.to_string())
+        .env("SAMPO_WORKSPACE_ROOT", root)
+        .stdin(Stdio::piped());
+    if let Some(package) = package {
+        cmd.env("SAMPO_PACKAGE", &package.package)
+            .env("SAMPO_PACKAGE_NAME", &package.name)
+            .env("SAMPO_ECOSYSTEM", package.ecosystem)
+            .env("SAMPO_PACKAGE_PATH", &package.path)
+            .env("SAMPO_NEW_VERSION", &package.new_version);
+        if let Some(old) = &package.old_version {
+            cmd.env("SAMPO_OLD_VERSION", old);
+        }
+    }
+
+    let input = serde_json::to_string(&HookInput {
+        hook: hook.as_str(),
+        dry_run,
+        packages,
+    })
+    .map_err(|err| SampoError::InvalidData(format!("failed to serialize hook input: {err}")))?;
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|err| io_error_with_path(err, program))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // Hooks that ignore their input may exit before reading it.
+        match stdin.write_all(input.as_bytes()) {
+            Err(err) if err.kind() != io::ErrorKind::BrokenPipe => return Err(err.into()),
+            _ => {}
+        }
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(hook.error(format!(
+            "{} hook failed ({status}): {}",
+            hook.as_str(),
+            argv.join(" ")
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(toml: &str) -> Result<Hooks, SampoError> {
+        Hooks::parse(&toml::from_str(toml).unwrap())
+    }
+
+    #[test]
+    fn parses_hook_commands() {
+        let hooks = parse(
+            "pre_release = [\"cargo\", \"run\", \"-p\", \"codegen\"]\npost_publish_package = [\"./smoke.sh\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            hooks.command(Hook::PreRelease),
+            Some(&["cargo", "run", "-p", "codegen"].map(String::from)[..])
+        );
+        assert_eq!(
+            hooks.command(Hook::PostPublishPackage),
+            Some(&["./smoke.sh".to_string()][..])
+        );
+        assert_eq!(hooks.command(Hook::PostRelease), None);
+    }
+
+    #[test]
+    fn rejects_invalid_hooks() {
+        let err = parse("pre_build = [\"make\"]").unwrap_err();
+        assert!(err.to_string().contains("Unknown hook 'hooks.pre_build'"));
+        let err = parse("pre_release = \"make docs\"").unwrap_err();
+        assert!(err.to_string().contains("must be a non-empty array"));
+        let err = parse("pre_release = []").unwrap_err();
+        assert!(err.to_string().contains("must be a non-empty array"));
+    }
+}
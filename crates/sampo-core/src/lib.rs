@@ -1,54 +1,70 @@
 pub mod adapters;
+pub mod calver;
 pub mod changeset;
 pub mod config;
 pub mod enrichment;
 pub mod errors;
 pub mod filters;
 pub mod git;
+pub mod hooks;
 pub mod markdown;
 pub mod prerelease;
 pub mod process;
+pub mod provenance;
 pub mod publish;
 pub mod release;
 pub mod tag_template;
+pub mod trusted_publishing;
 pub mod types;
+pub mod version_files;
 pub mod workspace;
+pub mod yank;
 
 /// Common User-Agent header for all HTTP requests.
 pub const USER_AGENT: &str = concat!("sampo-core/", env!("CARGO_PKG_VERSION"));
 
 // Re-export commonly used items
+pub use calver::CalverFormat;
 pub use changeset::{
     ChangesetInfo, load_changesets, parse_changeset, render_changeset_markdown,
     render_changeset_markdown_with_tags,
 };
-pub use config::Config;
+pub use config::{
+    CascadeGroup, CascadePolicy, Config, ExistenceCheck, FloatingTag, TagSigning, VersioningScheme,
+    ZeroMajorPolicy,
+};
 pub use enrichment::{
     AcknowledgmentStyle, CommitInfo, GitHubUserInfo, detect_github_repo_slug,
     detect_github_repo_slug_with_config, enrich_changeset_message, get_commit_hash_for_path,
 };
 pub use errors::{Result, SampoError, WorkspaceError};
 pub use filters::{filter_members, list_visible_packages, should_ignore_package, wildcard_match};
-pub use git::current_branch;
+pub use git::{commit_paths, current_branch, repository_path, working_tree_changes};
+pub use hooks::{Hook, Hooks};
 pub use markdown::format_markdown_list_item;
 pub use prerelease::{
     VersionChange, enter_prerelease, exit_prerelease, restore_preserved_changesets,
     validate_prerelease_entry,
 };
-pub use publish::{PublishExtraArgs, run_publish, tag_published_crate, topo_order};
+pub use publish::{
+    PublishExtraArgs, PublishOptions, run_publish, run_publish_with_options, tag_published_crate,
+    topo_order,
+};
 pub use release::{
-    build_dependency_updates, bump_version, create_dependency_update_entry,
+    ReleaseSelection, build_dependency_updates, bump_version, create_dependency_update_entry,
     create_fixed_dependency_policy_entry, create_structural_version_coupling_entry,
     detect_all_dependency_explanations, detect_fixed_dependency_policy_packages,
-    format_dependency_updates_message, infer_bump_from_versions, run_release,
-    run_stabilize_release,
+    extract_changelog_section, format_dependency_updates_message, infer_bump_from_versions,
+    plan_release, plan_stabilize_release, render_release_commit_message, resolve_fixed_groups,
+    run_release, run_release_with_selection, run_stabilize_release,
 };
 pub use types::{
     Bump, ChangelogCategory, ConstraintCheckResult, ConstraintViolation, DependencyUpdate,
-    PackageInfo, PackageKind, ParsedChangeType, PublishOutput, ReleaseOutput, ReleasedPackage,
-    Workspace,
+    PackageInfo, PackageKind, ParsedChangeType, PublishOutput, PublishRetry, RegistryPublish,
+    RegistryStatus, ReleaseOutput, ReleasedPackage, RetriedOperation, Workspace,
 };
 pub use workspace::{discover_packages_at, discover_workspace, find_sampo_root};
+pub use yank::{YankOptions, YankOutput, run_yank};
 
 #[cfg(test)]
 mod release_tests;
@@ -0,0 +1,274 @@
+//! In-toto statements carrying SLSA provenance for published packages.
+//!
+//! Each statement names the package's artifacts (or, when the ecosystem leaves no
+//! artifact behind, its package URL and release commit) and records how and where
+//! the release was built. It is written next to the release, and the GitHub Action
+//! uploads it as a release asset.
+
+use crate::errors::{Result, SampoError, io_error_with_path};
+use crate::git::head_commit;
+use crate::types::PackageInfo;
+use serde_json::{Map, Value, json};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+const SLSA_PROVENANCE_V1: &str = "https://slsa.dev/provenance/v1";
+const BUILD_TYPE: &str = "https://github.com/bruits/sampo/publish/v1";
+const SAMPO_BUILDER_ID: &str = "https://github.com/bruits/sampo";
+
+/// File name of the statement for the release tagged `tag`.
+pub fn statement_file_name(tag: &str) -> String {
+    let sanitized: String = tag
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{sanitized}.intoto.json")
+}
+
+/// Write the provenance statement of `package`, released as `tag` from the repository
+/// at `repo_root`, into `dir`. Returns the path of the statement.
+pub(crate) fn write_statement(
+    repo_root: &Path,
+    dir: &Path,
+    package: &PackageInfo,
+    tag: &str,
+    artifacts: &[PathBuf],
+) -> Result<PathBuf> {
+    let commit = head_commit(repo_root).ok();
+    let mut subjects = Vec::new();
+    for artifact in artifacts {
+        let bytes =
+            fs::read(artifact).map_err(|e| SampoError::Io(io_error_with_path(e, artifact)))?;
+        let name = artifact
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| artifact.display().to_string());
+        subjects.push(json!({ "name": name, "digest": { "sha256": sha256_hex(&bytes) } }));
+    }
+    if subjects.is_empty() {
+        let Some(commit) = &commit else {
+            return Err(SampoError::Publish(format!(
+                "cannot write provenance for {}: no artifact to digest and no git commit",
+                package.display_name(true)
+            )));
+        };
+        subjects.push(json!({ "name": package.package_url(), "digest": { "gitCommit": commit } }));
+    }
+
+    let finished_on = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let statement = build_statement(
+        package,
+        tag,
+        subjects,
+        commit.as_deref(),
+        &finished_on,
+        |key| std::env::var(key).ok().filter(|value| !value.is_empty()),
+    );
+
+    fs::create_dir_all(dir).map_err(|e| SampoError::Io(io_error_with_path(e, dir)))?;
+    let path = dir.join(statement_file_name(tag));
+    let mut text = serde_json::to_string_pretty(&statement)
+        .map_err(|e| SampoError::Publish(format!("failed to serialize provenance: {e}")))?;
+    text.push('\n');
+    fs::write(&path, text).map_err(|e| SampoError::Io(io_error_with_path(e, &path)))?;
+    Ok(path)
+}
+
+/// The in-toto statement; `env` reads the CI environment describing the build.
+fn build_statement(
+    package: &PackageInfo,
+    tag: &str,
+    subjects: Vec<Value>,
+    commit: Option<&str>,
+    finished_on: &str,
+    env: impl Fn(&str) -> Option<String>,
+) -> Value {
+    let server = env("GITHUB_SERVER_URL").unwrap_or_else(|| "https://github.com".to_string());
+    let repository = env("GITHUB_REPOSITORY");
+
+    let mut source = Map::new();
+    if let Some(repository) = &repository {
+        source.insert(
+            "uri".into(),
+            json!(format!("git+{server}/{repository}@refs/tags/{tag}")),
+        );
+    }
+    if let Some(commit) = commit {
+        source.insert("digest".into(), json!({ "gitCommit": commit }));
+    }
+    let resolved_dependencies = if source.is_empty() {
+        Vec::new()
+    } else {
+        vec![Value::Object(source)]
+    };
+
+    // A workflow run identifies the build; outside CI, Sampo itself is the builder.
+    let builder_id = match (&repository, env("GITHUB_WORKFLOW_REF")) {
+        (Some(_), Some(workflow)) => format!("{server}/{workflow}"),
+        _ => SAMPO_BUILDER_ID.to_string(),
+    };
+    let mut metadata = Map::new();
+    if let (Some(repository), Some(run_id)) = (&repository, env("GITHUB_RUN_ID")) {
+        let attempt = env("GITHUB_RUN_ATTEMPT").unwrap_or_else(|| "1".to_string());
+        metadata.insert(
+            "invocationId".into(),
+            json!(format!(
+                "{server}/{repository}/actions/runs/{run_id}/attempts/{attempt}"
+            )),
+        );
+    }
+    metadata.insert("finishedOn".into(), json!(finished_on));
+
+    json!({
+        "_type": STATEMENT_TYPE,
+        "subject": subjects,
+        "predicateType": SLSA_PROVENANCE_V1,
+        "predicate": {
+            "buildDefinition": {
+                "buildType": BUILD_TYPE,
+                "externalParameters": {
+                    "package": package.package_url(),
+                    "tag": tag,
+                },
+                "resolvedDependencies": resolved_dependencies,
+            },
+            "runDetails": {
+                "builder": {
+                    "id": builder_id,
+                    "version": { "sampo-core": env!("CARGO_PKG_VERSION") },
+                },
+                "metadata": metadata,
+            },
+        },
+    })
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PackageKind;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    fn package() -> PackageInfo {
+        PackageInfo {
+            name: "@acme/ui".to_string(),
+            identifier: PackageInfo::dependency_identifier(PackageKind::Npm, "@acme/ui"),
+            version: "2.0.0".to_string(),
+            path: PathBuf::from("packages/ui"),
+            internal_deps: BTreeSet::new(),
+            internal_dev_deps: BTreeSet::new(),
+            kind: PackageKind::Npm,
+        }
+    }
+
+    #[test]
+    fn statement_file_names_are_flat() {
+        assert_eq!(
+            statement_file_name("npm-@acme/ui-v2.0.0"),
+            "npm--acme-ui-v2.0.0.intoto.json"
+        );
+    }
+
+    #[test]
+    fn statement_records_the_workflow_run() {
+        let env: BTreeMap<&str, &str> = [
+            ("GITHUB_REPOSITORY", "acme/monorepo"),
+            (
+                "GITHUB_WORKFLOW_REF",
+                "acme/monorepo/.github/workflows/release.yml@refs/heads/main",
+            ),
+            ("GITHUB_RUN_ID", "42"),
+            ("GITHUB_RUN_ATTEMPT", "2"),
+        ]
+        .into_iter()
+        .collect();
+        let subjects = vec![json!({ "name": "ui-2.0.0.tgz", "digest": { "sha256": "abc" } })];
+        let statement = build_statement(
+            &package(),
+            "ui-v2.0.0",
+            subjects,
+            Some("0123abcd"),
+            "2026-01-01T00:00:00Z",
+            |key| env.get(key).map(|value| value.to_string()),
+        );
+
+        assert_eq!(statement["_type"], STATEMENT_TYPE);
+        assert_eq!(statement["predicateType"], SLSA_PROVENANCE_V1);
+        assert_eq!(statement["subject"][0]["name"], "ui-2.0.0.tgz");
+        let predicate = &statement["predicate"];
+        assert_eq!(
+            predicate["buildDefinition"]["externalParameters"]["package"],
+            "pkg:npm/%40acme/ui@2.0.0"
+        );
+        assert_eq!(
+            predicate["buildDefinition"]["resolvedDependencies"][0],
+            json!({
+                "uri": "git+https://github.com/acme/monorepo@refs/tags/ui-v2.0.0",
+                "digest": { "gitCommit": "0123abcd" },
+            })
+        );
+        assert_eq!(
+            predicate["runDetails"]["builder"]["id"],
+            "https://github.com/acme/monorepo/.github/workflows/release.yml@refs/heads/main"
+        );
+        assert_eq!(
+            predicate["runDetails"]["metadata"]["invocationId"],
+            "https://github.com/acme/monorepo/actions/runs/42/attempts/2"
+        );
+    }
+
+    #[test]
+    fn statement_outside_ci_names_sampo_as_builder() {
+        let statement = build_statement(
+            &package(),
+            "ui-v2.0.0",
+            Vec::new(),
+            None,
+            "2026-01-01T00:00:00Z",
+            |_| None,
+        );
+        let predicate = &statement["predicate"];
+        assert_eq!(predicate["runDetails"]["builder"]["id"], SAMPO_BUILDER_ID);
+        assert_eq!(
+            predicate["buildDefinition"]["resolvedDependencies"],
+            json!([])
+        );
+        assert!(
+            predicate["runDetails"]["metadata"]
+                .get("invocationId")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn writes_artifact_digests() {
+        let temp = tempfile::tempdir().unwrap();
+        let artifact = temp.path().join("ui-2.0.0.tgz");
+        fs::write(&artifact, b"hello").unwrap();
+        let dir = temp.path().join("provenance");
+
+        let path = write_statement(temp.path(), &dir, &package(), "ui-v2.0.0", &[artifact])
+            .expect("statement written");
+        assert_eq!(path, dir.join("ui-v2.0.0.intoto.json"));
+        let statement: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            statement["subject"][0]["digest"]["sha256"],
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
+}
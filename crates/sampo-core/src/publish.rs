@@ -1,14 +1,22 @@
-use crate::adapters::{PackageAdapter, PublishOutcome};
-use crate::tag_template::Placeholder;
-use crate::types::{PackageInfo, PackageKind, PublishOutput};
+use crate::adapters::{PackageAdapter, PublishOutcome, has_flag};
+use crate::hooks::{Hook, HookPackage, run_hook};
+use crate::provenance;
+use crate::tag_template::{Placeholder, validate_tag_name};
+use crate::trusted_publishing::{MintedToken, OidcProvider, mint_token};
+use crate::types::{
+    PackageInfo, PackageKind, PublishOutput, PublishRetry, RegistryPublish, RegistryStatus,
+    RetriedOperation,
+};
 use crate::{
-    Config, current_branch, discover_workspace,
+    Config, ExistenceCheck, current_branch, discover_workspace,
     errors::{Result, SampoError},
+    extract_changelog_section,
     filters::should_ignore_package,
 };
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
 /// Holds universal and per-ecosystem extra arguments for publish commands.
 ///
@@ -42,6 +50,20 @@ impl PublishExtraArgs {
     }
 }
 
+/// Options of `sampo publish` that are not forwarded to the publish commands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PublishOptions {
+    /// Overrides `publish.existence_check`; `--offline-check` sets [`ExistenceCheck::Tags`].
+    pub existence_check: Option<ExistenceCheck>,
+    /// Only create the missing release tags of versions the registries already serve,
+    /// without publishing anything.
+    pub repair_tags: bool,
+    /// Overrides `publish.trusted_publishing`.
+    pub trusted_publishing: Option<bool>,
+    /// Overrides `publish.provenance`.
+    pub provenance: Option<bool>,
+}
+
 /// Publishes all publishable packages in a workspace to their registries in dependency order.
 ///
 /// This function discovers all packages in the workspace, determines which ones are
@@ -81,12 +103,25 @@ pub fn run_publish(
     root: &std::path::Path,
     dry_run: bool,
     extra_args: &PublishExtraArgs,
+) -> Result<PublishOutput> {
+    run_publish_with_options(root, dry_run, extra_args, &PublishOptions::default())
+}
+
+/// Publish with the given [`PublishOptions`]; see [`run_publish`].
+pub fn run_publish_with_options(
+    root: &std::path::Path,
+    dry_run: bool,
+    extra_args: &PublishExtraArgs,
+    options: &PublishOptions,
 ) -> Result<PublishOutput> {
     let ws = discover_workspace(root)?;
     let config = Config::load(&ws.root)?;
 
+    // Packages with their own `[packages."<package>".git]` branches publish from those.
     let branch = current_branch()?;
-    if !config.is_release_branch(&branch) {
+    let on_release_branch =
+        |info: &PackageInfo| config.for_package(info).is_release_branch(&branch);
+    if !config.is_release_branch(&branch) && !ws.members.iter().any(on_release_branch) {
         return Err(SampoError::Release(format!(
             "Branch '{}' is not configured for publishing (allowed: {:?})",
             branch,
@@ -100,8 +135,8 @@ pub fn run_publish(
     let mut all_non_ignored: Vec<&PackageInfo> = Vec::new();
 
     for c in &ws.members {
-        // Skip ignored packages
-        if should_ignore_package(&config, &ws, c)? {
+        // Skip ignored packages, and packages released from another branch
+        if should_ignore_package(&config, &ws, c)? || !on_release_branch(c) {
             continue;
         }
 
@@ -130,7 +165,11 @@ pub fn run_publish(
         println!("No publishable packages were found in the workspace.");
         return Ok(PublishOutput {
             tags: Vec::new(),
+            floating_tags: Vec::new(),
             dry_run,
+            retries: Vec::new(),
+            provenance: BTreeMap::new(),
+            registries: Vec::new(),
         });
     }
 
@@ -141,7 +180,7 @@ pub fn run_publish(
         .copied()
         .filter(|p| !p.version.trim().is_empty())
         .collect();
-    for warning in check_tag_conflicts(&config, &taggable)? {
+    for warning in check_tag_conflicts(&ws.root, &config, &taggable)? {
         eprintln!("Warning: {warning}");
     }
 
@@ -175,11 +214,45 @@ pub fn run_publish(
     // Compute publish order (topological: deps first) for all publishable crates.
     let order = topo_order(&id_to_package, &publishable)?;
 
+    // Plain-name `[packages."<name>"]` tables only meet their ecosystem here, so reject
+    // unsupported `registries` before anything is queried or published.
+    for package in id_to_package.values() {
+        let adapter = PackageAdapter::from_kind(package.kind);
+        publish_targets_of(&config, adapter, package)?;
+    }
+
+    // Repairing tags means comparing them with what the registries serve.
+    let existence_check = if options.repair_tags {
+        ExistenceCheck::Both
+    } else {
+        options
+            .existence_check
+            .unwrap_or(config.publish_existence_check)
+    };
+    if existence_check != ExistenceCheck::Registry && !ws.root.join(".git").exists() {
+        return Err(SampoError::Publish(
+            "checking release tags for published versions requires a git repository".into(),
+        ));
+    }
+
     // Build list of packages that actually need publishing (version doesn't exist on registry).
     // We check version_exists() BEFORE dry-run validation to avoid unnecessary compilation
     // and failures when all packages are already published.
-    println!("Checking registry for existing versions…");
-    let mut publish_targets: Vec<(&PackageInfo, PackageAdapter, std::path::PathBuf)> = Vec::new();
+    if existence_check == ExistenceCheck::Tags {
+        println!("Checking release tags for existing versions…");
+    } else {
+        println!("Checking registry for existing versions…");
+    }
+    // Versions the registry serves that have no release tag.
+    let mut untagged: Vec<&PackageInfo> = Vec::new();
+    let mut retries: Vec<PublishRetry> = Vec::new();
+    let mut registries: Vec<RegistryPublish> = Vec::new();
+    let mut publish_targets: Vec<(
+        &PackageInfo,
+        PackageAdapter,
+        std::path::PathBuf,
+        Vec<PublishTarget>,
+    )> = Vec::new();
 
     for identifier in &order {
         let package = id_to_package.get(identifier).copied().ok_or_else(|| {
@@ -190,60 +263,148 @@ pub fn run_publish(
         })?;
         let adapter = PackageAdapter::from_kind(package.kind);
         let manifest = adapter.manifest_path(&package.path);
-
-        match adapter.version_exists(&package.name, &package.version, Some(manifest.as_path())) {
-            Ok(true) => {
-                println!(
-                    "  - {} (already exists on {})",
-                    package.display_name(true),
-                    package.kind.display_name()
-                );
+        let targets = publish_targets_of(&config, adapter, package)?;
+
+        let tagged = existence_check != ExistenceCheck::Registry
+            && package_tag_exists(&ws.root, &config, package)?;
+        // The tag is only created once every registry serves the version, so an untagged
+        // package may still be published on some of its registries.
+        if existence_check == ExistenceCheck::Tags && (tagged || targets.len() == 1) {
+            if tagged {
+                println!("  - {} (already tagged)", package.display_name(true));
+            } else {
+                publish_targets.push((package, adapter, manifest, targets));
             }
-            Ok(false) => {
-                publish_targets.push((package, adapter, manifest));
+            continue;
+        }
+
+        let mut pending = Vec::new();
+        for target in targets {
+            let registry = target.label(package);
+            let exists = retry_transient(
+                &config,
+                package,
+                RetriedOperation::RegistryCheck,
+                &mut retries,
+                |_| target.version_exists(adapter, package, &manifest),
+            );
+            match exists {
+                Ok(true) => {
+                    println!(
+                        "  - {} (already exists on {})",
+                        package.display_name(true),
+                        registry
+                    );
+                    if let Some(name) = &target.registry {
+                        registries.push(RegistryPublish {
+                            package: package.canonical_identifier().to_string(),
+                            registry: name.clone(),
+                            status: RegistryStatus::AlreadyPublished,
+                        });
+                    }
+                }
+                Ok(false) => {
+                    if tagged {
+                        eprintln!(
+                            "Warning: {}@{} is tagged as released, but the {} registry does not serve it. Will attempt publish.",
+                            package.name, package.version, registry
+                        );
+                    }
+                    pending.push(target);
+                }
+                Err(e) if tagged => {
+                    eprintln!(
+                        "Warning: could not check {} registry for {}@{}: {}. Its release tag marks it as published.",
+                        registry, package.name, package.version, e
+                    );
+                }
+                Err(e) => {
+                    // If we can't check, include in publish targets to be safe
+                    eprintln!(
+                        "Warning: could not check {} registry for {}@{}: {}. Will attempt publish.",
+                        registry, package.name, package.version, e
+                    );
+                    pending.push(target);
+                }
             }
-            Err(e) => {
-                // If we can't check, include in publish targets to be safe
+        }
+
+        if !pending.is_empty() {
+            publish_targets.push((package, adapter, manifest, pending));
+        } else if existence_check == ExistenceCheck::Both && !tagged {
+            if !options.repair_tags {
                 eprintln!(
-                    "Warning: could not check {} registry for {}@{}: {}. Will attempt publish.",
-                    package.kind.display_name(),
+                    "Warning: {}@{} is published on the {} registry, but its release tag '{}' is missing. Run `sampo publish --repair-tags` to create it.",
                     package.name,
                     package.version,
-                    e
+                    package.kind.display_name(),
+                    config.package_tag_name(&ws.root, package, &package.version)
                 );
-                publish_targets.push((package, adapter, manifest));
             }
+            untagged.push(package);
         }
     }
 
+    if options.repair_tags {
+        return repair_missing_tags(&ws.root, &config, &untagged, dry_run, retries);
+    }
+
     if publish_targets.is_empty() {
         println!("All packages are already published. Nothing to do.");
         // Still need to handle private package tagging below
     } else {
         println!("Publish plan:");
-        for (package, _, _) in &publish_targets {
-            println!("  - {}", package.display_name(true));
+        for (package, _, _, targets) in &publish_targets {
+            let names: Vec<&str> = targets
+                .iter()
+                .filter_map(|target| target.registry.as_deref())
+                .collect();
+            if names.is_empty() {
+                println!("  - {}", package.display_name(true));
+            } else {
+                println!("  - {} to {}", package.display_name(true), names.join(", "));
+            }
         }
     }
 
+    let hook_packages: Vec<HookPackage> = publish_targets
+        .iter()
+        .map(|(package, _, _, _)| HookPackage::new(&ws.root, package, None, &package.version))
+        .collect();
+    if !hook_packages.is_empty() {
+        run_hook(
+            &ws.root,
+            &config.hooks,
+            Hook::PrePublish,
+            dry_run,
+            &hook_packages,
+            None,
+        )?;
+    }
+
+    if !dry_run {
+        check_tag_signing(&ws.root, &config)?;
+    }
+
     if !dry_run && !publish_targets.is_empty() {
         println!("Validating publish commands (dry-run)…");
 
-        let mut packages_by_kind: BTreeMap<
-            crate::types::PackageKind,
-            Vec<(&PackageInfo, &std::path::Path)>,
-        > = BTreeMap::new();
-        for (package, _, manifest) in &publish_targets {
-            packages_by_kind
-                .entry(package.kind)
-                .or_default()
-                .push((*package, manifest.as_path()));
+        // Packages are validated together per ecosystem and registry.
+        let mut packages_by_target = BTreeMap::new();
+        for (package, _, manifest, targets) in &publish_targets {
+            for target in targets {
+                packages_by_target
+                    .entry((package.kind, target.args.as_slice()))
+                    .or_insert_with(Vec::new)
+                    .push((*package, manifest.as_path()));
+            }
         }
 
         let mut not_validated: Vec<String> = Vec::new();
-        for (kind, packages) in &packages_by_kind {
+        for ((kind, target_args), packages) in &packages_by_target {
             let adapter = PackageAdapter::from_kind(*kind);
-            let args = extra_args.args_for_kind(*kind);
+            let mut args = extra_args.args_for_kind(*kind);
+            args.extend(target_args.iter().cloned());
             not_validated.extend(adapter.publish_dry_run(&ws.root, packages, &args)?);
         }
 
@@ -257,45 +418,259 @@ pub fn run_publish(
         }
     }
 
+    let trusted_publishing = options
+        .trusted_publishing
+        .unwrap_or(config.publish_trusted_publishing);
+    let write_provenance = options.provenance.unwrap_or(config.publish_provenance);
+
+    // Registry tokens are only minted for real publishes to crates.io and PyPI.
+    let oidc = if trusted_publishing && !dry_run {
+        let oidc = OidcProvider::from_env();
+        let needs_token = publish_targets
+            .iter()
+            .any(|(package, adapter, manifest, targets)| {
+                targets.iter().any(|target| {
+                    let mut args = extra_args.args_for_kind(package.kind);
+                    args.extend(target.args.iter().cloned());
+                    adapter.trusted_registry(manifest, &args).is_some()
+                })
+            });
+        if oidc.is_none() && needs_token {
+            eprintln!(
+                "Warning: trusted publishing is enabled, but no OIDC token is available (ACTIONS_ID_TOKEN_REQUEST_URL is not set). Publishing with the configured credentials."
+            );
+        }
+        oidc
+    } else {
+        None
+    };
+
     let mut tags_to_create: Vec<String> = Vec::new();
+    let mut floating_tags: Vec<String> = Vec::new();
+    let mut provenance_statements: BTreeMap<String, std::path::PathBuf> = BTreeMap::new();
     let mut any_published = false;
     let mut not_simulated: Vec<String> = Vec::new();
 
-    for (package, adapter, manifest) in &publish_targets {
-        let args = extra_args.args_for_kind(package.kind);
-        if adapter.publish(manifest.as_path(), dry_run, &args)? == PublishOutcome::DryRunSkipped {
+    for ((package, adapter, manifest, targets), hook_package) in
+        publish_targets.iter().zip(&hook_packages)
+    {
+        run_hook(
+            &ws.root,
+            &config.hooks,
+            Hook::PrePublishPackage,
+            dry_run,
+            &hook_packages,
+            Some(hook_package),
+        )?;
+
+        // Each registry is published independently; the package is only tagged once all
+        // of them accepted it.
+        let mut published = false;
+        let mut skipped = false;
+        let mut unverified = None;
+        let mut failures: Vec<(String, SampoError)> = Vec::new();
+        for target in targets {
+            let registry_label = target.label(package);
+            let mut args = extra_args.args_for_kind(package.kind);
+            args.extend(target.args.iter().cloned());
+            if write_provenance
+                && !dry_run
+                && package.kind == PackageKind::Npm
+                && !has_flag(&args, "--provenance")
+            {
+                args.push("--provenance".to_string());
+            }
+            let minted = match (&oidc, adapter.trusted_registry(manifest, &args)) {
+                (Some(oidc), Some(registry)) => {
+                    println!(
+                        "Exchanging the OIDC token for a {} publish token…",
+                        registry.display_name()
+                    );
+                    match retry_transient(
+                        &config,
+                        package,
+                        RetriedOperation::TokenExchange,
+                        &mut retries,
+                        |_| mint_token(registry, oidc),
+                    ) {
+                        Ok(minted) => Some(minted),
+                        Err(e) => {
+                            failures.push((registry_label, e));
+                            continue;
+                        }
+                    }
+                }
+                _ => None,
+            };
+            let outcome = retry_transient(
+                &config,
+                package,
+                RetriedOperation::Publish,
+                &mut retries,
+                |attempt| {
+                    // The failed attempt may still have reached the registry; publishing
+                    // the same version again would fail for good.
+                    if attempt > 0
+                        && !dry_run
+                        && existence_check != ExistenceCheck::Tags
+                        && adapter.observes_publish(manifest)
+                        && matches!(target.version_exists(*adapter, package, manifest), Ok(true))
+                    {
+                        println!(
+                            "{} reached the {} registry despite the failure.",
+                            package.display_name(true),
+                            registry_label
+                        );
+                        return Ok(PublishOutcome::Ran);
+                    }
+                    adapter.publish(
+                        manifest,
+                        dry_run,
+                        &args,
+                        minted.as_ref().map(MintedToken::token),
+                    )
+                },
+            );
+            if let Some(minted) = &minted {
+                minted.revoke();
+            }
+            let outcome = match outcome {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    if let Some(name) = &target.registry {
+                        registries.push(RegistryPublish {
+                            package: package.canonical_identifier().to_string(),
+                            registry: name.clone(),
+                            status: RegistryStatus::Failed {
+                                error: e.to_string(),
+                            },
+                        });
+                    }
+                    failures.push((registry_label, e));
+                    continue;
+                }
+            };
+            if outcome == PublishOutcome::DryRunSkipped {
+                skipped = true;
+            } else {
+                published = true;
+            }
+
+            // Dependents resolve this version from the registry, so wait for it to be served.
+            if !dry_run
+                && outcome == PublishOutcome::Ran
+                && existence_check != ExistenceCheck::Tags
+                && let Err(e) = verify_published(&config, package, *adapter, manifest, target)
+            {
+                unverified.get_or_insert(e);
+            }
+            if let Some(name) = &target.registry {
+                registries.push(RegistryPublish {
+                    package: package.canonical_identifier().to_string(),
+                    registry: name.clone(),
+                    status: match outcome {
+                        PublishOutcome::DryRunSkipped => RegistryStatus::Skipped,
+                        PublishOutcome::Ran if dry_run => RegistryStatus::DryRun,
+                        PublishOutcome::Ran => RegistryStatus::Published,
+                    },
+                });
+            }
+        }
+        if !failures.is_empty() {
+            let source = if targets.len() == 1 {
+                failures.remove(0).1
+            } else {
+                print_registries(&registries);
+                registries_failure(package, &failures, &registries)
+            };
+            return Err(SampoError::PublishIncomplete {
+                source: Box::new(source),
+                output: partial_output(
+                    &tags_to_create,
+                    &floating_tags,
+                    dry_run,
+                    &retries,
+                    &provenance_statements,
+                    &registries,
+                ),
+            });
+        }
+        if skipped && !published {
             not_simulated.push(package.display_name(true));
         }
         any_published = true;
+        let outcome = if published {
+            PublishOutcome::Ran
+        } else {
+            PublishOutcome::DryRunSkipped
+        };
 
         // Publishable packages always carry a version, so the tag is well-formed.
-        let tag = config.build_tag_name(package.kind, &package.name, &package.version);
+        let tag = config.package_tag_name(&ws.root, package, &package.version);
+
+        if let Some(e) = unverified {
+            // Tag what the registries accepted: a re-run then skips it and resumes with
+            // the packages that were not published yet.
+            if tag_published_crate(&ws.root, &config, package).is_ok() {
+                tags_to_create.push(tag);
+                floating_tags.extend(move_floating_tags(&ws.root, &config, package, false)?);
+            }
+            print_registries(&registries);
+            return Err(SampoError::PublishIncomplete {
+                source: Box::new(e),
+                output: partial_output(
+                    &tags_to_create,
+                    &floating_tags,
+                    dry_run,
+                    &retries,
+                    &provenance_statements,
+                    &registries,
+                ),
+            });
+        }
+
+        if write_provenance && !dry_run && outcome == PublishOutcome::Ran {
+            let dir = ws.root.join(&config.publish_provenance_dir);
+            let artifacts = adapter.published_artifacts(manifest, package);
+            match provenance::write_statement(&ws.root, &dir, package, &tag, &artifacts) {
+                Ok(path) => {
+                    println!("Wrote provenance statement {}", path.display());
+                    provenance_statements.insert(tag.clone(), path);
+                }
+                Err(e) => eprintln!(
+                    "Warning: failed to write the provenance statement of {}@{}: {}",
+                    package.name, package.version, e
+                ),
+            }
+        }
 
         // Tag immediately after successful publish to ensure partial failures still tag what succeeded
         if !dry_run {
-            if let Err(e) = tag_published_crate(
-                &ws.root,
-                &config,
-                package.kind,
-                &package.name,
-                &package.version,
-            ) {
-                eprintln!(
+            match tag_published_crate(&ws.root, &config, package) {
+                Ok(_) => {
+                    tags_to_create.push(tag);
+                    floating_tags.extend(move_floating_tags(&ws.root, &config, package, false)?);
+                }
+                // Unsigned tags are not an acceptable fallback when signing is required.
+                Err(e) if config.git_sign_tags.is_some() => return Err(e),
+                Err(e) => eprintln!(
                     "Warning: failed to create tag for {}@{}: {}",
                     package.name, package.version, e
-                );
-            } else {
-                tags_to_create.push(tag);
+                ),
             }
-        } else if !package_tag_exists(
-            &ws.root,
-            &config,
-            package.kind,
-            &package.name,
-            &package.version,
-        )? {
+        } else if !package_tag_exists(&ws.root, &config, package)? {
             tags_to_create.push(tag);
+            floating_tags.extend(move_floating_tags(&ws.root, &config, package, true)?);
         }
+
+        run_hook(
+            &ws.root,
+            &config.hooks,
+            Hook::PostPublishPackage,
+            dry_run,
+            &hook_packages,
+            Some(hook_package),
+        )?;
     }
 
     // Determine which private (non-publishable) packages still need tags.
@@ -316,40 +691,47 @@ pub fn run_publish(
             );
             continue;
         }
-        if !package_tag_exists(
-            &ws.root,
-            &config,
-            package.kind,
-            &package.name,
-            &package.version,
-        )? {
+        if !package_tag_exists(&ws.root, &config, package)? {
             private_packages_to_tag.push(*package);
         }
     }
 
     if any_published || !private_packages_to_tag.is_empty() {
         for package in &private_packages_to_tag {
-            let tag = config.build_tag_name(package.kind, &package.name, &package.version);
+            let tag = config.package_tag_name(&ws.root, package, &package.version);
             if !dry_run {
-                if let Err(e) = tag_published_crate(
-                    &ws.root,
-                    &config,
-                    package.kind,
-                    &package.name,
-                    &package.version,
-                ) {
-                    eprintln!(
+                match tag_published_crate(&ws.root, &config, package) {
+                    Ok(_) => {
+                        tags_to_create.push(tag);
+                        floating_tags
+                            .extend(move_floating_tags(&ws.root, &config, package, false)?);
+                    }
+                    Err(e) if config.git_sign_tags.is_some() => return Err(e),
+                    Err(e) => eprintln!(
                         "Warning: failed to create tag for {}@{}: {}",
                         package.name, package.version, e
-                    );
-                } else {
-                    tags_to_create.push(tag);
+                    ),
                 }
             } else {
                 tags_to_create.push(tag);
+                floating_tags.extend(move_floating_tags(&ws.root, &config, package, true)?);
             }
         }
     }
+    tags_to_create.extend(floating_tags.iter().cloned());
+
+    if !hook_packages.is_empty() {
+        run_hook(
+            &ws.root,
+            &config.hooks,
+            Hook::PostPublish,
+            dry_run,
+            &hook_packages,
+            None,
+        )?;
+    }
+
+    print_registries(&registries);
 
     if dry_run {
         println!("Dry-run complete.");
@@ -358,6 +740,18 @@ pub fn run_publish(
         }
     } else {
         println!("Publish complete.");
+        if !retries.is_empty() {
+            println!("Retried after transient failures:");
+            for retry in &retries {
+                println!(
+                    "  - {} of {} (retry {}): {}",
+                    retry.operation.as_str(),
+                    retry.package,
+                    retry.attempt,
+                    retry.error
+                );
+            }
+        }
         if !not_simulated.is_empty() {
             eprintln!(
                 "Warning: {} did not reach the registry: the publish was skipped as a dry run, but the release was still tagged.",
@@ -368,10 +762,374 @@ pub fn run_publish(
 
     Ok(PublishOutput {
         tags: tags_to_create,
+        floating_tags,
         dry_run,
+        retries,
+        provenance: provenance_statements,
+        registries,
     })
 }
 
+/// A registry a package is published to.
+#[derive(Debug, Clone)]
+struct PublishTarget {
+    /// The name listed in the package's `registries`, or `None` for the registry its
+    /// manifest and the publish arguments select.
+    registry: Option<String>,
+    /// Arguments selecting the registry, appended to the publish command.
+    args: Vec<String>,
+}
+
+impl PublishTarget {
+    /// Name of the registry in messages.
+    fn label(&self, package: &PackageInfo) -> String {
+        match &self.registry {
+            Some(registry) => registry.clone(),
+            None => package.kind.display_name().to_string(),
+        }
+    }
+
+    fn version_exists(
+        &self,
+        adapter: PackageAdapter,
+        package: &PackageInfo,
+        manifest: &Path,
+    ) -> Result<bool> {
+        match &self.registry {
+            Some(registry) => {
+                adapter.version_exists_on(&package.name, &package.version, manifest, registry)
+            }
+            None => adapter.version_exists(&package.name, &package.version, Some(manifest)),
+        }
+    }
+}
+
+/// The registries `package` is published to: those of its `registries` setting, or the
+/// default one.
+fn publish_targets_of(
+    config: &Config,
+    adapter: PackageAdapter,
+    package: &PackageInfo,
+) -> Result<Vec<PublishTarget>> {
+    let registries = config.for_package(package).publish_registries;
+    if registries.is_empty() {
+        return Ok(vec![PublishTarget {
+            registry: None,
+            args: Vec::new(),
+        }]);
+    }
+    registries
+        .into_iter()
+        .map(|registry| {
+            let args = adapter.registry_args(package, &registry)?;
+            Ok(PublishTarget {
+                registry: Some(registry),
+                args,
+            })
+        })
+        .collect()
+}
+
+/// What a publish that fails part way did so far, reported by
+/// [`SampoError::PublishIncomplete`].
+fn partial_output(
+    tags: &[String],
+    floating_tags: &[String],
+    dry_run: bool,
+    retries: &[PublishRetry],
+    provenance: &BTreeMap<String, PathBuf>,
+    registries: &[RegistryPublish],
+) -> Box<PublishOutput> {
+    Box::new(PublishOutput {
+        tags: tags.iter().chain(floating_tags).cloned().collect(),
+        floating_tags: floating_tags.to_vec(),
+        dry_run,
+        retries: retries.to_vec(),
+        provenance: provenance.clone(),
+        registries: registries.to_vec(),
+    })
+}
+
+/// Prints the status of each registry of the packages published to several `registries`.
+fn print_registries(registries: &[RegistryPublish]) {
+    if registries.is_empty() {
+        return;
+    }
+    println!("Registries:");
+    for publish in registries {
+        match &publish.status {
+            RegistryStatus::Failed { error } => println!(
+                "  - {} on {}: failed ({})",
+                publish.package, publish.registry, error
+            ),
+            status => println!(
+                "  - {} on {}: {}",
+                publish.package,
+                publish.registry,
+                status.as_str()
+            ),
+        }
+    }
+}
+
+/// The error of a package that some of its registries rejected. It is left untagged, so
+/// a re-run publishes it to the remaining registries.
+fn registries_failure(
+    package: &PackageInfo,
+    failures: &[(String, SampoError)],
+    registries: &[RegistryPublish],
+) -> SampoError {
+    let failed: Vec<String> = failures
+        .iter()
+        .map(|(registry, e)| format!("{registry}: {e}"))
+        .collect();
+    let mut message = format!(
+        "{}@{} was not published to every registry, so it was not tagged. Failed on {}.",
+        package.name,
+        package.version,
+        failed.join("; ")
+    );
+    let done: Vec<String> = registries
+        .iter()
+        .filter(|publish| {
+            publish.package == package.canonical_identifier()
+                && !matches!(publish.status, RegistryStatus::Failed { .. })
+        })
+        .map(|publish| format!("{} ({})", publish.registry, publish.status.as_str()))
+        .collect();
+    if !done.is_empty() {
+        message.push_str(&format!(" Succeeded on {}.", done.join(", ")));
+    }
+    SampoError::Publish(message)
+}
+
+/// Creates the release tags (and floating tags) of `packages`, versions the registries
+/// serve that were never tagged, on the commits that released them. Nothing is created
+/// in a dry run.
+fn repair_missing_tags(
+    repo_root: &Path,
+    config: &Config,
+    packages: &[&PackageInfo],
+    dry_run: bool,
+    retries: Vec<PublishRetry>,
+) -> Result<PublishOutput> {
+    if packages.is_empty() {
+        println!("Every published version is tagged. Nothing to repair.");
+    } else if dry_run {
+        println!("Would create missing release tags:");
+    } else {
+        println!("Creating missing release tags:");
+    }
+
+    // Resolve every release commit first: an unknown one must not leave a partial repair.
+    let commits = packages
+        .iter()
+        .map(|package| release_commit(repo_root, config, package))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut tags = Vec::new();
+    let mut floating_tags = Vec::new();
+    for (package, commit) in packages.iter().zip(&commits) {
+        let tag = config.package_tag_name(repo_root, package, &package.version);
+        println!("  - {tag} ({})", &commit[..commit.len().min(12)]);
+        if !dry_run {
+            create_release_tag(repo_root, config, package, Some(commit))?;
+        }
+        tags.push(tag);
+        floating_tags.extend(move_floating_tags(repo_root, config, package, dry_run)?);
+    }
+    tags.extend(floating_tags.iter().cloned());
+
+    Ok(PublishOutput {
+        tags,
+        floating_tags,
+        dry_run,
+        retries,
+        provenance: BTreeMap::new(),
+        registries: Vec::new(),
+    })
+}
+
+/// The commit that released the current version of `package`: the first one adding its
+/// `## <version>` section to the package changelog, as `sampo release` does. HEAD may be
+/// any later commit, so it is never assumed.
+fn release_commit(repo_root: &Path, config: &Config, package: &PackageInfo) -> Result<String> {
+    let changelog = package
+        .path
+        .join(&config.for_package(package).changelog_path);
+    let relative = changelog.strip_prefix(repo_root).unwrap_or(&changelog);
+    let heading = format!("## {}", package.version);
+
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(repo_root)
+        .args(["log", "--reverse", "--format=%H", "-S", &heading, "--"])
+        .arg(relative)
+        .output()?;
+    if out.status.success() {
+        for commit in String::from_utf8_lossy(&out.stdout).lines() {
+            let show = Command::new("git")
+                .arg("-C")
+                .arg(repo_root)
+                .arg("show")
+                .arg(format!("{commit}:./{}", relative.display()))
+                .output()?;
+            let text = String::from_utf8_lossy(&show.stdout);
+            if show.status.success()
+                && text.lines().any(|line| {
+                    line.trim_end() == heading || line.starts_with(&format!("{heading} "))
+                })
+            {
+                return Ok(commit.to_string());
+            }
+        }
+    }
+
+    Err(SampoError::Publish(format!(
+        "Cannot find the commit that released {}@{}: no commit adds its '{}' section to {}. Tag it by hand with `git tag {} <commit>`.",
+        package.name,
+        package.version,
+        heading,
+        relative.display(),
+        config.package_tag_name(repo_root, package, &package.version)
+    )))
+}
+
+/// First pause before retrying an operation that failed for a transient reason.
+const RETRY_INITIAL_DELAY: Duration = Duration::from_secs(2);
+/// The pause doubles after each retry, up to this delay.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Runs `operation`, retrying it up to `publish.retries` times while it fails with a
+/// [`SampoError::Retryable`] error. Each retry is logged and recorded in `retries`.
+fn retry_transient<T>(
+    config: &Config,
+    package: &PackageInfo,
+    operation: RetriedOperation,
+    retries: &mut Vec<PublishRetry>,
+    run: impl FnMut(u32) -> Result<T>,
+) -> Result<T> {
+    let max = config.publish_retries(package.kind);
+    with_retries(max, run, |attempt, error, delay| {
+        eprintln!(
+            "Warning: {} of {} failed: {}. Retrying in {}s ({}/{}).",
+            operation.as_str(),
+            package.display_name(true),
+            error,
+            delay.as_secs(),
+            attempt,
+            max
+        );
+        retries.push(PublishRetry {
+            package: package.canonical_identifier().to_string(),
+            operation,
+            attempt,
+            error: error.to_string(),
+        });
+        std::thread::sleep(delay);
+    })
+}
+
+/// Calls `run` with the attempt number until it succeeds, fails for a reason that is not
+/// [retryable](SampoError::is_retryable), or has been retried `retries` times. Before
+/// each retry, `on_retry` gets the retry number, the failure and the backoff delay to wait.
+fn with_retries<T>(
+    retries: u32,
+    mut run: impl FnMut(u32) -> Result<T>,
+    mut on_retry: impl FnMut(u32, &SampoError, Duration),
+) -> Result<T> {
+    let mut attempt = 0;
+    let mut delay = RETRY_INITIAL_DELAY;
+    loop {
+        match run(attempt) {
+            Err(error) if error.is_retryable() && attempt < retries => {
+                attempt += 1;
+                on_retry(attempt, &error, delay);
+                delay = (delay * 2).min(RETRY_MAX_DELAY);
+            }
+            result => return result.map_err(SampoError::into_inner),
+        }
+    }
+}
+
+/// First pause between two registry lookups while waiting for a published version.
+const VERIFY_INITIAL_INTERVAL: Duration = Duration::from_secs(2);
+/// The pause doubles after each lookup, up to this interval.
+const VERIFY_MAX_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Waits until the registry serves the version that was just published, for up to
+/// [`Config::publish_verify_timeout`].
+fn verify_published(
+    config: &Config,
+    package: &PackageInfo,
+    adapter: PackageAdapter,
+    manifest: &Path,
+    target: &PublishTarget,
+) -> Result<()> {
+    let timeout = config.publish_verify_timeout(package.kind);
+    if timeout.is_zero() || !adapter.observes_publish(manifest) {
+        return Ok(());
+    }
+
+    let registry = target.label(package);
+    println!(
+        "Waiting for {}@{} to be available on the {} registry…",
+        package.name, package.version, registry
+    );
+    let mut last_error = None;
+    let visible = wait_until_visible(
+        timeout,
+        || match target.version_exists(adapter, package, manifest) {
+            Ok(found) => found,
+            Err(e) => {
+                last_error = Some(e);
+                false
+            }
+        },
+        std::thread::sleep,
+    );
+    if visible {
+        return Ok(());
+    }
+
+    let cause = last_error
+        .map(|e| format!(" The last lookup failed: {e}."))
+        .unwrap_or_default();
+    Err(SampoError::Publish(format!(
+        "{}@{} was published, but the {} registry still did not serve it after {}s.{} \
+         It was tagged; run `sampo publish` again once the registry catches up to publish \
+         the remaining packages, or raise `publish.verify_timeout`.",
+        package.name,
+        package.version,
+        registry,
+        timeout.as_secs(),
+        cause
+    )))
+}
+
+/// Calls `exists` until it returns true, sleeping with exponential backoff in between.
+/// Gives up once the pauses add up to `timeout`.
+fn wait_until_visible(
+    timeout: Duration,
+    mut exists: impl FnMut() -> bool,
+    mut sleep: impl FnMut(Duration),
+) -> bool {
+    let mut waited = Duration::ZERO;
+    let mut interval = VERIFY_INITIAL_INTERVAL;
+    loop {
+        if exists() {
+            return true;
+        }
+        if waited >= timeout {
+            return false;
+        }
+        let pause = interval.min(timeout - waited);
+        sleep(pause);
+        waited += pause;
+        interval = (interval * 2).min(VERIFY_MAX_INTERVAL);
+    }
+}
+
 /// Two-tier tag-conflict diagnostics, scoped to all non-ignored packages
 /// (private ones get tagged too):
 ///
@@ -386,10 +1144,20 @@ pub fn run_publish(
 /// Callers must surface the returned warnings — losing them silently defeats
 /// the point of running this check.
 #[must_use = "tag-conflict warnings must be surfaced to the user"]
-fn check_tag_conflicts(config: &Config, packages: &[&PackageInfo]) -> Result<Vec<String>> {
+fn check_tag_conflicts(
+    root: &Path,
+    config: &Config,
+    packages: &[&PackageInfo],
+) -> Result<Vec<String>> {
     let mut by_tag: BTreeMap<String, &PackageInfo> = BTreeMap::new();
     for package in packages {
-        let tag = config.build_tag_name(package.kind, &package.name, &package.version);
+        let tag = config.package_tag_name(root, package, &package.version);
+        validate_tag_name(&tag).map_err(|err| {
+            SampoError::Publish(format!(
+                "{} cannot be tagged: {err}",
+                package.canonical_identifier()
+            ))
+        })?;
         if let Some(existing) = by_tag.insert(tag.clone(), package) {
             return Err(SampoError::Publish(format!(
                 "tag conflict: '{}' and '{}' both render to git tag '{}'. {}",
@@ -423,7 +1191,7 @@ fn check_tag_conflicts(config: &Config, packages: &[&PackageInfo]) -> Result<Vec
         }
         let any_template_disambiguates = group.iter().any(|package| {
             config
-                .tag_template_for(&package.name)
+                .tag_template_for(package.kind, &package.name)
                 .contains(Placeholder::Ecosystem)
         });
         if any_template_disambiguates {
@@ -469,18 +1237,12 @@ fn tag_conflict_hint(config: &Config, group: &[&PackageInfo]) -> String {
     }
 }
 
-fn package_tag_exists(
-    repo_root: &Path,
-    config: &Config,
-    kind: PackageKind,
-    crate_name: &str,
-    version: &str,
-) -> Result<bool> {
+fn package_tag_exists(repo_root: &Path, config: &Config, package: &PackageInfo) -> Result<bool> {
     if !repo_root.join(".git").exists() {
         return Ok(false);
     }
 
-    let tag = config.build_tag_name(kind, crate_name, version);
+    let tag = config.package_tag_name(repo_root, package, &package.version);
     let out = Command::new("git")
         .arg("-C")
         .arg(repo_root)
@@ -498,35 +1260,47 @@ fn package_tag_exists(
     Ok(false)
 }
 
-/// Creates an annotated git tag for a published crate.
+/// Creates an annotated git tag for a published package, signed when `git.sign_tags`
+/// is set, with `git.tag_message` as its message.
 ///
 /// Skips tagging if not in a git repository or if the tag already exists.
 pub fn tag_published_crate(
     repo_root: &Path,
     config: &Config,
-    kind: PackageKind,
-    crate_name: &str,
-    version: &str,
+    package: &PackageInfo,
+) -> Result<bool> {
+    create_release_tag(repo_root, config, package, None)
+}
+
+/// Creates the release tag of `package` on `commit`, or on HEAD when `None`.
+fn create_release_tag(
+    repo_root: &Path,
+    config: &Config,
+    package: &PackageInfo,
+    commit: Option<&str>,
 ) -> Result<bool> {
     if !repo_root.join(".git").exists() {
         // Not a git repo, skip
         return Ok(false);
     }
-    if package_tag_exists(repo_root, config, kind, crate_name, version)? {
+    if package_tag_exists(repo_root, config, package)? {
         return Ok(false);
     }
-    let tag = config.build_tag_name(kind, crate_name, version);
+    let tag = config.package_tag_name(repo_root, package, &package.version);
 
-    let msg = format!("Release {} {}", crate_name, version);
-    let status = Command::new("git")
-        .arg("-C")
-        .arg(repo_root)
-        .arg("tag")
-        .arg("-a")
+    let msg = render_tag_message(config, package);
+    let mut cmd = git_with_signing(repo_root, config);
+    cmd.arg("tag")
+        .arg(if config.git_sign_tags.is_some() {
+            "-s"
+        } else {
+            "-a"
+        })
         .arg(&tag)
         .arg("-m")
         .arg(&msg)
-        .status()?;
+        .args(commit);
+    let status = cmd.status()?;
     if status.success() {
         Ok(true)
     } else {
@@ -537,6 +1311,147 @@ pub fn tag_published_crate(
     }
 }
 
+/// Moves the `git.floating_tags` of a package (e.g. `foo-v2` and `foo-v2.3`) onto its
+/// release tag, as lightweight tags. A floating tag already on a newer release stays
+/// there, so publishing a backport never moves it backwards. Returns the names of the
+/// moved tags; nothing moves in a dry run.
+fn move_floating_tags(
+    repo_root: &Path,
+    config: &Config,
+    package: &PackageInfo,
+    dry_run: bool,
+) -> Result<Vec<String>> {
+    if !dry_run && !repo_root.join(".git").exists() {
+        return Ok(Vec::new());
+    }
+    // A shorter version only renders a tag through `{version}`.
+    if !config
+        .tag_template_for(package.kind, &package.name)
+        .contains(Placeholder::Version)
+    {
+        return Ok(Vec::new());
+    }
+    let release_tag = config.package_tag_name(repo_root, package, &package.version);
+    let mut moved = Vec::new();
+    for floating in &config.for_package(package).git_floating_tags {
+        let Some(version) = floating.version(&package.version) else {
+            continue;
+        };
+        let tag = config.package_tag_name(repo_root, package, &version);
+        if let (Some(current), Ok(released)) = (
+            floating_tag_version(repo_root, config, package, &tag)?,
+            semver::Version::parse(&package.version),
+        ) && released < current
+        {
+            println!("Keeping floating tag {tag} on the newer {current} release.");
+            continue;
+        }
+        if !dry_run {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(repo_root)
+                .args(["tag", "-f", &tag, &format!("{release_tag}^{{commit}}")])
+                .stdout(std::process::Stdio::null())
+                .status()?;
+            if !status.success() {
+                eprintln!(
+                    "Warning: failed to move floating tag {} to {}: git tag failed with status {}",
+                    tag, release_tag, status
+                );
+                continue;
+            }
+        }
+        moved.push(tag);
+    }
+    Ok(moved)
+}
+
+/// The highest version of `package` released on the commit `tag` points at, or `None`
+/// when `tag` does not exist yet.
+fn floating_tag_version(
+    repo_root: &Path,
+    config: &Config,
+    package: &PackageInfo,
+    tag: &str,
+) -> Result<Option<semver::Version>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_root)
+        .args(["tag", "--points-at", &format!("refs/tags/{tag}")])
+        .stderr(std::process::Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (_, version) = config.find_tagged_package(
+                repo_root,
+                std::slice::from_ref(package),
+                line.trim(),
+            )?;
+            semver::Version::parse(&version).ok()
+        })
+        .max())
+}
+
+/// `git -C <root>`, with the `gpg.format` requested by `git.sign_tags`.
+fn git_with_signing(repo_root: &Path, config: &Config) -> Command {
+    let mut cmd = Command::new("git");
+    cmd.arg("-C").arg(repo_root);
+    if let Some(format) = config
+        .git_sign_tags
+        .and_then(|signing| signing.gpg_format())
+    {
+        cmd.arg("-c").arg(format!("gpg.format={format}"));
+    }
+    cmd
+}
+
+/// Renders `git.tag_message`; `{changelog}` is the package's section of its changelog.
+fn render_tag_message(config: &Config, package: &PackageInfo) -> String {
+    let template = &config.git_tag_message;
+    let mut message = template
+        .replace("{package_name}", &package.name)
+        .replace("{version}", &package.version)
+        .replace("{ecosystem}", package.kind.as_str());
+    if template.contains("{changelog}") {
+        let changelog = package
+            .path
+            .join(config.for_package(package).changelog_path);
+        let notes =
+            extract_changelog_section(&changelog, Some(&package.version)).unwrap_or_default();
+        message = message.replace("{changelog}", &notes);
+    }
+    message.trim().to_string()
+}
+
+/// Signs a throwaway commit object the way release tags will be signed, so a missing
+/// key or agent fails the publish before any package reaches a registry.
+fn check_tag_signing(repo_root: &Path, config: &Config) -> Result<()> {
+    if config.git_sign_tags.is_none() || !repo_root.join(".git").exists() {
+        return Ok(());
+    }
+    let out = git_with_signing(repo_root, config)
+        .args([
+            "commit-tree",
+            "-S",
+            "-m",
+            "sampo tag signing check",
+            "HEAD^{tree}",
+        ])
+        .output()?;
+    if out.status.success() {
+        Ok(())
+    } else {
+        Err(SampoError::Publish(format!(
+            "git.sign_tags is set but git cannot sign: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        )))
+    }
+}
+
 /// Computes topological ordering for publishing crates (dependencies first).
 ///
 /// Given a set of crates and their internal dependencies, returns the order
@@ -635,7 +1550,6 @@ mod tests {
             atomic::{AtomicBool, Ordering},
         },
         thread,
-        time::Duration,
     };
 
     /// Initialise a git repo with a first commit so HEAD exists.
@@ -668,6 +1582,109 @@ mod tests {
         }
     }
 
+    #[test]
+    fn with_retries_retries_transient_failures_only() {
+        let mut delays = Vec::new();
+        let result = with_retries(
+            3,
+            |attempt| {
+                if attempt < 2 {
+                    Err(SampoError::Publish("registry returned 503".into()).retryable())
+                } else {
+                    Ok(attempt)
+                }
+            },
+            |attempt, _, delay| delays.push((attempt, delay.as_secs())),
+        );
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(delays, vec![(1, 2), (2, 4)]);
+
+        let mut calls = 0;
+        let result: Result<()> = with_retries(
+            3,
+            |_| {
+                calls += 1;
+                Err(SampoError::Publish("invalid manifest".into()))
+            },
+            |_, _, _| {},
+        );
+        assert!(matches!(result, Err(SampoError::Publish(_))));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn with_retries_gives_up_with_the_underlying_error() {
+        let mut calls = 0;
+        let result: Result<()> = with_retries(
+            2,
+            |_| {
+                calls += 1;
+                Err(SampoError::Publish("registry returned 502".into()).retryable())
+            },
+            |_, _, _| {},
+        );
+        assert!(matches!(result, Err(SampoError::Publish(_))));
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn publish_retry_does_not_publish_a_version_twice() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("flaky", "1.0.0");
+        workspace.set_config("[publish]\nverify_timeout = 0\n");
+        init_git_repo_for_test(&workspace.root);
+        let fake_cargo = FakeCargo::install_flaky();
+
+        let output = workspace
+            .run_publish(false)
+            .expect("publish should recover from the transient failure");
+
+        let log = fs::read_to_string(fake_cargo.log_path()).unwrap();
+        let published = log
+            .lines()
+            .filter(|line| line.starts_with("publish ") && !line.contains("--dry-run"))
+            .count();
+        assert_eq!(published, 1, "log: {log}");
+        assert_eq!(output.tags, vec!["flaky-v1.0.0".to_string()]);
+        assert_eq!(output.retries.len(), 1);
+        assert_eq!(output.retries[0].operation, RetriedOperation::Publish);
+        assert_eq!(output.retries[0].package, "cargo/flaky");
+        assert!(output.retries[0].error.contains("cargo publish failed"));
+
+        let report = serde_json::to_value(&output).unwrap();
+        assert_eq!(report["retries"][0]["package"], "cargo/flaky");
+        assert_eq!(report["retries"][0]["operation"], "publish");
+        assert_eq!(report["retries"][0]["attempt"], 1);
+    }
+
+    #[test]
+    fn wait_until_visible_backs_off_until_the_version_shows_up() {
+        let mut lookups = 0;
+        let mut pauses = Vec::new();
+        let visible = wait_until_visible(
+            Duration::from_secs(300),
+            || {
+                lookups += 1;
+                lookups == 4
+            },
+            |pause| pauses.push(pause.as_secs()),
+        );
+        assert!(visible);
+        assert_eq!(pauses, vec![2, 4, 8]);
+    }
+
+    #[test]
+    fn wait_until_visible_gives_up_after_the_timeout() {
+        let mut pauses = Vec::new();
+        let visible = wait_until_visible(
+            Duration::from_secs(100),
+            || false,
+            |pause| pauses.push(pause.as_secs()),
+        );
+        assert!(!visible);
+        assert_eq!(pauses, vec![2, 4, 8, 16, 30, 30, 10]);
+    }
+
     #[test]
     fn check_tag_conflicts_errors_on_same_rendered_tag() {
         // Default template drops `{ecosystem}`, so two same-name same-version
@@ -676,7 +1693,7 @@ mod tests {
         let npm_pkg = make_package(PackageKind::Npm, "shared", "1.0.0");
         let packages = vec![&cargo_pkg, &npm_pkg];
         let config = Config::default();
-        let err = check_tag_conflicts(&config, &packages).unwrap_err();
+        let err = check_tag_conflicts(Path::new(""), &config, &packages).unwrap_err();
         let msg = format!("{err}");
         assert!(msg.contains("tag conflict"), "{msg}");
         assert!(msg.contains("cargo/shared"), "{msg}");
@@ -693,7 +1710,8 @@ mod tests {
         let npm_pkg = make_package(PackageKind::Npm, "shared", "2.3.4");
         let packages = vec![&cargo_pkg, &npm_pkg];
         let config = Config::default();
-        let warnings = check_tag_conflicts(&config, &packages).expect("no immediate collision");
+        let warnings =
+            check_tag_conflicts(Path::new(""), &config, &packages).expect("no immediate collision");
         assert_eq!(warnings.len(), 1, "{warnings:?}");
         let warning = &warnings[0];
         assert!(warning.contains("'cargo/shared'"), "{warning}");
@@ -714,7 +1732,7 @@ mod tests {
             .unwrap(),
             ..Config::default()
         };
-        let warnings = check_tag_conflicts(&config, &packages)
+        let warnings = check_tag_conflicts(Path::new(""), &config, &packages)
             .expect("ecosystem in template prevents collisions");
         assert!(warnings.is_empty(), "{warnings:?}");
     }
@@ -725,7 +1743,8 @@ mod tests {
         let npm_pkg = make_package(PackageKind::Npm, "beta", "1.0.0");
         let packages = vec![&cargo_pkg, &npm_pkg];
         let config = Config::default();
-        let warnings = check_tag_conflicts(&config, &packages).expect("no shared names");
+        let warnings =
+            check_tag_conflicts(Path::new(""), &config, &packages).expect("no shared names");
         assert!(warnings.is_empty(), "{warnings:?}");
     }
 
@@ -741,7 +1760,7 @@ mod tests {
             git_short_tags: Some("alpha".to_string()),
             ..Config::default()
         };
-        let err = check_tag_conflicts(&config, &packages).unwrap_err();
+        let err = check_tag_conflicts(Path::new(""), &config, &packages).unwrap_err();
         let msg = format!("{err}");
         assert!(msg.contains("short_tags_format"), "{msg}");
     }
@@ -753,7 +1772,8 @@ mod tests {
         let hex_pkg = make_package(PackageKind::Hex, "shared", "3.0.0");
         let packages = vec![&cargo_pkg, &npm_pkg, &hex_pkg];
         let config = Config::default();
-        let warnings = check_tag_conflicts(&config, &packages).expect("no immediate collision");
+        let warnings =
+            check_tag_conflicts(Path::new(""), &config, &packages).expect("no immediate collision");
         assert_eq!(warnings.len(), 1, "{warnings:?}");
         let warning = &warnings[0];
         assert!(
@@ -906,6 +1926,55 @@ fn main() {
     }
 
     let is_dry_run = args.iter().any(|arg| arg == "--dry-run");
+    let registry = args
+        .iter()
+        .position(|arg| arg == "--registry")
+        .and_then(|index| args.get(index + 1))
+        .cloned();
+
+    // With a `<log>.published` file, alternative registries only serve what was published
+    // to them; otherwise `cargo info` finds every version.
+    let published_path = format!("{}.published", log_path);
+    if args.first().map(String::as_str) == Some("info") {
+        if let Ok(published) = std::fs::read_to_string(&published_path) {
+            let registry = registry.unwrap_or_default();
+            if !published.lines().any(|line| line == registry) {
+                eprintln!("error: could not find `{}` in registry `{}`", args[1], registry);
+                process::exit(101);
+            }
+        }
+        return;
+    }
+
+    // `<log>.fail-registry` names a registry whose publishes fail.
+    if !is_dry_run && args.first().map(String::as_str) == Some("publish") {
+        if let Some(registry) = &registry {
+            let failing = std::fs::read_to_string(format!("{}.fail-registry", log_path))
+                .unwrap_or_default();
+            if failing.trim() == registry {
+                eprintln!("error: failed to publish to registry `{}`", registry);
+                process::exit(101);
+            }
+            if let Ok(mut published) = OpenOptions::new().append(true).open(&published_path) {
+                writeln!(published, "{}", registry).expect("failed to record the publish");
+            }
+        }
+    }
+
+    // "transient": the first real publish goes through, then reports a registry outage.
+    if !is_dry_run && matches!(env::var("SAMPO_FAKE_CARGO_FAIL_ACTUAL"), Ok(val) if val == "transient") {
+        let log = std::fs::read_to_string(&log_path).unwrap_or_default();
+        let publishes = log
+            .lines()
+            .filter(|line| line.starts_with("publish ") && !line.contains("--dry-run"))
+            .count();
+        if publishes == 1 {
+            eprintln!("error: the remote server responded with an error (status 503 Service Unavailable)");
+            process::exit(101);
+        }
+        return;
+    }
+
     let should_fail = if is_dry_run {
         matches!(env::var("SAMPO_FAKE_CARGO_FAIL_DRY_RUN"), Ok(val) if val == "1")
     } else {
@@ -918,8 +1987,19 @@ fn main() {
 }
 "#;
 
-    /// Local stand-in for the crates.io existence check: answers every request with a
-    /// fixed status, keeping publish tests off the rate-limited real API.
+    /// What the local crates.io stand-in reports for a version.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum FakeRegistry {
+        /// Missing until the stub cargo publishes it, like crates.io.
+        Indexing,
+        /// Every version already exists.
+        Published,
+        /// Versions never show up, as when crates.io lags behind.
+        Stalled,
+    }
+
+    /// Local stand-in for the crates.io existence check, keeping publish tests off the
+    /// rate-limited real API.
     struct FakeCratesIo {
         base_url: String,
         addr: std::net::SocketAddr,
@@ -928,7 +2008,7 @@ fn main() {
     }
 
     impl FakeCratesIo {
-        fn serve(status_line: &'static str) -> Self {
+        fn serve(registry: FakeRegistry, publish_log: PathBuf) -> Self {
             let listener = TcpListener::bind("127.0.0.1:0").unwrap();
             let addr = listener.local_addr().unwrap();
             let stop = Arc::new(AtomicBool::new(false));
@@ -951,6 +2031,15 @@ fn main() {
                             Ok(n) => request.extend_from_slice(&buffer[..n]),
                         }
                     }
+                    let status_line = match registry {
+                        FakeRegistry::Published => "200 OK",
+                        FakeRegistry::Indexing
+                            if was_published(&publish_log, &String::from_utf8_lossy(&request)) =>
+                        {
+                            "200 OK"
+                        }
+                        FakeRegistry::Indexing | FakeRegistry::Stalled => "404 Not Found",
+                    };
                     let response = format!(
                         "HTTP/1.1 {status_line}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
                     );
@@ -966,6 +2055,28 @@ fn main() {
         }
     }
 
+    /// Whether the stub cargo logged a real publish of the crate in `request`
+    /// (`GET /crates/<name>/<version>`).
+    fn was_published(publish_log: &Path, request: &str) -> bool {
+        let Some(name) = request
+            .split_whitespace()
+            .nth(1)
+            .and_then(|path| path.strip_prefix("/crates/"))
+            .and_then(|rest| rest.split('/').next())
+        else {
+            return false;
+        };
+        let manifest = format!("/{name}/Cargo.toml");
+        fs::read_to_string(publish_log).is_ok_and(|log| {
+            log.lines().any(|line| {
+                line.starts_with("publish ")
+                    && !line.contains("--dry-run")
+                    && line.contains(&manifest)
+                    && (!line.contains("--registry ") || line.contains("--registry crates-io"))
+            })
+        })
+    }
+
     impl Drop for FakeCratesIo {
         fn drop(&mut self) {
             self.stop.store(true, Ordering::SeqCst);
@@ -988,16 +2099,32 @@ fn main() {
 
     impl FakeCargo {
         /// Installs the stub cargo binary plus a local crates.io stand-in reporting
-        /// every version as unpublished (the common case).
+        /// versions as unpublished until the stub publishes them (the common case).
         fn install(fail_dry_run: bool, fail_actual: bool, version: &str) -> Self {
-            Self::install_with_registry(fail_dry_run, fail_actual, version, false)
+            Self::install_with_registry(fail_dry_run, fail_actual, version, FakeRegistry::Indexing)
+        }
+
+        /// Installs a stub cargo whose first real publish uploads the crate, then fails
+        /// with a registry 503, as when crates.io times out after taking the upload.
+        fn install_flaky() -> Self {
+            Self::install_with(false, "transient", "1.91.0", FakeRegistry::Indexing)
         }
 
         fn install_with_registry(
             fail_dry_run: bool,
             fail_actual: bool,
             version: &str,
-            already_published: bool,
+            registry: FakeRegistry,
+        ) -> Self {
+            let fail_actual = if fail_actual { "1" } else { "0" };
+            Self::install_with(fail_dry_run, fail_actual, version, registry)
+        }
+
+        fn install_with(
+            fail_dry_run: bool,
+            fail_actual: &str,
+            version: &str,
+            registry: FakeRegistry,
         ) -> Self {
             let temp_dir = tempfile::tempdir().unwrap();
             let bin_dir = temp_dir.path().join("bin");
@@ -1037,11 +2164,7 @@ fn main() {
                 path_override.push(&existing);
             }
 
-            let crates_io = FakeCratesIo::serve(if already_published {
-                "200 OK"
-            } else {
-                "404 Not Found"
-            });
+            let crates_io = FakeCratesIo::serve(registry, log_path.clone());
             let crates_io_base =
                 crate::adapters::cargo::override_crates_io_api_base_for_tests(&crates_io.base_url);
 
@@ -1052,10 +2175,7 @@ fn main() {
                     "SAMPO_FAKE_CARGO_FAIL_DRY_RUN",
                     OsString::from(if fail_dry_run { "1" } else { "0" }),
                 ),
-                (
-                    "SAMPO_FAKE_CARGO_FAIL_ACTUAL",
-                    OsString::from(if fail_actual { "1" } else { "0" }),
-                ),
+                ("SAMPO_FAKE_CARGO_FAIL_ACTUAL", OsString::from(fail_actual)),
                 ("SAMPO_FAKE_CARGO_VERSION", OsString::from(version)),
             ];
 
@@ -1073,6 +2193,22 @@ fn main() {
         fn log_path(&self) -> &std::path::Path {
             &self.log_path
         }
+
+        /// Makes alternative registries serve only the versions published to them.
+        fn track_registries(&self) {
+            fs::write(self.log_path.with_extension("log.published"), "").unwrap();
+        }
+
+        /// Makes publishes to `registry` fail, or lifts the failure.
+        fn fail_registry(&self, registry: Option<&str>) {
+            let path = self.log_path.with_extension("log.fail-registry");
+            match registry {
+                Some(registry) => fs::write(path, registry).unwrap(),
+                None => {
+                    let _ = fs::remove_file(path);
+                }
+            }
+        }
     }
 
     impl TestWorkspace {
@@ -1165,6 +2301,20 @@ fn main() {
             super::run_publish(&self.root, dry_run, &super::PublishExtraArgs::default())
         }
 
+        fn run_publish_with_options(
+            &self,
+            dry_run: bool,
+            options: &super::PublishOptions,
+        ) -> Result<PublishOutput> {
+            let _branch_guard = override_current_branch_for_tests(&self.branch);
+            super::run_publish_with_options(
+                &self.root,
+                dry_run,
+                &super::PublishExtraArgs::default(),
+                options,
+            )
+        }
+
         fn run_publish_with_args(
             &self,
             dry_run: bool,
@@ -1889,6 +3039,173 @@ edition = "2021"
         );
     }
 
+    #[test]
+    fn tag_message_embeds_changelog_section() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("private-one", "1.1.0")
+            .set_publishable("private-one", false);
+        workspace
+            .set_config("[git]\ntag_message = \"{package_name} {version}\\n\\n{changelog}\"\n");
+        fs::write(
+            workspace.crates["private-one"].join("CHANGELOG.md"),
+            "# private-one\n\n## 1.1.0\n\n- Faster startup\n\n## 1.0.0\n\n- First release\n",
+        )
+        .unwrap();
+        init_git_repo_for_test(&workspace.root);
+
+        let _fake_cargo = FakeCargo::install(false, false, "1.91.0");
+        workspace
+            .run_publish(false)
+            .expect("publish should succeed");
+
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&workspace.root)
+            .args(["tag", "-l", "--format=%(contents)", "private-one-v1.1.0"])
+            .output()
+            .expect("git tag list should succeed");
+        let message = String::from_utf8_lossy(&output.stdout);
+        assert_eq!(message.trim(), "private-one 1.1.0\n\n- Faster startup");
+    }
+
+    #[test]
+    fn tag_signing_failure_aborts_publish() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("private-one", "1.0.0")
+            .set_publishable("private-one", false);
+        workspace.set_config("[git]\nsign_tags = \"ssh\"\n");
+        init_git_repo_for_test(&workspace.root);
+        let missing_key = workspace.root.join("missing-key");
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(&workspace.root)
+            .args(["config", "user.signingkey"])
+            .arg(&missing_key)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let _fake_cargo = FakeCargo::install(false, false, "1.91.0");
+        let err = workspace.run_publish(false).unwrap_err();
+        assert!(err.to_string().contains("cannot sign"), "{err}");
+
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&workspace.root)
+            .args(["tag", "--list"])
+            .output()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&output.stdout).trim().is_empty());
+    }
+
+    #[test]
+    fn floating_tags_follow_the_latest_release() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("action", "2.3.1")
+            .add_crate("preview", "3.0.0-rc.1")
+            .set_publishable("action", false)
+            .set_publishable("preview", false);
+        workspace.set_config("[git]\nfloating_tags = [\"major\", \"minor\"]\n");
+        init_git_repo_for_test(&workspace.root);
+        let _fake_cargo = FakeCargo::install(false, false, "1.91.0");
+
+        let output = workspace
+            .run_publish(false)
+            .expect("publish should succeed");
+        assert_eq!(output.floating_tags, vec!["action-v2", "action-v2.3"]);
+        assert!(output.tags.contains(&"action-v2".to_string()));
+        assert!(output.tags.contains(&"preview-v3.0.0-rc.1".to_string()));
+
+        let rev = |tag: &str| {
+            let out = Command::new("git")
+                .arg("-C")
+                .arg(&workspace.root)
+                .args(["rev-parse", &format!("{tag}^{{commit}}")])
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&out.stdout).trim().to_string()
+        };
+        assert_eq!(rev("action-v2"), rev("action-v2.3.1"));
+
+        fs::write(
+            workspace.crates["action"].join("Cargo.toml"),
+            "[package]\nname=\"action\"\nversion=\"2.3.2\"\npublish = false\n",
+        )
+        .unwrap();
+        for args in [
+            vec!["add", "-A"],
+            vec!["commit", "-q", "-m", "Release 2.3.2"],
+        ] {
+            let status = Command::new("git")
+                .args(&args)
+                .current_dir(&workspace.root)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+
+        let output = workspace
+            .run_publish(false)
+            .expect("publish should succeed");
+        assert_eq!(output.floating_tags, vec!["action-v2", "action-v2.3"]);
+        assert_eq!(rev("action-v2"), rev("action-v2.3.2"));
+        assert_eq!(rev("action-v2.3"), rev("action-v2.3.2"));
+        assert_ne!(rev("action-v2"), rev("action-v2.3.1"));
+    }
+
+    #[test]
+    fn floating_tags_never_move_back_to_a_backport() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("action", "1.3.0")
+            .set_publishable("action", false);
+        workspace.set_config("[git]\nfloating_tags = [\"major\", \"minor\"]\n");
+        init_git_repo_for_test(&workspace.root);
+        let _fake_cargo = FakeCargo::install(false, false, "1.91.0");
+
+        workspace
+            .run_publish(false)
+            .expect("publish should succeed");
+
+        fs::write(
+            workspace.crates["action"].join("Cargo.toml"),
+            "[package]\nname=\"action\"\nversion=\"1.2.5\"\npublish = false\n",
+        )
+        .unwrap();
+        for args in [
+            vec!["add", "-A"],
+            vec!["commit", "-q", "-m", "Backport 1.2.5"],
+        ] {
+            let status = Command::new("git")
+                .args(&args)
+                .current_dir(&workspace.root)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+
+        let output = workspace
+            .run_publish(false)
+            .expect("publish should succeed");
+        assert_eq!(output.floating_tags, vec!["action-v1.2"]);
+
+        let rev = |tag: &str| {
+            let out = Command::new("git")
+                .arg("-C")
+                .arg(&workspace.root)
+                .args(["rev-parse", &format!("{tag}^{{commit}}")])
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&out.stdout).trim().to_string()
+        };
+        assert_eq!(rev("action-v1"), rev("action-v1.3.0"));
+        assert_eq!(rev("action-v1.3"), rev("action-v1.3.0"));
+        assert_eq!(rev("action-v1.2"), rev("action-v1.2.5"));
+    }
+
     #[test]
     fn private_only_workspace_creates_tags() {
         let mut workspace = TestWorkspace::new();
@@ -1989,6 +3306,379 @@ edition = "2021"
         );
     }
 
+    #[test]
+    fn publish_stops_when_the_registry_never_serves_a_version() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("base", "1.0.0")
+            .add_crate("app", "1.0.0")
+            .add_dependency("app", "base", "1.0.0");
+        workspace.set_config("[publish]\nverify_timeout = 1\n");
+        init_git_repo_for_test(&workspace.root);
+        let fake_cargo =
+            FakeCargo::install_with_registry(false, false, "1.91.0", FakeRegistry::Stalled);
+
+        let err = workspace.run_publish(false).unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "base@1.0.0 was published, but the Cargo registry still did not serve it"
+            ),
+            "unexpected error: {err}"
+        );
+        // The tag of the published package is reported with the failure, to be pushed.
+        let SampoError::PublishIncomplete { output, .. } = err else {
+            panic!("expected a partial publish, got {err:?}");
+        };
+        assert_eq!(output.tags, vec!["base-v1.0.0".to_string()]);
+
+        // The dependent is left for the next run, and the published package is tagged.
+        let log = fs::read_to_string(fake_cargo.log_path()).unwrap();
+        let published: Vec<&str> = log
+            .lines()
+            .filter(|line| line.starts_with("publish ") && !line.contains("--dry-run"))
+            .collect();
+        assert_eq!(published.len(), 1, "log: {log}");
+        assert!(published[0].contains("/base/Cargo.toml"));
+
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&workspace.root)
+            .args(["tag", "--list"])
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            "base-v1.0.0"
+        );
+    }
+
+    fn create_tag(root: &Path, tag: &str) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["tag", tag])
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn offline_check_trusts_release_tags_over_the_registry() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("tagged", "1.0.0")
+            .add_crate("fresh", "1.0.0");
+        workspace.set_config("[publish]\nverify_timeout = 1\n");
+        init_git_repo_for_test(&workspace.root);
+        create_tag(&workspace.root, "tagged-v1.0.0");
+        // The registry never serves anything: only the tags can tell what was published.
+        let fake_cargo =
+            FakeCargo::install_with_registry(false, false, "1.91.0", FakeRegistry::Stalled);
+
+        let output = workspace
+            .run_publish_with_options(
+                false,
+                &PublishOptions {
+                    existence_check: Some(ExistenceCheck::Tags),
+                    ..Default::default()
+                },
+            )
+            .expect("publish should not wait on the registry");
+
+        let log = fs::read_to_string(fake_cargo.log_path()).unwrap();
+        let published: Vec<&str> = log
+            .lines()
+            .filter(|line| line.starts_with("publish ") && !line.contains("--dry-run"))
+            .collect();
+        assert_eq!(published.len(), 1, "log: {log}");
+        assert!(published[0].contains("/fresh/Cargo.toml"));
+        assert_eq!(output.tags, vec!["fresh-v1.0.0".to_string()]);
+    }
+
+    #[test]
+    fn provenance_statements_are_written_for_published_packages() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("fresh", "1.0.0");
+        workspace.set_config("[publish]\nprovenance = true\nverify_timeout = 1\n");
+        init_git_repo_for_test(&workspace.root);
+        let _fake_cargo =
+            FakeCargo::install_with_registry(false, false, "1.91.0", FakeRegistry::Indexing);
+
+        let output = workspace
+            .run_publish_with_options(true, &PublishOptions::default())
+            .expect("dry-run publish should succeed");
+        assert!(output.provenance.is_empty());
+
+        let output = workspace
+            .run_publish_with_options(false, &PublishOptions::default())
+            .expect("publish should succeed");
+        let statement_path = &output.provenance["fresh-v1.0.0"];
+        assert_eq!(
+            statement_path,
+            &workspace
+                .root
+                .join(".sampo/provenance/fresh-v1.0.0.intoto.json")
+        );
+
+        // The fake cargo packages nothing, so the release commit identifies the package.
+        let statement: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(statement_path).unwrap()).unwrap();
+        let head = crate::git::head_commit(&workspace.root).unwrap();
+        assert_eq!(statement["subject"][0]["name"], "pkg:cargo/fresh@1.0.0");
+        assert_eq!(
+            statement["subject"][0]["digest"]["gitCommit"],
+            head.as_str()
+        );
+    }
+
+    #[test]
+    fn packages_are_published_to_each_of_their_registries() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "1.0.0");
+        workspace.set_config(
+            "[publish]\nverify_timeout = 1\n\n[packages.\"cargo/foo\"]\nregistries = [\"crates-io\", \"internal\"]\n",
+        );
+        init_git_repo_for_test(&workspace.root);
+        // The stub `cargo info` reports the version on `internal` already.
+        let fake_cargo =
+            FakeCargo::install_with_registry(false, false, "1.91.0", FakeRegistry::Indexing);
+
+        let output = workspace
+            .run_publish_with_options(true, &PublishOptions::default())
+            .expect("dry run should succeed");
+        assert_eq!(
+            output.registries[1],
+            RegistryPublish {
+                package: "cargo/foo".to_string(),
+                registry: "crates-io".to_string(),
+                status: RegistryStatus::DryRun,
+            }
+        );
+
+        let output = workspace
+            .run_publish_with_options(false, &PublishOptions::default())
+            .expect("publish should succeed");
+        assert_eq!(output.tags, vec!["foo-v1.0.0"]);
+        assert_eq!(
+            output.registries,
+            vec![
+                RegistryPublish {
+                    package: "cargo/foo".to_string(),
+                    registry: "internal".to_string(),
+                    status: RegistryStatus::AlreadyPublished,
+                },
+                RegistryPublish {
+                    package: "cargo/foo".to_string(),
+                    registry: "crates-io".to_string(),
+                    status: RegistryStatus::Published,
+                },
+            ]
+        );
+
+        let log = fs::read_to_string(fake_cargo.log_path()).unwrap();
+        assert!(
+            log.lines()
+                .any(|line| line.starts_with("info foo@1.0.0 --registry internal"))
+        );
+        let publishes: Vec<&str> = log
+            .lines()
+            .filter(|line| line.starts_with("publish ") && !line.contains("--dry-run"))
+            .collect();
+        assert_eq!(publishes.len(), 1);
+        assert!(publishes[0].ends_with("--registry crates-io"));
+    }
+
+    #[test]
+    fn package_is_not_tagged_until_every_registry_accepts_it() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "1.0.0");
+        workspace.set_config(
+            "[publish]\nverify_timeout = 1\nretries = 0\n\n[packages.\"cargo/foo\"]\nregistries = [\"crates-io\", \"internal\"]\n",
+        );
+        init_git_repo_for_test(&workspace.root);
+        let fake_cargo =
+            FakeCargo::install_with_registry(false, false, "1.91.0", FakeRegistry::Indexing);
+        fake_cargo.track_registries();
+        fake_cargo.fail_registry(Some("internal"));
+
+        let err = workspace
+            .run_publish_with_options(false, &PublishOptions::default())
+            .expect_err("a failing registry fails the publish");
+        let message = err.to_string();
+        assert!(
+            message.contains("foo@1.0.0 was not published to every registry"),
+            "{message}"
+        );
+        assert!(message.contains("Failed on internal"), "{message}");
+        assert!(
+            message.contains("Succeeded on crates-io (published)"),
+            "{message}"
+        );
+        let SampoError::PublishIncomplete { output, .. } = err else {
+            panic!("the failure should carry the registry statuses: {message}");
+        };
+        assert_eq!(output.registries.len(), 2);
+        assert_eq!(output.registries[0].registry, "crates-io");
+        assert_eq!(output.registries[0].status, RegistryStatus::Published);
+        assert_eq!(output.registries[1].registry, "internal");
+        assert!(
+            matches!(&output.registries[1].status, RegistryStatus::Failed { error } if !error.is_empty()),
+            "{:?}",
+            output.registries
+        );
+        assert!(output.tags.is_empty());
+        let tags = Command::new("git")
+            .arg("-C")
+            .arg(&workspace.root)
+            .args(["tag", "--list"])
+            .output()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&tags.stdout).trim().is_empty());
+
+        fake_cargo.fail_registry(None);
+        let output = workspace
+            .run_publish_with_options(false, &PublishOptions::default())
+            .expect("the re-run publishes to the remaining registry");
+        assert_eq!(output.tags, vec!["foo-v1.0.0"]);
+        assert_eq!(
+            output.registries,
+            vec![
+                RegistryPublish {
+                    package: "cargo/foo".to_string(),
+                    registry: "crates-io".to_string(),
+                    status: RegistryStatus::AlreadyPublished,
+                },
+                RegistryPublish {
+                    package: "cargo/foo".to_string(),
+                    registry: "internal".to_string(),
+                    status: RegistryStatus::Published,
+                },
+            ]
+        );
+
+        let log = fs::read_to_string(fake_cargo.log_path()).unwrap();
+        let publishes: Vec<&str> = log
+            .lines()
+            .filter(|line| line.starts_with("publish ") && !line.contains("--dry-run"))
+            .collect();
+        assert_eq!(publishes.len(), 3, "log: {log}");
+        assert!(publishes[0].contains("--registry crates-io"), "log: {log}");
+        assert!(publishes[1].contains("--registry internal"), "log: {log}");
+        assert!(publishes[2].contains("--registry internal"), "log: {log}");
+    }
+
+    #[test]
+    fn repair_tags_tags_published_versions_without_publishing() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("tagged", "1.0.0")
+            .add_crate("untagged", "2.1.0");
+        workspace.set_config("[git]\nfloating_tags = [\"major\"]\n");
+        fs::write(
+            workspace.crates["untagged"].join("CHANGELOG.md"),
+            "# untagged\n\n## 2.1.0 — 2026-10-01\n\n- Released\n",
+        )
+        .unwrap();
+        init_git_repo_for_test(&workspace.root);
+        create_tag(&workspace.root, "tagged-v1.0.0");
+        let release_commit = rev_parse(&workspace.root, "HEAD");
+        fs::write(workspace.root.join("README.md"), "Later work\n").unwrap();
+        commit_all(&workspace.root, "Later work");
+        let fake_cargo =
+            FakeCargo::install_with_registry(false, false, "1.91.0", FakeRegistry::Published);
+        let options = PublishOptions {
+            repair_tags: true,
+            ..Default::default()
+        };
+
+        let output = workspace
+            .run_publish_with_options(true, &options)
+            .expect("dry-run repair should succeed");
+        assert_eq!(output.tags, vec!["untagged-v2.1.0", "untagged-v2"]);
+
+        let output = workspace
+            .run_publish_with_options(false, &options)
+            .expect("repair should succeed");
+        assert_eq!(output.tags, vec!["untagged-v2.1.0", "untagged-v2"]);
+        assert_eq!(output.floating_tags, vec!["untagged-v2"]);
+
+        let log = fs::read_to_string(fake_cargo.log_path()).unwrap_or_default();
+        assert!(!log.lines().any(|line| line.starts_with("publish ")));
+        let tags = Command::new("git")
+            .arg("-C")
+            .arg(&workspace.root)
+            .args(["tag", "--list"])
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&tags.stdout)
+                .lines()
+                .collect::<Vec<_>>(),
+            vec!["tagged-v1.0.0", "untagged-v2", "untagged-v2.1.0"]
+        );
+        for tag in ["untagged-v2.1.0", "untagged-v2"] {
+            assert_eq!(
+                rev_parse(&workspace.root, &format!("{tag}^{{commit}}")),
+                release_commit,
+                "{tag} should point at the release commit, not HEAD"
+            );
+        }
+    }
+
+    #[test]
+    fn repair_tags_refuses_versions_without_a_release_commit() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("untagged", "2.1.0");
+        init_git_repo_for_test(&workspace.root);
+        let _fake_cargo =
+            FakeCargo::install_with_registry(false, false, "1.91.0", FakeRegistry::Published);
+
+        let err = workspace
+            .run_publish_with_options(
+                false,
+                &PublishOptions {
+                    repair_tags: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Cannot find the commit that released untagged@2.1.0"),
+            "{err}"
+        );
+        let tags = Command::new("git")
+            .arg("-C")
+            .arg(&workspace.root)
+            .args(["tag", "--list"])
+            .output()
+            .unwrap();
+        assert!(tags.stdout.is_empty());
+    }
+
+    fn rev_parse(root: &Path, rev: &str) -> String {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["rev-parse", rev])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn commit_all(root: &Path, message: &str) {
+        for args in [vec!["add", "-A"], vec!["commit", "-m", message]] {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(root)
+                .args(&args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        }
+    }
+
     #[test]
     fn private_versionless_npm_member_is_not_tagged() {
         // A versionless package would render a malformed tag like "npm-internal-v";
@@ -2341,8 +4031,8 @@ edition = "2021"
         .unwrap();
         fs::write(crate_dir.join("src/lib.rs"), "// test").unwrap();
 
-        // `true`: the local registry reports the version as already published.
-        let fake_cargo = FakeCargo::install_with_registry(false, false, "1.91.0", true);
+        let fake_cargo =
+            FakeCargo::install_with_registry(false, false, "1.91.0", FakeRegistry::Published);
 
         // Run publish (not dry-run)
         let _branch_guard = override_current_branch_for_tests("main");
@@ -2,19 +2,24 @@ use crate::adapters::PackageAdapter;
 use crate::adapters::cargo::ManifestMetadata;
 use crate::errors::{Result, SampoError, io_error_with_path};
 use crate::filters::should_ignore_package;
+use crate::hooks::{Hook, HookPackage, Hooks, run_hook};
 use crate::types::{
     Bump, ChangelogCategory, ConstraintCheckResult, ConstraintViolation, DependencyUpdate,
     PackageInfo, PackageKind, PackageSpecifier, ReleaseOutput, ReleasedPackage, SpecResolution,
     Workspace, format_ambiguity_options,
 };
+use crate::version_files::{
+    VersionFileUpdate, plan_version_file_updates, print_version_file_diff, write_version_files,
+};
 use crate::{
     AcknowledgmentStyle,
-    changeset::{ChangesetInfo, parse_changeset, render_changeset_markdown_with_tags},
-    config::Config,
+    calver::CalverFormat,
+    changeset::{ChangesetInfo, parse_changeset, render_changeset_subset},
+    config::{CascadePolicy, Config, VersioningScheme, ZeroMajorPolicy},
     current_branch, detect_github_repo_slug_with_config, discover_workspace,
     enrich_changeset_message, get_commit_hash_for_path, load_changesets,
 };
-use chrono::{DateTime, FixedOffset, Local, Utc};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, Utc};
 use chrono_tz::Tz;
 use semver::{BuildMetadata, Prerelease, Version};
 use std::collections::{BTreeMap, BTreeSet};
@@ -191,6 +196,79 @@ fn resolve_config_groups(
     Ok(resolved)
 }
 
+/// The released packages, as `[hooks]` commands receive them.
+fn release_hook_packages(workspace: &Workspace, releases: &ReleasePlan) -> Vec<HookPackage> {
+    releases
+        .iter()
+        .filter_map(|(identifier, old, new)| {
+            let info = workspace.find_by_identifier(identifier)?;
+            Some(HookPackage::new(&workspace.root, info, Some(old), new))
+        })
+        .collect()
+}
+
+/// Rewrites of the `[[packages.version_files]]` entries whose package is released,
+/// computed from the files as they are now. Manifests and changelogs are left to the
+/// release itself.
+fn version_file_updates(
+    workspace: &Workspace,
+    config: &Config,
+    releases: &ReleasePlan,
+) -> Result<Vec<VersionFileUpdate>> {
+    let mut entries = Vec::new();
+    for entry in &config.version_files {
+        let identifier = resolve_config_value(workspace, &entry.package, "packages.version_files")?;
+        if let Some((_, old, new)) = releases.iter().find(|(name, _, _)| *name == identifier) {
+            entries.push((entry, old.as_str(), new.as_str()));
+        }
+    }
+    if entries.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut managed = BTreeSet::new();
+    for info in &workspace.members {
+        let adapter = PackageAdapter::from_kind(info.kind);
+        managed.insert(adapter.manifest_path(&info.path));
+        managed.insert(adapter.manifest_path(&workspace.root));
+        managed.insert(info.path.join(&config.for_package(info).changelog_path));
+    }
+    if let Some(aggregate) = &config.changelog_aggregate {
+        managed.insert(workspace.root.join(aggregate));
+    }
+    plan_version_file_updates(&workspace.root, &entries, &managed)
+}
+
+/// The `zero_major` policy of each member, with its `[packages."<package>"]` override.
+fn zero_major_policies(
+    workspace: &Workspace,
+    config: &Config,
+) -> BTreeMap<String, ZeroMajorPolicy> {
+    workspace
+        .members
+        .iter()
+        .map(|info| {
+            let policy = config.for_package(info).versioning_zero_major;
+            (info.canonical_identifier().to_string(), policy)
+        })
+        .collect()
+}
+
+/// The versioning scheme of each member, with its `[packages."<package>"] versioning`.
+fn versioning_schemes(
+    workspace: &Workspace,
+    config: &Config,
+) -> BTreeMap<String, VersioningScheme> {
+    workspace
+        .members
+        .iter()
+        .map(|info| {
+            let scheme = config.for_package(info).versioning_scheme;
+            (info.canonical_identifier().to_string(), scheme)
+        })
+        .collect()
+}
+
 /// The fixed groups Sampo enforces: the user's `packages.fixed` config plus any implicit
 /// groups an ecosystem derives from its structure (Maven parent-inherited versions).
 /// Overlapping groups are merged so no package lands in two.
@@ -414,7 +492,10 @@ pub fn detect_fixed_dependency_policy_packages(
     // Build set of packages with direct changesets
     let mut packages_with_changesets: BTreeSet<String> = BTreeSet::new();
     for cs in changesets {
-        for (spec, _, _) in &cs.entries {
+        for (spec, bump, _) in &cs.entries {
+            if *bump == Bump::None {
+                continue;
+            }
             let info = resolve_package_spec(workspace, spec)?;
             packages_with_changesets.insert(info.canonical_identifier().to_string());
         }
@@ -430,10 +511,13 @@ pub fn detect_fixed_dependency_policy_packages(
             continue;
         }
 
+        let dev_deps = config
+            .cascade_dev_dependencies
+            .then_some(&crate_info.internal_dev_deps);
         for dep_name in crate_info
             .internal_deps
             .iter()
-            .chain(&crate_info.internal_dev_deps)
+            .chain(dev_deps.into_iter().flatten())
         {
             dependents
                 .entry(dep_name.clone())
@@ -530,6 +614,7 @@ type InitialBumpsResult = (
     BTreeMap<String, Bump>,                             // bump_by_pkg
     BTreeMap<String, Vec<(String, ChangelogCategory)>>, // messages_by_pkg
     BTreeSet<std::path::PathBuf>,                       // used_paths
+    BTreeMap<String, String>,                           // target_by_pkg
 );
 
 /// Type alias for release plan
@@ -539,6 +624,9 @@ type ReleasePlan = Vec<(String, String, String)>; // (name, old_version, new_ver
 struct PlanState {
     messages_by_pkg: BTreeMap<String, Vec<(String, ChangelogCategory)>>,
     used_paths: BTreeSet<PathBuf>,
+    /// `none` entries of consumed changesets whose package is not released, written back
+    /// once the consumed files are gone.
+    pending_notes: Vec<ChangesetInfo>,
     releases: ReleasePlan,
     released_packages: Vec<ReleasedPackage>,
 }
@@ -550,28 +638,194 @@ enum PlanOutcome {
     Plan(PlanState),
 }
 
-/// Main release function that can be called from CLI or other interfaces
-pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutput> {
-    let workspace = discover_workspace(root)?;
-    let config = Config::load(&workspace.root)?;
+/// Restricts a release to the changesets of a subset of packages.
+///
+/// A changeset is consumed only when every applicable entry targets a selected package;
+/// the others stay pending, unless `split_mixed` is set. The dependency cascade and
+/// fixed/linked groups still apply, so the plan may include packages outside the selection.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReleaseSelection {
+    /// Canonical identifiers (`<kind>/<name>`) of the selected packages.
+    /// An empty set selects every package not listed in `excluded`.
+    pub packages: BTreeSet<String>,
+    /// Canonical identifiers of packages whose changesets stay pending.
+    pub excluded: BTreeSet<String>,
+    /// Split changesets that target both selected and unselected packages: the selected
+    /// entries are released, and the others stay pending in the same changeset file.
+    pub split_mixed: bool,
+}
 
-    let branch = current_branch()?;
-    if !config.is_release_branch(&branch) {
+impl ReleaseSelection {
+    /// Select the given canonical package identifiers.
+    pub fn packages<I, S>(identifiers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            packages: identifiers.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Whether every package is selected.
+    pub fn is_unrestricted(&self) -> bool {
+        self.packages.is_empty() && self.excluded.is_empty()
+    }
+
+    /// Whether the package with the given canonical identifier is selected.
+    pub fn includes(&self, identifier: &str) -> bool {
+        (self.packages.is_empty() || self.packages.contains(identifier))
+            && !self.excluded.contains(identifier)
+    }
+
+    /// Whether every applicable entry of `changeset` targets a selected package.
+    pub fn covers_changeset(
+        &self,
+        workspace: &Workspace,
+        config: &Config,
+        changeset: &ChangesetInfo,
+    ) -> Result<bool> {
+        if self.is_unrestricted() {
+            return Ok(true);
+        }
+        for (spec, _, _) in &changeset.entries {
+            let info = resolve_package_spec(workspace, spec)?;
+            if should_ignore_package(config, workspace, info)? {
+                continue;
+            }
+            if !self.includes(info.canonical_identifier()) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Keep the changesets released by this selection, narrowing split ones to their
+    /// selected entries.
+    fn retain_covered(
+        &self,
+        workspace: &Workspace,
+        config: &Config,
+        changesets: Vec<ChangesetInfo>,
+    ) -> Result<Vec<ChangesetInfo>> {
+        if self.is_unrestricted() {
+            return Ok(changesets);
+        }
+        let mut covered = Vec::with_capacity(changesets.len());
+        for mut changeset in changesets {
+            let mut kept = Vec::with_capacity(changeset.entries.len());
+            let mut any_selected = false;
+            for entry in &changeset.entries {
+                let info = resolve_package_spec(workspace, &entry.0)?;
+                if should_ignore_package(config, workspace, info)? {
+                    kept.push(entry.clone());
+                } else if self.includes(info.canonical_identifier()) {
+                    any_selected = true;
+                    kept.push(entry.clone());
+                }
+            }
+            if kept.len() == changeset.entries.len() {
+                covered.push(changeset);
+            } else if self.split_mixed && any_selected {
+                changeset.entries = kept;
+                covered.push(changeset);
+            }
+        }
+        Ok(covered)
+    }
+}
+
+/// Exclude the packages that are not released from `branch`. A package follows its own
+/// `[packages."<package>".git]` branches when set, the global ones otherwise.
+fn restrict_to_release_branch(
+    workspace: &Workspace,
+    config: &Config,
+    branch: &str,
+    selection: &ReleaseSelection,
+) -> Result<ReleaseSelection> {
+    let mut restricted = selection.clone();
+    let mut any_allowed = false;
+    for info in &workspace.members {
+        if config.for_package(info).is_release_branch(branch) {
+            any_allowed = true;
+        } else {
+            restricted
+                .excluded
+                .insert(info.canonical_identifier().to_string());
+        }
+    }
+    if !any_allowed && !config.is_release_branch(branch) {
         return Err(SampoError::Release(format!(
             "Branch '{}' is not configured for releases (allowed: {:?})",
             branch,
             config.release_branches().into_iter().collect::<Vec<_>>()
         )));
     }
+    Ok(restricted)
+}
+
+/// Resolve the fixed groups enforced during releases (`packages.fixed` plus the groups
+/// implied by ecosystem structure) to canonical identifiers.
+pub fn resolve_fixed_groups(workspace: &Workspace, config: &Config) -> Result<Vec<Vec<String>>> {
+    enforced_fixed_groups(workspace, config)
+}
+
+/// Main release function that can be called from CLI or other interfaces
+pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutput> {
+    run_release_with_selection(root, dry_run, &ReleaseSelection::default())
+}
+
+/// Release only the changesets covered by `selection` (see [`ReleaseSelection`]).
+pub fn run_release_with_selection(
+    root: &std::path::Path,
+    dry_run: bool,
+    selection: &ReleaseSelection,
+) -> Result<ReleaseOutput> {
+    run_release_with_now(root, dry_run, selection, Utc::now(), true)
+}
+
+/// The release plan of the changesets covered by `selection`: a dry run that skips the
+/// `[hooks]`, for callers that only read the plan rather than preview the release.
+pub fn plan_release(root: &std::path::Path, selection: &ReleaseSelection) -> Result<ReleaseOutput> {
+    run_release_with_now(root, true, selection, Utc::now(), false)
+}
+
+/// [`run_release_with_selection`], dating the release (changelogs and CalVer versions)
+/// at `now`, and running the `[hooks]` only when `hooks` is set.
+pub(crate) fn run_release_with_now(
+    root: &std::path::Path,
+    dry_run: bool,
+    selection: &ReleaseSelection,
+    now: DateTime<Utc>,
+    hooks: bool,
+) -> Result<ReleaseOutput> {
+    let workspace = discover_workspace(root)?;
+    let mut config = Config::load(&workspace.root)?;
+    if !hooks {
+        config.hooks = Hooks::default();
+    }
+
+    let branch = current_branch()?;
+    let selection = &restrict_to_release_branch(&workspace, &config, &branch, selection)?;
 
     // Validate fixed dependencies configuration
     validate_fixed_dependencies(&config, &workspace)?;
+    validate_aggregate_changelog(&config, &workspace)?;
 
     let changesets_dir = workspace.root.join(".sampo").join("changesets");
     let prerelease_dir = workspace.root.join(".sampo").join("prerelease");
 
-    let current_changesets = load_changesets(&changesets_dir, &config.changesets_tags)?;
-    let preserved_changesets = load_changesets(&prerelease_dir, &config.changesets_tags)?;
+    let current_changesets = selection.retain_covered(
+        &workspace,
+        &config,
+        load_changesets(&changesets_dir, &config.changesets_tags)?,
+    )?;
+    let preserved_changesets = selection.retain_covered(
+        &workspace,
+        &config,
+        load_changesets(&prerelease_dir, &config.changesets_tags)?,
+    )?;
     let preserved_targets = collect_preserved_targets(&preserved_changesets, &workspace)?;
 
     let mut using_preserved = false;
@@ -579,12 +833,17 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
 
     if current_changesets.is_empty() {
         if preserved_changesets.is_empty() {
-            println!(
-                "No changesets found in {}",
-                workspace.root.join(".sampo").join("changesets").display()
-            );
+            if selection.is_unrestricted() {
+                println!(
+                    "No changesets found in {}",
+                    workspace.root.join(".sampo").join("changesets").display()
+                );
+            } else {
+                println!("No changesets found for the selected packages.");
+            }
             return Ok(ReleaseOutput {
                 released_packages: vec![],
+                consumed_changesets: vec![],
                 dry_run,
             });
         }
@@ -596,6 +855,7 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
             );
             return Ok(ReleaseOutput {
                 released_packages: vec![],
+                consumed_changesets: vec![],
                 dry_run,
             });
         }
@@ -609,6 +869,7 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
             &preserved_targets,
             false,
             true,
+            now,
         )? {
             PlanOutcome::Plan(plan) => {
                 let is_prerelease_preview = releases_include_prerelease(&plan.releases);
@@ -623,6 +884,7 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
                     println!("No applicable packages found in changesets.");
                     return Ok(ReleaseOutput {
                         released_packages: vec![],
+                        consumed_changesets: vec![],
                         dry_run,
                     });
                 }
@@ -633,6 +895,7 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
                     println!("No matching workspace crates to release.");
                     return Ok(ReleaseOutput {
                         released_packages: vec![],
+                        consumed_changesets: vec![],
                         dry_run,
                     });
                 }
@@ -658,6 +921,7 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
                     &config,
                     &preserved_targets,
                     false,
+                    now,
                 )?
             }
         };
@@ -677,7 +941,11 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
                 &workspace,
                 &config.changesets_tags,
             )?;
-            final_changesets = load_changesets(&changesets_dir, &config.changesets_tags)?;
+            final_changesets = selection.retain_covered(
+                &workspace,
+                &config,
+                load_changesets(&changesets_dir, &config.changesets_tags)?,
+            )?;
         }
 
         match compute_plan_state(
@@ -687,12 +955,14 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
             &preserved_targets,
             false,
             true,
+            now,
         )? {
             PlanOutcome::Plan(plan) => plan,
             PlanOutcome::NoApplicablePackages => {
                 println!("No applicable packages found in changesets.");
                 return Ok(ReleaseOutput {
                     released_packages: vec![],
+                    consumed_changesets: vec![],
                     dry_run,
                 });
             }
@@ -700,6 +970,7 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
                 println!("No matching workspace crates to release.");
                 return Ok(ReleaseOutput {
                     released_packages: vec![],
+                    consumed_changesets: vec![],
                     dry_run,
                 });
             }
@@ -715,12 +986,14 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
                 &preserved_targets,
                 false,
                 true,
+                now,
             )? {
                 PlanOutcome::Plan(plan) => plan,
                 PlanOutcome::NoApplicablePackages => {
                     println!("No applicable packages found in changesets.");
                     return Ok(ReleaseOutput {
                         released_packages: vec![],
+                        consumed_changesets: vec![],
                         dry_run,
                     });
                 }
@@ -728,6 +1001,7 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
                     println!("No matching workspace crates to release.");
                     return Ok(ReleaseOutput {
                         released_packages: vec![],
+                        consumed_changesets: vec![],
                         dry_run,
                     });
                 }
@@ -738,31 +1012,60 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
     let PlanState {
         mut messages_by_pkg,
         used_paths,
+        pending_notes,
         releases,
         released_packages,
     } = plan_state;
 
     print_release_plan(&workspace, &releases);
+    let version_file_updates = version_file_updates(&workspace, &config, &releases)?;
+    let hook_packages = release_hook_packages(&workspace, &releases);
+    run_hook(
+        &workspace.root,
+        &config.hooks,
+        Hook::PreRelease,
+        dry_run,
+        &hook_packages,
+        None,
+    )?;
 
     let is_prerelease_release = releases_include_prerelease(&releases);
 
     if dry_run {
+        print_version_file_diff(&workspace.root, &version_file_updates);
+        run_hook(
+            &workspace.root,
+            &config.hooks,
+            Hook::PostRelease,
+            true,
+            &hook_packages,
+            None,
+        )?;
         println!("Dry-run: no files modified, no tags created.");
         return Ok(ReleaseOutput {
             released_packages,
+            consumed_changesets: used_paths.into_iter().collect(),
             dry_run: true,
         });
     }
 
+    let split_remainders = if selection.split_mixed {
+        split_changesets(&final_changesets, &config.changesets_tags)?
+    } else {
+        Vec::new()
+    };
+
     apply_releases(
         &releases,
         &workspace,
         &mut messages_by_pkg,
         &final_changesets,
         &config,
+        now,
     )?;
 
     let prerelease_targets = collect_prerelease_targets(&releases);
+    let consumed_changesets: Vec<PathBuf> = used_paths.iter().cloned().collect();
     finalize_consumed_changesets(
         used_paths,
         &workspace.root,
@@ -770,37 +1073,67 @@ pub fn run_release(root: &std::path::Path, dry_run: bool) -> Result<ReleaseOutpu
         &final_changesets,
         &workspace,
         &prerelease_targets,
+        &config.changesets_tags,
     )?;
+    write_pending_notes(&workspace.root, &pending_notes, &config.changesets_tags)?;
+    write_split_remainders(&split_remainders, &config.changesets_tags)?;
 
     regenerate_lockfile(&workspace, &released_ecosystems(&workspace, &releases))?;
 
+    run_hook(
+        &workspace.root,
+        &config.hooks,
+        Hook::PostRelease,
+        false,
+        &hook_packages,
+        None,
+    )?;
+
     Ok(ReleaseOutput {
         released_packages,
+        consumed_changesets,
         dry_run: false,
     })
 }
 
 /// Stabilize prerelease packages in the workspace, producing stable version numbers
 pub fn run_stabilize_release(root: &Path, dry_run: bool) -> Result<ReleaseOutput> {
+    stabilize_release(root, dry_run, true)
+}
+
+/// The plan of [`run_stabilize_release`]: a dry run that skips the `[hooks]`.
+pub fn plan_stabilize_release(root: &Path) -> Result<ReleaseOutput> {
+    stabilize_release(root, true, false)
+}
+
+fn stabilize_release(root: &Path, dry_run: bool, hooks: bool) -> Result<ReleaseOutput> {
+    let now = Utc::now();
     let workspace = discover_workspace(root)?;
-    let config = Config::load(&workspace.root)?;
+    let mut config = Config::load(&workspace.root)?;
+    if !hooks {
+        config.hooks = Hooks::default();
+    }
 
     let branch = current_branch()?;
-    if !config.is_release_branch(&branch) {
-        return Err(SampoError::Release(format!(
-            "Branch '{}' is not configured for releases (allowed: {:?})",
-            branch,
-            config.release_branches().into_iter().collect::<Vec<_>>()
-        )));
-    }
+    let selection =
+        restrict_to_release_branch(&workspace, &config, &branch, &ReleaseSelection::default())?;
 
     validate_fixed_dependencies(&config, &workspace)?;
+    validate_aggregate_changelog(&config, &workspace)?;
 
     let changesets_dir = workspace.root.join(".sampo").join("changesets");
     let prerelease_dir = workspace.root.join(".sampo").join("prerelease");
 
-    let current_changesets = load_changesets(&changesets_dir, &config.changesets_tags)?;
-    let preserved_changesets = load_changesets(&prerelease_dir, &config.changesets_tags)?;
+    let current_changesets = selection.retain_covered(
+        &workspace,
+        &config,
+        load_changesets(&changesets_dir, &config.changesets_tags)?,
+    )?;
+    let preserved_changesets = selection.retain_covered(
+        &workspace,
+        &config,
+        load_changesets(&prerelease_dir, &config.changesets_tags)?,
+    )?;
     let preserved_targets = collect_preserved_targets(&preserved_changesets, &workspace)?;
 
     if current_changesets.is_empty() && preserved_changesets.is_empty() {
@@ -810,6 +1143,7 @@ pub fn run_stabilize_release(root: &Path, dry_run: bool) -> Result<ReleaseOutput
         );
         return Ok(ReleaseOutput {
             released_packages: vec![],
+            consumed_changesets: vec![],
             dry_run,
         });
     }
@@ -819,8 +1153,14 @@ pub fn run_stabilize_release(root: &Path, dry_run: bool) -> Result<ReleaseOutput
     if !dry_run && restores_changesets && any_lockfile_present(&workspace) {
         let mut preview = current_changesets.clone();
         preview.extend(preserved_changesets.clone());
-        let planned =
-            preview_released_ecosystems(&preview, &workspace, &config, &preserved_targets, true)?;
+        let planned = preview_released_ecosystems(
+            &preview,
+            &workspace,
+            &config,
+            &preserved_targets,
+            true,
+            now,
+        )?;
         preflight_lockfile_tools(&workspace, &planned)?;
     }
 
@@ -851,12 +1191,14 @@ pub fn run_stabilize_release(root: &Path, dry_run: bool) -> Result<ReleaseOutput
         &preserved_targets,
         true,
         true,
+        now,
     )? {
         PlanOutcome::Plan(plan) => plan,
         PlanOutcome::NoApplicablePackages => {
             println!("No applicable packages found in changesets.");
             return Ok(ReleaseOutput {
                 released_packages: vec![],
+                consumed_changesets: vec![],
                 dry_run,
             });
         }
@@ -864,6 +1206,7 @@ pub fn run_stabilize_release(root: &Path, dry_run: bool) -> Result<ReleaseOutput
             println!("No matching workspace crates to release.");
             return Ok(ReleaseOutput {
                 released_packages: vec![],
+                consumed_changesets: vec![],
                 dry_run,
             });
         }
@@ -872,16 +1215,37 @@ pub fn run_stabilize_release(root: &Path, dry_run: bool) -> Result<ReleaseOutput
     let PlanState {
         mut messages_by_pkg,
         used_paths,
+        pending_notes,
         releases,
         released_packages,
     } = plan_state;
 
     print_release_plan(&workspace, &releases);
+    let version_file_updates = version_file_updates(&workspace, &config, &releases)?;
+    let hook_packages = release_hook_packages(&workspace, &releases);
+    run_hook(
+        &workspace.root,
+        &config.hooks,
+        Hook::PreRelease,
+        dry_run,
+        &hook_packages,
+        None,
+    )?;
 
     if dry_run {
+        print_version_file_diff(&workspace.root, &version_file_updates);
+        run_hook(
+            &workspace.root,
+            &config.hooks,
+            Hook::PostRelease,
+            true,
+            &hook_packages,
+            None,
+        )?;
         println!("Dry-run: no files modified, no tags created.");
         return Ok(ReleaseOutput {
             released_packages,
+            consumed_changesets: used_paths.into_iter().collect(),
             dry_run: true,
         });
     }
@@ -897,9 +1261,11 @@ pub fn run_stabilize_release(root: &Path, dry_run: bool) -> Result<ReleaseOutput
         &mut messages_by_pkg,
         &final_changesets,
         &config,
+        now,
     )?;
 
     let prerelease_targets = BTreeSet::new();
+    let consumed_changesets: Vec<PathBuf> = used_paths.iter().cloned().collect();
     finalize_consumed_changesets(
         used_paths,
         &workspace.root,
@@ -907,12 +1273,24 @@ pub fn run_stabilize_release(root: &Path, dry_run: bool) -> Result<ReleaseOutput
         &final_changesets,
         &workspace,
         &prerelease_targets,
+        &config.changesets_tags,
     )?;
+    write_pending_notes(&workspace.root, &pending_notes, &config.changesets_tags)?;
 
     regenerate_lockfile(&workspace, &released_kinds)?;
 
+    run_hook(
+        &workspace.root,
+        &config.hooks,
+        Hook::PostRelease,
+        false,
+        &hook_packages,
+        None,
+    )?;
+
     Ok(ReleaseOutput {
         released_packages,
+        consumed_changesets,
         dry_run: false,
     })
 }
@@ -926,8 +1304,9 @@ fn compute_plan_state(
     preserved_targets: &BTreeSet<String>,
     stabilize: bool,
     report_warnings: bool,
+    now: DateTime<Utc>,
 ) -> Result<PlanOutcome> {
-    let (mut bump_by_pkg, messages_by_pkg, used_paths) =
+    let (mut bump_by_pkg, messages_by_pkg, mut used_paths, mut target_by_pkg) =
         compute_initial_bumps(changesets, workspace, config)?;
 
     if bump_by_pkg.is_empty() {
@@ -943,18 +1322,29 @@ fn compute_plan_state(
     // linked has nothing left to raise. Bumps only ever rise through three levels over a
     // finite set of packages, so this converges; with no `packages.linked` it runs once.
     loop {
-        apply_dependency_cascade(&mut bump_by_pkg, &dependents, config, workspace)?;
+        apply_dependency_cascade(
+            &mut bump_by_pkg,
+            &target_by_pkg,
+            &dependents,
+            config,
+            workspace,
+        )?;
         if !apply_linked_dependencies(&mut bump_by_pkg, config, workspace)? {
             break;
         }
     }
 
+    align_fixed_group_targets(&mut target_by_pkg, &bump_by_pkg, workspace, config)?;
+
     let releases = prepare_release_plan(
         &bump_by_pkg,
+        &target_by_pkg,
         workspace,
+        config,
         preserved_targets,
         stabilize,
         &direct_targets,
+        now,
     )?;
     if releases.is_empty() {
         return Ok(PlanOutcome::NoMatchingCrates);
@@ -978,6 +1368,14 @@ fn compute_plan_state(
         .collect();
     PackageAdapter::validate_release_plan(workspace, &new_version_by_id)?;
 
+    let pending_notes = settle_note_entries(
+        changesets,
+        workspace,
+        config,
+        &new_version_by_id,
+        &mut used_paths,
+    )?;
+
     let released_packages: Vec<ReleasedPackage> = releases
         .iter()
         .map(|(name, old_version, new_version)| {
@@ -999,6 +1397,7 @@ fn compute_plan_state(
     Ok(PlanOutcome::Plan(PlanState {
         messages_by_pkg,
         used_paths,
+        pending_notes,
         releases,
         released_packages,
     }))
@@ -1006,6 +1405,9 @@ fn compute_plan_state(
 
 /// Validates dependency constraints before applying releases.
 /// Returns error for fixed/linked packages with violations, warnings otherwise.
+///
+/// Constraints are checked against the planned versions, so a breaking `0.x` release
+/// under `zero_major = "minor"` (`0.4.2` to `0.5.0`) falls outside `^0.4` as expected.
 pub(crate) fn validate_dependency_constraints(
     releases: &ReleasePlan,
     workspace: &Workspace,
@@ -1249,7 +1651,10 @@ fn collect_preserved_targets(
 ) -> Result<BTreeSet<String>> {
     let mut targets = BTreeSet::new();
     for changeset in changesets {
-        for (spec, _, _) in &changeset.entries {
+        for (spec, bump, _) in &changeset.entries {
+            if *bump == Bump::None {
+                continue;
+            }
             let info = resolve_package_spec(workspace, spec)?;
             targets.insert(info.canonical_identifier().to_string());
         }
@@ -1359,13 +1764,11 @@ fn restore_stable_preserved_changesets(
                 stable_path = unique_destination_path(changesets_dir, file_name);
             }
 
-            let stable_content =
-                render_changeset_markdown_with_tags(&stable_entries, &parsed.message);
+            let stable_content = render_changeset_subset(&parsed, &stable_entries);
             fs::write(&stable_path, &stable_content)
                 .map_err(|e| SampoError::Io(io_error_with_path(e, &stable_path)))?;
 
-            let prerelease_content =
-                render_changeset_markdown_with_tags(&prerelease_entries, &parsed.message);
+            let prerelease_content = render_changeset_subset(&parsed, &prerelease_entries);
             fs::write(&path, prerelease_content)
                 .map_err(|e| SampoError::Io(io_error_with_path(e, &path)))?;
         }
@@ -1403,6 +1806,7 @@ fn finalize_consumed_changesets(
     changesets: &[ChangesetInfo],
     workspace: &Workspace,
     prerelease_targets: &BTreeSet<String>,
+    allowed_tags: &[String],
 ) -> Result<()> {
     if used_paths.is_empty() {
         return Ok(());
@@ -1433,6 +1837,29 @@ fn finalize_consumed_changesets(
                     continue;
                 }
 
+                // The rest of a changeset an earlier pre-release preserved, such as notes
+                // left pending then: keep the changeset in one preserved file.
+                if let Some((preserved_path, mut preserved)) =
+                    preserved_copy(&prerelease_dir, changeset, allowed_tags)?
+                {
+                    for entry in prerelease_entries {
+                        if !preserved.entries.contains(&entry) {
+                            preserved.entries.push(entry);
+                        }
+                    }
+                    preserved
+                        .target_versions
+                        .extend(changeset.target_versions.clone());
+                    fs::write(
+                        &preserved_path,
+                        render_changeset_subset(&preserved, &preserved.entries),
+                    )
+                    .map_err(|err| SampoError::Io(io_error_with_path(err, &preserved_path)))?;
+                    fs::remove_file(&path)
+                        .map_err(|err| SampoError::Io(io_error_with_path(err, &path)))?;
+                    continue;
+                }
+
                 if prerelease_entries.len() == changeset.entries.len() {
                     let _ = move_changeset_file(&path, &prerelease_dir)?;
                     continue;
@@ -1446,8 +1873,7 @@ fn finalize_consumed_changesets(
                 if destination.exists() {
                     destination = unique_destination_path(&prerelease_dir, file_name);
                 }
-                let preserved_content =
-                    render_changeset_markdown_with_tags(&prerelease_entries, &changeset.message);
+                let preserved_content = render_changeset_subset(changeset, &prerelease_entries);
                 fs::write(&destination, preserved_content)
                     .map_err(|err| SampoError::Io(io_error_with_path(err, &destination)))?;
                 fs::remove_file(&path)
@@ -1470,6 +1896,73 @@ fn finalize_consumed_changesets(
     Ok(())
 }
 
+/// Narrow the files of changesets split by a [`ReleaseSelection`] to their released
+/// entries, and return the remaining entries of each. Those are written back to the same
+/// file once the release consumed it (see [`write_split_remainders`]), so the commit that
+/// added the changeset is still the one its changelog entries credit.
+fn split_changesets(
+    changesets: &[ChangesetInfo],
+    allowed_tags: &[String],
+) -> Result<Vec<ChangesetInfo>> {
+    let mut remainders = Vec::new();
+    for changeset in changesets {
+        let text = fs::read_to_string(&changeset.path)
+            .map_err(|err| SampoError::Io(io_error_with_path(err, &changeset.path)))?;
+        let Some(original) = parse_changeset(&text, &changeset.path, allowed_tags)? else {
+            continue;
+        };
+        if original.entries.len() == changeset.entries.len() {
+            continue;
+        }
+
+        let remaining: Vec<_> = original
+            .entries
+            .iter()
+            .filter(|entry| !changeset.entries.contains(entry))
+            .cloned()
+            .collect();
+        fs::write(
+            &changeset.path,
+            render_changeset_subset(&original, &changeset.entries),
+        )
+        .map_err(|err| SampoError::Io(io_error_with_path(err, &changeset.path)))?;
+        println!(
+            "Split {}: remaining entries stay pending.",
+            changeset.path.display()
+        );
+        remainders.push(ChangesetInfo {
+            entries: remaining,
+            ..original
+        });
+    }
+    Ok(remainders)
+}
+
+/// Write the remaining entries of split changesets back to their files, next to the
+/// notes [`write_pending_notes`] may have left there.
+fn write_split_remainders(remainders: &[ChangesetInfo], allowed_tags: &[String]) -> Result<()> {
+    for remainder in remainders {
+        let mut changeset = remainder.clone();
+        if changeset.path.exists() {
+            let text = fs::read_to_string(&changeset.path)
+                .map_err(|err| SampoError::Io(io_error_with_path(err, &changeset.path)))?;
+            if let Some(pending) = parse_changeset(&text, &changeset.path, allowed_tags)? {
+                for entry in pending.entries {
+                    if !changeset.entries.contains(&entry) {
+                        changeset.entries.push(entry);
+                    }
+                }
+            }
+        }
+        fs::write(
+            &changeset.path,
+            render_changeset_subset(&changeset, &changeset.entries),
+        )
+        .map_err(|err| SampoError::Io(io_error_with_path(err, &changeset.path)))?;
+    }
+    Ok(())
+}
+
 pub(crate) fn move_changeset_file(source: &Path, dest_dir: &Path) -> Result<PathBuf> {
     if !source.exists() {
         return Ok(source.to_path_buf());
@@ -1648,6 +2141,7 @@ fn preview_released_ecosystems(
     config: &Config,
     preserved_targets: &BTreeSet<String>,
     stabilize: bool,
+    now: DateTime<Utc>,
 ) -> Result<BTreeSet<PackageKind>> {
     match compute_plan_state(
         changesets,
@@ -1656,6 +2150,7 @@ fn preview_released_ecosystems(
         preserved_targets,
         stabilize,
         false,
+        now,
     )? {
         PlanOutcome::Plan(plan) => Ok(released_ecosystems(workspace, &plan.releases)),
         PlanOutcome::NoApplicablePackages | PlanOutcome::NoMatchingCrates => Ok(BTreeSet::new()),
@@ -1679,6 +2174,7 @@ fn compute_initial_bumps(
     let mut bump_by_pkg: BTreeMap<String, Bump> = BTreeMap::new();
     let mut messages_by_pkg: BTreeMap<String, Vec<(String, ChangelogCategory)>> = BTreeMap::new();
     let mut used_paths: BTreeSet<std::path::PathBuf> = BTreeSet::new();
+    let mut target_by_pkg: BTreeMap<String, String> = BTreeMap::new();
 
     // Resolve GitHub repo slug once if available (config, env or origin remote)
     let repo_slug = detect_github_repo_slug_with_config(&ws.root, cfg.github_repository.as_deref());
@@ -1694,31 +2190,54 @@ fn compute_initial_bumps(
                 continue;
             }
 
-            // Mark this changeset as consumed since at least one package is applicable
-            consumed_changeset = true;
-
             let identifier = info.canonical_identifier().to_string();
 
-            bump_by_pkg
-                .entry(identifier.clone())
-                .and_modify(|b| {
-                    if *bump > *b {
-                        *b = *bump;
+            // An explicit version bumps by whatever level separates it from the current one
+            let bump = &match cs.target_versions.get(spec) {
+                Some(target) => {
+                    if let Some(previous) = target_by_pkg.get(&identifier)
+                        && previous != target
+                    {
+                        return Err(SampoError::Release(format!(
+                            "Changesets request conflicting versions for '{}': {} and {}",
+                            identifier, previous, target
+                        )));
                     }
-                })
-                .or_insert(*bump);
+                    target_by_pkg.insert(identifier.clone(), target.clone());
+                    infer_bump_from_versions(&info.version, target)
+                }
+                None => *bump,
+            };
+
+            // `none` entries only carry a changelog note; whether they are consumed
+            // depends on the final plan (see `settle_note_entries`).
+            if *bump != Bump::None {
+                // Mark this changeset as consumed since at least one package is applicable
+                consumed_changeset = true;
+
+                bump_by_pkg
+                    .entry(identifier.clone())
+                    .and_modify(|b| {
+                        if *bump > *b {
+                            *b = *bump;
+                        }
+                    })
+                    .or_insert(*bump);
+            }
 
             // Enrich message with commit info and acknowledgments
             let commit_hash = get_commit_hash_for_path(&ws.root, &cs.path);
             let enriched = if let Some(hash) = commit_hash {
+                let package_cfg = cfg.for_package(info);
                 enrich_changeset_message(
                     &cs.message,
                     &hash,
                     &ws.root,
                     repo_slug.as_deref(),
                     github_token.as_deref(),
-                    cfg.changelog_show_commit_hash,
-                    cfg.changelog_show_acknowledgments
+                    package_cfg.changelog_show_commit_hash,
+                    package_cfg
+                        .changelog_show_acknowledgments
                         .then_some(AcknowledgmentStyle::Mention),
                 )
             } else {
@@ -1741,7 +2260,105 @@ fn compute_initial_bumps(
         }
     }
 
-    Ok((bump_by_pkg, messages_by_pkg, used_paths))
+    Ok((bump_by_pkg, messages_by_pkg, used_paths, target_by_pkg))
+}
+
+/// Decide what happens to `none` entries once the released packages are known.
+///
+/// A note is consumed when its package is released. A changeset made only of notes is
+/// consumed once all of them are; when some of its entries are consumed but others are
+/// not, the changeset is consumed and the pending notes are returned, to be written back
+/// under the same path with the same message.
+fn settle_note_entries(
+    changesets: &[ChangesetInfo],
+    ws: &Workspace,
+    cfg: &Config,
+    released: &BTreeMap<String, String>,
+    used_paths: &mut BTreeSet<PathBuf>,
+) -> Result<Vec<ChangesetInfo>> {
+    let mut pending_notes = Vec::new();
+    for cs in changesets {
+        let mut consumed_any = used_paths.contains(&cs.path);
+        let mut pending = Vec::new();
+        for entry in &cs.entries {
+            if entry.1 != Bump::None {
+                continue;
+            }
+            let info = resolve_package_spec(ws, &entry.0)?;
+            if should_ignore_package(cfg, ws, info)? {
+                continue;
+            }
+            if released.contains_key(info.canonical_identifier()) {
+                consumed_any = true;
+            } else {
+                pending.push(entry.clone());
+            }
+        }
+
+        if !consumed_any {
+            continue;
+        }
+        used_paths.insert(cs.path.clone());
+        if !pending.is_empty() {
+            pending_notes.push(ChangesetInfo {
+                path: cs.path.clone(),
+                entries: pending,
+                target_versions: BTreeMap::new(),
+                message: cs.message.clone(),
+            });
+        }
+    }
+    Ok(pending_notes)
+}
+
+/// Write back the `none` entries left pending by [`settle_note_entries`], except those the
+/// pre-release copy of their changeset already records: an earlier pre-release released them.
+fn write_pending_notes(
+    root: &Path,
+    pending_notes: &[ChangesetInfo],
+    allowed_tags: &[String],
+) -> Result<()> {
+    let prerelease_dir = root.join(".sampo").join("prerelease");
+    for changeset in pending_notes {
+        let recorded = preserved_copy(&prerelease_dir, changeset, allowed_tags)?
+            .map(|(_, preserved)| preserved.entries)
+            .unwrap_or_default();
+        let entries: Vec<_> = changeset
+            .entries
+            .iter()
+            .filter(|entry| !recorded.contains(entry))
+            .cloned()
+            .collect();
+        if entries.is_empty() {
+            continue;
+        }
+        fs::write(
+            &changeset.path,
+            render_changeset_subset(changeset, &entries),
+        )
+        .map_err(|err| SampoError::Io(io_error_with_path(err, &changeset.path)))?;
+    }
+    Ok(())
+}
+
+/// The file a pre-release preserved for `changeset`: the one with the same name and message.
+fn preserved_copy(
+    prerelease_dir: &Path,
+    changeset: &ChangesetInfo,
+    allowed_tags: &[String],
+) -> Result<Option<(PathBuf, ChangesetInfo)>> {
+    let Some(file_name) = changeset.path.file_name() else {
+        return Ok(None);
+    };
+    let path = prerelease_dir.join(file_name);
+    if path == changeset.path || !path.is_file() {
+        return Ok(None);
+    }
+    let text =
+        fs::read_to_string(&path).map_err(|err| SampoError::Io(io_error_with_path(err, &path)))?;
+    Ok(parse_changeset(&text, &path, allowed_tags)?
+        .filter(|preserved| preserved.message == changeset.message)
+        .map(|preserved| (path, preserved)))
 }
 
 /// Build reverse dependency graph: dep -> set of dependents
@@ -1764,6 +2381,8 @@ fn build_dependency_graph(ws: &Workspace, cfg: &Config) -> BTreeMap<String, BTre
             continue;
         }
 
+        // Dev edges stay even without `cascade_dev_dependencies`: the cascade still bumps
+        // a dev-dependent whose constraint rejects the new version.
         for dep in c.internal_deps.iter().chain(&c.internal_dev_deps) {
             // Also skip dependencies that point to ignored packages
             if ignored_packages.contains(dep) {
@@ -1779,15 +2398,112 @@ fn build_dependency_graph(ws: &Workspace, cfg: &Config) -> BTreeMap<String, BTre
     dependents
 }
 
+/// The cascade policy each package follows as a dependent.
+fn cascade_policies(
+    workspace: &Workspace,
+    config: &Config,
+) -> Result<BTreeMap<String, CascadePolicy>> {
+    let mut policies: BTreeMap<String, CascadePolicy> = workspace
+        .members
+        .iter()
+        .map(|info| (info.canonical_identifier().to_string(), config.cascade))
+        .collect();
+    for group in &config.cascade_groups {
+        for package in &group.packages {
+            let identifier = resolve_config_value(workspace, package, "packages.cascade_groups")?;
+            policies.insert(identifier, group.cascade);
+        }
+    }
+    Ok(policies)
+}
+
+/// The version `dependency` is heading to, as far as the cascade can tell: its explicit
+/// target, or its current version bumped under its `zero_major` policy.
+fn predicted_version(
+    dependency: &PackageInfo,
+    bump: Bump,
+    target_by_pkg: &BTreeMap<String, String>,
+    policy: ZeroMajorPolicy,
+) -> Result<String> {
+    if let Some(target) = target_by_pkg.get(dependency.canonical_identifier()) {
+        return Ok(target.clone());
+    }
+    let current = parse_version_string(&dependency.version).map_err(SampoError::Release)?;
+    bump_version(&dependency.version, zero_major_bump(&current, bump, policy))
+        .map_err(SampoError::Release)
+}
+
+/// Whether the constraint `dependent` declares on `dependency` rejects `new_version`, as
+/// reported by the adapter. Skipped and unverifiable checks do not reject.
+fn constraint_rejects(
+    cargo_metadata: &mut Option<ManifestMetadata>,
+    workspace: &Workspace,
+    dependent: &PackageInfo,
+    dependency: &PackageInfo,
+    new_version: &str,
+) -> Result<bool> {
+    let adapter = PackageAdapter::from_kind(dependent.kind);
+    let manifest_path = adapter.manifest_path(&dependent.path);
+    let constraint = match dependent.kind {
+        PackageKind::Cargo => {
+            if cargo_metadata.is_none() {
+                *cargo_metadata = Some(ManifestMetadata::load(workspace)?);
+            }
+            cargo_metadata
+                .as_ref()
+                .and_then(|meta| meta.get_dependency_constraint(&manifest_path, &dependency.name))
+                .unwrap_or_else(|| "*".to_string())
+        }
+        _ => "*".to_string(),
+    };
+    let result = adapter.check_dependency_constraint(
+        &manifest_path,
+        &dependency.name,
+        &constraint,
+        new_version,
+    )?;
+    Ok(matches!(result, ConstraintCheckResult::NotSatisfied { .. }))
+}
+
 /// Apply dependency cascade logic and fixed dependency groups
 fn apply_dependency_cascade(
     bump_by_pkg: &mut BTreeMap<String, Bump>,
+    target_by_pkg: &BTreeMap<String, String>,
     dependents: &BTreeMap<String, BTreeSet<String>>,
     cfg: &Config,
     ws: &Workspace,
 ) -> Result<()> {
     let resolved_fixed_groups = enforced_fixed_groups(ws, cfg)?;
 
+    // Fixed members share one bump level and one version, so they must also agree on
+    // what that level means on 0.x, or the group would split into `0.5.0` and `1.0.0`.
+    let policies = zero_major_policies(ws, cfg);
+    for group in &resolved_fixed_groups {
+        let mut group_policies = group.iter().filter_map(|id| policies.get(id));
+        if let Some(first) = group_policies.next()
+            && group_policies.any(|policy| policy != first)
+        {
+            return Err(SampoError::Config(format!(
+                "Packages in a fixed group must share the same versioning.zero_major policy: {}",
+                group.join(", ")
+            )));
+        }
+    }
+
+    // A fixed group releases one version, which can't be a date and a semver bump at once.
+    let schemes = versioning_schemes(ws, cfg);
+    for group in &resolved_fixed_groups {
+        let mut group_schemes = group.iter().filter_map(|id| schemes.get(id));
+        if let Some(first) = group_schemes.next()
+            && group_schemes.any(|scheme| scheme != first)
+        {
+            return Err(SampoError::Config(format!(
+                "Packages in a fixed group must share the same versioning scheme: {}",
+                group.join(", ")
+            )));
+        }
+    }
+
     // Helper function to find which fixed group a package belongs to, if any
     let find_fixed_group = |pkg_id: &str| -> Option<usize> {
         resolved_fixed_groups
@@ -1801,6 +2517,10 @@ fn apply_dependency_cascade(
         by_id.insert(c.canonical_identifier().to_string(), c);
     }
 
+    let cascade_policies = cascade_policies(ws, cfg)?;
+    // Only loaded once a `none` dependent needs its Cargo constraint read.
+    let mut cargo_metadata: Option<ManifestMetadata> = None;
+
     let mut queue: Vec<String> = bump_by_pkg.keys().cloned().collect();
     let mut seen: BTreeSet<String> = queue.iter().cloned().collect();
 
@@ -1823,12 +2543,48 @@ fn apply_dependency_cascade(
                 }
 
                 // Determine bump level for this dependent
+                // Without `cascade_dev_dependencies`, a dev-only edge cascades like
+                // `cascade = "none"`: only when the dev-dependency constraint breaks.
+                let dev_only = !cfg.cascade_dev_dependencies
+                    && by_id
+                        .get(dep_name)
+                        .is_some_and(|info| !info.internal_deps.contains(&changed));
                 let dependent_bump = if find_fixed_group(dep_name).is_some() {
                     // Fixed dependencies: same bump level as the dependency
                     changed_bump
                 } else {
-                    // Normal dependencies: at least patch
-                    Bump::Patch
+                    let policy = if dev_only {
+                        CascadePolicy::None
+                    } else {
+                        cascade_policies.get(dep_name).copied().unwrap_or_default()
+                    };
+                    match policy {
+                        CascadePolicy::Patch => Bump::Patch,
+                        CascadePolicy::Match => changed_bump,
+                        CascadePolicy::None => {
+                            let (Some(dependent), Some(dependency)) =
+                                (by_id.get(dep_name), by_id.get(&changed))
+                            else {
+                                continue;
+                            };
+                            let new_version = predicted_version(
+                                dependency,
+                                changed_bump,
+                                target_by_pkg,
+                                policies.get(&changed).copied().unwrap_or_default(),
+                            )?;
+                            if !constraint_rejects(
+                                &mut cargo_metadata,
+                                ws,
+                                dependent,
+                                dependency,
+                                &new_version,
+                            )? {
+                                continue;
+                            }
+                            Bump::Patch
+                        }
+                    }
                 };
 
                 let entry = bump_by_pkg
@@ -1957,19 +2713,80 @@ fn version_plan_error(
     SampoError::Release(message)
 }
 
+/// Extend explicit target versions across `fixed` groups.
+///
+/// Members sharing the current version of a targeted member are released at the same
+/// target, so the group stays aligned; two different targets in one group are rejected.
+/// Members on another version are left to their bump, since the target may not even be
+/// greater than their current version.
+fn align_fixed_group_targets(
+    target_by_pkg: &mut BTreeMap<String, String>,
+    bump_by_pkg: &BTreeMap<String, Bump>,
+    ws: &Workspace,
+    cfg: &Config,
+) -> Result<()> {
+    if target_by_pkg.is_empty() {
+        return Ok(());
+    }
+
+    for group in enforced_fixed_groups(ws, cfg)? {
+        let mut targeted = group
+            .iter()
+            .filter_map(|id| target_by_pkg.get(id).map(|target| (id, target)));
+        let Some((anchor, target)) = targeted.next() else {
+            continue;
+        };
+        if let Some((other, other_target)) = targeted.find(|(_, t)| *t != target) {
+            return Err(SampoError::Release(format!(
+                "Packages '{}' and '{}' share a fixed group but request different versions ({} and {})",
+                anchor, other, target, other_target
+            )));
+        }
+
+        let target = target.clone();
+        let anchor_version = ws
+            .find_by_identifier(anchor)
+            .map(|info| info.version.clone());
+        for member in &group {
+            if target_by_pkg.contains_key(member) || !bump_by_pkg.contains_key(member) {
+                continue;
+            }
+            let member_version = ws.find_by_identifier(member).map(|info| &info.version);
+            if member_version == anchor_version.as_ref() {
+                target_by_pkg.insert(member.clone(), target.clone());
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Prepare the release plan by matching bumps to workspace members
+#[allow(clippy::too_many_arguments)]
 fn prepare_release_plan(
     bump_by_pkg: &BTreeMap<String, Bump>,
+    target_by_pkg: &BTreeMap<String, String>,
     ws: &Workspace,
+    cfg: &Config,
     preserved_targets: &BTreeSet<String>,
     stabilize: bool,
     direct_targets: &BTreeSet<String>,
+    now: DateTime<Utc>,
 ) -> Result<ReleasePlan> {
     // Map package identifier -> PackageInfo for quick lookup
     let mut by_id: BTreeMap<String, &PackageInfo> = BTreeMap::new();
     for c in &ws.members {
         by_id.insert(c.canonical_identifier().to_string(), c);
     }
+    let policies = zero_major_policies(ws, cfg);
+    let schemes = versioning_schemes(ws, cfg);
+    let calver_date = if schemes
+        .values()
+        .any(|scheme| matches!(scheme, VersioningScheme::Calver(_)))
+    {
+        Some(release_date(cfg, now)?)
+    } else {
+        None
+    };
 
     let mut releases: Vec<(String, String, String)> = Vec::new(); // (name, old_version, new_version)
     for (identifier, bump) in bump_by_pkg {
@@ -1982,12 +2799,23 @@ fn prepare_release_plan(
 
             let parsed = parse_version_string(&old)
                 .map_err(|err| version_plan_error(identifier, &old, &err, direct_targets))?;
-            let newv = if stabilize && !parsed.pre.is_empty() {
-                stabilize_version_from_parsed(&parsed, *bump)
+            let policy = policies.get(identifier).copied().unwrap_or_default();
+            let bump = zero_major_bump(&parsed, *bump, policy);
+            let scheme = schemes.get(identifier).unwrap_or(&VersioningScheme::Semver);
+            let newv = if let Some(target) = target_by_pkg.get(identifier) {
+                // Calendar versions are not ordered by bump level, so only semver
+                // targets are checked against the bumps other changesets request.
+                let required = matches!(scheme, VersioningScheme::Semver).then_some(bump);
+                explicit_target_version(&parsed, target, required)
+            } else if let (VersioningScheme::Calver(format), Some(date)) = (scheme, calver_date) {
+                let bump_base = should_bump_prerelease_base(&parsed, identifier, preserved_targets);
+                calver_version_from_parsed(format, &parsed, date, stabilize, bump_base)
+            } else if stabilize && !parsed.pre.is_empty() {
+                stabilize_version_from_parsed(&parsed, bump)
             } else if should_bump_prerelease_base(&parsed, identifier, preserved_targets) {
-                bump_prerelease_entry(&parsed, *bump)
+                bump_prerelease_entry(&parsed, bump)
             } else {
-                bump_version_from_parsed(&parsed, *bump)
+                bump_version_from_parsed(&parsed, bump)
             }
             .map_err(|err| version_plan_error(identifier, &old, &err, direct_targets))?;
 
@@ -1998,6 +2826,98 @@ fn prepare_release_plan(
     Ok(releases)
 }
 
+/// Compute the next calendar version of `current` for a release on `date`.
+///
+/// Within the current period the `MICRO` counter advances, and a new period starts it
+/// over at 0. Pre-release labels follow the semver rules: an ongoing pre-release only
+/// bumps its numeric suffix until the period changes.
+fn calver_version_from_parsed(
+    format: &CalverFormat,
+    current: &Version,
+    date: NaiveDate,
+    stabilize: bool,
+    bump_base: bool,
+) -> std::result::Result<String, String> {
+    let period = format.period(date);
+    let current_period = (current.major, current.minor);
+    if period < current_period {
+        return Err(format!(
+            "the release date {date} falls before the {format} period of the current version"
+        ));
+    }
+
+    let mut version = current.clone();
+    version.build = BuildMetadata::EMPTY;
+    if !current.pre.is_empty() && stabilize {
+        version.pre = Prerelease::EMPTY;
+        return Ok(version.to_string());
+    }
+    if !current.pre.is_empty() && !bump_base && period == current_period {
+        version.pre = increment_prerelease(&current.pre)?;
+        return Ok(version.to_string());
+    }
+
+    if period == current_period {
+        version.patch = current
+            .patch
+            .checked_add(1)
+            .ok_or_else(|| "Patch component overflow".to_string())?;
+    } else {
+        (version.major, version.minor, version.patch) = (period.0, period.1, 0);
+    }
+    if !current.pre.is_empty() {
+        version.pre = strip_trailing_numeric_identifiers(&current.pre).ok_or_else(|| {
+            "Pre-release version must include a non-numeric identifier before the counter"
+                .to_string()
+        })?;
+    }
+    Ok(version.to_string())
+}
+
+/// Shift a bump level down one step on `0.x` under `zero_major = "minor"`, so breaking
+/// changes bump minor and features bump patch.
+fn zero_major_bump(version: &Version, bump: Bump, policy: ZeroMajorPolicy) -> Bump {
+    if policy != ZeroMajorPolicy::Minor || version.major != 0 {
+        return bump;
+    }
+    match bump {
+        Bump::Major => Bump::Minor,
+        Bump::Minor => Bump::Patch,
+        other => other,
+    }
+}
+
+/// Validate an explicit `=<version>` against the current version and, when given, the
+/// bump the package's other changesets and the cascade require.
+fn explicit_target_version(
+    current: &Version,
+    target: &str,
+    required: Option<Bump>,
+) -> std::result::Result<String, String> {
+    let parsed = parse_version_string(target)?;
+    if parsed <= *current {
+        return Err(format!(
+            "the requested version {target} is not greater than the current version"
+        ));
+    }
+    let reached = if parsed.major > current.major {
+        Bump::Major
+    } else if parsed.minor > current.minor {
+        Bump::Minor
+    } else {
+        Bump::Patch
+    };
+    if let Some(required) = required
+        && required > reached
+    {
+        return Err(format!(
+            "the requested version {target} is only a {reached} release, but other changes \
+             require a {required} bump"
+        ));
+    }
+    Ok(parsed.to_string())
+}
+
 /// Print the planned releases
 fn print_release_plan(workspace: &Workspace, releases: &ReleasePlan) {
     let include_kind = workspace.has_multiple_package_kinds();
@@ -2101,8 +3021,31 @@ fn parse_release_date_timezone(spec: &str) -> Result<ReleaseDateTimezone> {
     }
 }
 
-fn compute_release_date_display(cfg: &Config) -> Result<Option<String>> {
-    compute_release_date_display_with_now(cfg, Utc::now())
+impl ReleaseDateTimezone {
+    fn from_config(cfg: &Config) -> Result<Self> {
+        let timezone_pref = cfg
+            .changelog_release_date_timezone
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(parse_release_date_timezone)
+            .transpose()?;
+        Ok(timezone_pref.unwrap_or(ReleaseDateTimezone::Local))
+    }
+
+    fn date(self, now: DateTime<Utc>) -> NaiveDate {
+        match self {
+            ReleaseDateTimezone::Local => now.with_timezone(&Local).date_naive(),
+            ReleaseDateTimezone::Utc => now.date_naive(),
+            ReleaseDateTimezone::Offset(offset) => now.with_timezone(&offset).date_naive(),
+            ReleaseDateTimezone::Named(zone) => now.with_timezone(&zone).date_naive(),
+        }
+    }
+}
+
+/// The release date in the `changelog.release_date_timezone`, which dates CalVer versions.
+fn release_date(cfg: &Config, now: DateTime<Utc>) -> Result<NaiveDate> {
+    Ok(ReleaseDateTimezone::from_config(cfg)?.date(now))
 }
 
 fn compute_release_date_display_with_now(
@@ -2118,15 +3061,7 @@ fn compute_release_date_display_with_now(
         return Ok(None);
     }
 
-    let timezone_pref = cfg
-        .changelog_release_date_timezone
-        .as_deref()
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .map(parse_release_date_timezone)
-        .transpose()?;
-
-    let tz = timezone_pref.unwrap_or(ReleaseDateTimezone::Local);
+    let tz = ReleaseDateTimezone::from_config(cfg)?;
 
     let formatted = match tz {
         ReleaseDateTimezone::Local => now.with_timezone(&Local).format(format_str).to_string(),
@@ -2147,6 +3082,7 @@ fn apply_releases(
     messages_by_pkg: &mut BTreeMap<String, Vec<(String, ChangelogCategory)>>,
     changesets: &[ChangesetInfo],
     cfg: &Config,
+    now: DateTime<Utc>,
 ) -> Result<()> {
     // Build lookup map for all packages
     let mut by_id: BTreeMap<String, &PackageInfo> = BTreeMap::new();
@@ -2186,8 +3122,6 @@ fn apply_releases(
             .extend(explanations);
     }
 
-    let release_date_display = compute_release_date_display(cfg)?;
-
     for (name, old, newv) in releases {
         let info = by_id
             .get(name.as_str())
@@ -2211,9 +3145,11 @@ fn apply_releases(
         )?;
         fs::write(&manifest_path, updated)?;
 
+        let package_cfg = cfg.for_package(info);
+        let release_date_display = compute_release_date_display_with_now(&package_cfg, now)?;
         let messages = messages_by_pkg.get(name).cloned().unwrap_or_default();
         update_changelog(
-            &info.path,
+            &info.path.join(&package_cfg.changelog_path),
             &info.name,
             old,
             newv,
@@ -2222,7 +3158,21 @@ fn apply_releases(
         )?;
     }
 
+    if let Some(aggregate) = &cfg.changelog_aggregate {
+        update_aggregate_changelog(
+            &ws.root.join(aggregate),
+            releases,
+            ws,
+            messages_by_pkg,
+            cfg,
+            now,
+        )?;
+    }
+
     PackageAdapter::finalize_workspace_roots(ws, &new_version_by_name)?;
+    // Planned again now that the manifests and changelogs are written, so the rewrites
+    // apply to the files as the release left them.
+    write_version_files(&version_file_updates(ws, cfg, releases)?)?;
 
     Ok(())
 }
@@ -2351,6 +3301,7 @@ fn prerelease_has_trailing_numeric(pre: &Prerelease) -> bool {
 
 fn apply_base_bump(version: &mut Version, bump: Bump) -> std::result::Result<(), String> {
     match bump {
+        Bump::None => return Ok(()),
         Bump::Patch => {
             version.patch = version
                 .patch
@@ -2487,7 +3438,57 @@ fn split_intro_and_versions(body: &str) -> (&str, &str) {
     (body, "")
 }
 
-fn header_matches_release_version(header_text: &str, version: &str) -> bool {
+/// Render a release commit message from `git.release_commit_message`. `{releases}` expands
+/// to `name@version` pairs, `{release_list}` to one `- name old -> new` line per package.
+pub fn render_release_commit_message<'a>(
+    template: &str,
+    releases: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>,
+) -> String {
+    let releases: Vec<_> = releases.into_iter().collect();
+    let inline = releases
+        .iter()
+        .map(|(name, _old, new)| format!("{name}@{new}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let list = releases
+        .iter()
+        .map(|(name, old, new)| format!("- {name} {old} -> {new}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    template
+        .replace("{releases}", &inline)
+        .replace("{release_list}", &list)
+        .trim_end()
+        .to_string()
+}
+
+/// Release notes from a changelog: the body of the `##` section for `version`, or of the
+/// first section when no version is given. The heading itself is left out.
+pub fn extract_changelog_section(path: &Path, version: Option<&str>) -> Option<String> {
+    let text = fs::read_to_string(path).ok()?;
+    let mut collecting = false;
+    let mut collected = Vec::new();
+
+    for line in text.lines() {
+        if let Some(heading) = line.trim_start().strip_prefix("## ") {
+            if collecting {
+                break;
+            }
+            collecting = version
+                .is_none_or(|version| header_matches_release_version(heading.trim(), version));
+            continue;
+        }
+
+        if collecting {
+            collected.push(line);
+        }
+    }
+
+    let body = collected.join("\n").trim().to_string();
+    if body.is_empty() { None } else { Some(body) }
+}
+
+pub(crate) fn header_matches_release_version(header_text: &str, version: &str) -> bool {
     if header_text == version {
         return true;
     }
@@ -2502,16 +3503,15 @@ fn header_matches_release_version(header_text: &str, version: &str) -> bool {
 }
 
 fn update_changelog(
-    crate_dir: &Path,
+    path: &Path,
     package: &str,
     old_version: &str,
     new_version: &str,
     entries: &[(String, ChangelogCategory)],
     release_date_display: Option<&str>,
 ) -> Result<()> {
-    let path = crate_dir.join("CHANGELOG.md");
     let existing = if path.exists() {
-        fs::read_to_string(&path)?
+        fs::read_to_string(path)?
     } else {
         String::new()
     };
@@ -2610,7 +3610,8 @@ fn update_changelog(
                 "Major changes" => 0,
                 "Minor changes" => 1,
                 "Patch changes" => 2,
-                _ => 3, // Unknown headings go last
+                "Notes" => 3,
+                _ => 4, // Unknown headings go last
             }
         };
         sections.sort_by_key(|(a, _)| bump_order(a));
@@ -2651,22 +3652,292 @@ fn update_changelog(
         combined.push_str(&versions_body);
     }
 
-    fs::write(&path, combined)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| io_error_with_path(err, parent))?;
+    }
+    fs::write(path, combined)?;
     Ok(())
 }
 
+/// Prepend this release to the `[changelog] aggregate` file at the repository root.
+///
+/// The section is headed by the release date, or by the version of the package named in
+/// `changelog.aggregate_version` when it is released. Releases sharing a heading (e.g.
+/// two on the same day) are merged into one section.
+fn update_aggregate_changelog(
+    path: &Path,
+    releases: &ReleasePlan,
+    ws: &Workspace,
+    messages_by_pkg: &BTreeMap<String, Vec<(String, ChangelogCategory)>>,
+    cfg: &Config,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    let date_display = compute_release_date_display_with_now(cfg, now)?;
+    let product_version = match &cfg.changelog_aggregate_version {
+        Some(package) => {
+            let identifier = resolve_config_value(ws, package, "changelog.aggregate_version")?;
+            releases
+                .iter()
+                .find(|(id, _, _)| *id == identifier)
+                .map(|(_, _, new)| new.clone())
+        }
+        None => None,
+    };
+    let heading = match (product_version, date_display) {
+        (Some(version), Some(date)) => format!("{version} — {date}"),
+        (Some(version), None) => version,
+        (None, Some(date)) => date,
+        (None, None) => release_date(cfg, now)?.format("%Y-%m-%d").to_string(),
+    };
+
+    let include_kind = ws.has_multiple_package_kinds();
+    let package_level = if include_kind { "####" } else { "###" };
+    let mut body = String::new();
+    let mut current_kind = None;
+    let mut released: Vec<(&PackageInfo, &String)> = releases
+        .iter()
+        .filter_map(|(id, _, new)| ws.find_by_identifier(id).map(|info| (info, new)))
+        .collect();
+    released.sort_by_key(|(info, _)| (info.kind, info.name.clone()));
+    for (info, new_version) in released {
+        if include_kind && current_kind != Some(info.kind) {
+            current_kind = Some(info.kind);
+            body.push_str(&format!("### {}\n\n", info.kind.display_name()));
+        }
+        body.push_str(&format!("{package_level} {} {new_version}\n\n", info.name));
+
+        let entries = messages_by_pkg
+            .get(info.canonical_identifier())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let mut categories: Vec<&ChangelogCategory> = Vec::new();
+        for (_, category) in entries {
+            if !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+        if !entries
+            .iter()
+            .any(|(_, cat)| matches!(cat, ChangelogCategory::Tag(_)))
+        {
+            categories.sort_by_key(|category| category.sort_key());
+        }
+        for category in categories {
+            body.push_str(&format!("{package_level}# {}\n\n", category.heading()));
+            for (msg, _) in entries.iter().filter(|(_, cat)| cat == category) {
+                body.push_str(&crate::markdown::format_markdown_list_item(msg));
+            }
+            body.push('\n');
+        }
+    }
+
+    let existing = if path.exists() {
+        fs::read_to_string(path).map_err(|err| io_error_with_path(err, path))?
+    } else {
+        String::new()
+    };
+    let cleaned = existing.trim_start_matches('\u{feff}');
+    let (intro, mut versions_body) = split_intro_and_versions(cleaned);
+    let intro = if intro.trim().is_empty() {
+        "# Changelog\n\n".to_string()
+    } else {
+        format!("{}\n\n", intro.trim_end())
+    };
+
+    // Same heading as the top section: merge into it rather than repeat it.
+    let header_line = format!("## {heading}");
+    if let Some(rest) = versions_body.strip_prefix(header_line.as_str())
+        && (rest.is_empty() || rest.starts_with('\n'))
+    {
+        let rest = rest.trim_start_matches('\n');
+        let (previous, remaining) = match rest.find("\n## ") {
+            Some(pos) => rest.split_at(pos + 1),
+            None => (rest, ""),
+        };
+        body = merge_changelog_sections(&body, previous);
+        versions_body = remaining;
+    }
+
+    let mut combined = format!("{intro}{header_line}\n\n{}", body.trim_end());
+    combined.push('\n');
+    if !versions_body.trim().is_empty() {
+        combined.push('\n');
+        combined.push_str(versions_body);
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| io_error_with_path(err, parent))?;
+    }
+    fs::write(path, combined).map_err(|err| io_error_with_path(err, path))?;
+    Ok(())
+}
+
+/// A heading of a changelog section with the lines under it and its subheadings.
+#[derive(Default)]
+struct SectionNode {
+    heading: String,
+    level: usize,
+    lines: Vec<String>,
+    children: Vec<SectionNode>,
+}
+
+impl SectionNode {
+    fn parse(text: &str) -> Self {
+        let mut stack = vec![SectionNode::default()];
+        // The marker of the code fence the current line is in, whose `#` lines are code.
+        let mut fence: Option<&str> = None;
+        for line in text.lines() {
+            let level = line.chars().take_while(|c| *c == '#').count();
+            let in_code = fence.is_some();
+            let trimmed = line.trim_start();
+            fence = match fence {
+                Some(marker) => {
+                    let closes = trimmed.starts_with(marker)
+                        && trimmed.trim_start_matches(&marker[..1]).trim().is_empty();
+                    (!closes).then_some(marker)
+                }
+                None => code_fence_marker(trimmed),
+            };
+            if !in_code && level > 0 && line[level..].starts_with(' ') {
+                while stack.last().is_some_and(|node| node.level >= level) {
+                    let node = stack.pop().expect("non-empty stack");
+                    stack.last_mut().expect("root node").children.push(node);
+                }
+                stack.push(SectionNode {
+                    heading: line.trim_end().to_string(),
+                    level,
+                    ..SectionNode::default()
+                });
+            } else {
+                stack
+                    .last_mut()
+                    .expect("root node")
+                    .lines
+                    .push(line.to_string());
+            }
+        }
+        while stack.len() > 1 {
+            let node = stack.pop().expect("non-empty stack");
+            stack.last_mut().expect("root node").children.push(node);
+        }
+        stack.pop().expect("root node")
+    }
+
+    /// Merge `other` after this node's own content, subheading by subheading.
+    fn merge(&mut self, other: SectionNode) {
+        self.lines = trim_blank_lines(&self.lines);
+        self.lines.extend(trim_blank_lines(&other.lines));
+        for child in other.children {
+            match self
+                .children
+                .iter_mut()
+                .find(|existing| existing.heading == child.heading)
+            {
+                Some(existing) => existing.merge(child),
+                None => self.children.push(child),
+            }
+        }
+    }
+
+    fn render(&self, out: &mut String) {
+        if !self.heading.is_empty() {
+            out.push_str(&self.heading);
+            out.push_str("\n\n");
+        }
+        let lines = trim_blank_lines(&self.lines);
+        if !lines.is_empty() {
+            out.push_str(&lines.join("\n"));
+            out.push_str("\n\n");
+        }
+        for child in &self.children {
+            child.render(out);
+        }
+    }
+}
+
+fn trim_blank_lines(lines: &[String]) -> Vec<String> {
+    let start = lines.iter().position(|line| !line.trim().is_empty());
+    let end = lines.iter().rposition(|line| !line.trim().is_empty());
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].to_vec(),
+        _ => Vec::new(),
+    }
+}
+
+/// The opening run of backticks or tildes when `line` starts a fenced code block.
+fn code_fence_marker(line: &str) -> Option<&str> {
+    let marker = ['`', '~'].into_iter().find_map(|fence| {
+        let len = line.chars().take_while(|c| *c == fence).count();
+        (len >= 3).then(|| &line[..len])
+    })?;
+    // Backtick fences cannot carry backticks in their info string.
+    (marker.starts_with('~') || !line[marker.len()..].contains('`')).then_some(marker)
+}
+
+/// Merge two bodies of changelog sections sharing a heading. Subheadings present in both
+/// (e.g. `### Cargo`) appear once, with the entries of `newer` first.
+fn merge_changelog_sections(newer: &str, older: &str) -> String {
+    let mut merged = SectionNode::parse(newer);
+    merged.merge(SectionNode::parse(older));
+    let mut out = String::new();
+    merged.render(&mut out);
+    out
+}
+
 /// Validate fixed dependencies configuration against the workspace
 fn validate_fixed_dependencies(config: &Config, workspace: &Workspace) -> Result<()> {
     resolve_config_groups(workspace, &config.fixed_dependencies, "packages.fixed")?;
     Ok(())
 }
 
+/// Reject an aggregate changelog that is also the changelog of a package.
+fn validate_aggregate_changelog(config: &Config, workspace: &Workspace) -> Result<()> {
+    let Some(aggregate) = &config.changelog_aggregate else {
+        return Ok(());
+    };
+    let aggregate_path = workspace.root.join(aggregate);
+    for info in &workspace.members {
+        if info.path.join(&config.for_package(info).changelog_path) == aggregate_path {
+            return Err(SampoError::Config(format!(
+                "changelog.aggregate '{}' is the changelog of {}; choose another path",
+                aggregate,
+                info.display_name(workspace.has_multiple_package_kinds())
+            )));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::TimeZone;
     use std::collections::BTreeMap;
 
+    #[test]
+    fn merges_changelog_sections_by_subheading() {
+        let newer = "### Cargo\n\n#### foo 0.1.1\n\n##### Patch changes\n\n- fix-foo\n\n";
+        let older = "### Cargo\n\n#### bar 0.2.0\n\n##### Minor changes\n\n- feat-bar\n\n\
+                     ### npm\n\n#### web 1.0.1\n\n##### Patch changes\n\n- fix-web\n";
+        assert_eq!(
+            merge_changelog_sections(newer, older),
+            "### Cargo\n\n#### foo 0.1.1\n\n##### Patch changes\n\n- fix-foo\n\n\
+             #### bar 0.2.0\n\n##### Minor changes\n\n- feat-bar\n\n\
+             ### npm\n\n#### web 1.0.1\n\n##### Patch changes\n\n- fix-web\n\n"
+        );
+    }
+
+    #[test]
+    fn merging_changelog_sections_keeps_code_blocks_whole() {
+        let newer = "### Cargo\n\n- fix-foo:\n\n  ```sh\n  # run once\n  foo migrate\n  ```\n\n";
+        let older = "### Cargo\n\n- feat-bar\n\n~~~\n# Cargo\n~~~\n";
+        assert_eq!(
+            merge_changelog_sections(newer, older),
+            "### Cargo\n\n- fix-foo:\n\n  ```sh\n  # run once\n  foo migrate\n  ```\n\
+             - feat-bar\n\n~~~\n# Cargo\n~~~\n\n"
+        );
+    }
+
     #[test]
     fn preserves_changelog_intro_when_updating() {
         use std::fs;
@@ -2686,7 +3957,7 @@ mod tests {
             ChangelogCategory::Bump(Bump::Minor),
         )];
         update_changelog(
-            crate_dir,
+            &crate_dir.join("CHANGELOG.md"),
             "my-package",
             "1.0.0",
             "1.0.1",
@@ -2719,7 +3990,15 @@ mod tests {
             "Initial release".to_string(),
             ChangelogCategory::Bump(Bump::Major),
         )];
-        update_changelog(crate_dir, "new-package", "0.1.0", "1.0.0", &entries, None).unwrap();
+        update_changelog(
+            &crate_dir.join("CHANGELOG.md"),
+            "new-package",
+            "0.1.0",
+            "1.0.0",
+            &entries,
+            None,
+        )
+        .unwrap();
 
         let updated = fs::read_to_string(crate_dir.join("CHANGELOG.md")).unwrap();
         assert!(updated.starts_with("# new-package\n\n## 1.0.0"));
@@ -2739,6 +4018,40 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn release_commit_message_expands_placeholders() {
+        let releases = [("a", "0.1.0", "0.2.0"), ("b", "1.0.0", "1.0.1")];
+        assert_eq!(
+            render_release_commit_message("chore(release): {releases}", releases),
+            "chore(release): a@0.2.0, b@1.0.1"
+        );
+        assert_eq!(
+            render_release_commit_message("Release\n\n{release_list}\n", releases),
+            "Release\n\n- a 0.1.0 -> 0.2.0\n- b 1.0.0 -> 1.0.1"
+        );
+    }
+
+    #[test]
+    fn extract_changelog_section_picks_the_requested_version() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("CHANGELOG.md");
+        fs::write(
+            &path,
+            "# Changelog\n\n## 1.1.0 — 2024-06-20\n\n- Added a flag\n\n## 1.0.0\n\n- First release\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            extract_changelog_section(&path, None).as_deref(),
+            Some("- Added a flag")
+        );
+        assert_eq!(
+            extract_changelog_section(&path, Some("1.0.0")).as_deref(),
+            Some("- First release")
+        );
+        assert_eq!(extract_changelog_section(&path, Some("2.0.0")), None);
+    }
+
     #[test]
     fn update_changelog_skips_blank_release_date() {
         use std::fs;
@@ -2749,7 +4062,7 @@ mod tests {
         let entries = vec![("Bug fix".to_string(), ChangelogCategory::Bump(Bump::Patch))];
 
         update_changelog(
-            crate_dir,
+            &crate_dir.join("CHANGELOG.md"),
             "blank-date",
             "0.1.0",
             "0.1.1",
@@ -2904,7 +4217,14 @@ mod tests {
         bump_by_pkg.insert("cargo/main-package".to_string(), Bump::Minor);
 
         // Apply dependency cascade
-        apply_dependency_cascade(&mut bump_by_pkg, &dependents, &config, &workspace).unwrap();
+        apply_dependency_cascade(
+            &mut bump_by_pkg,
+            &BTreeMap::new(),
+            &dependents,
+            &config,
+            &workspace,
+        )
+        .unwrap();
 
         // The ignored packages should NOT be added to bump_by_pkg
         assert_eq!(bump_by_pkg.len(), 1);
@@ -2962,6 +4282,53 @@ mod tests {
         assert!(dependents.is_empty());
     }
 
+    #[test]
+    fn calver_version_follows_release_date() {
+        let format = CalverFormat::parse("YYYY.MM.MICRO").unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let next = |v: &str, stabilize: bool, bump_base: bool| {
+            let parsed = parse_version_string(v).unwrap();
+            calver_version_from_parsed(&format, &parsed, date, stabilize, bump_base)
+        };
+
+        // New period resets the counter, same period advances it
+        assert_eq!(next("2026.9.4", false, false).unwrap(), "2026.10.0");
+        assert_eq!(next("2026.10.0", false, false).unwrap(), "2026.10.1");
+        assert_eq!(next("0.3.1", false, false).unwrap(), "2026.10.0");
+
+        // Pre-releases bump their suffix within the period, and restart in a new one
+        assert_eq!(
+            next("2026.10.1-alpha.1", false, false).unwrap(),
+            "2026.10.1-alpha.2"
+        );
+        assert_eq!(
+            next("2026.9.2-alpha.3", false, false).unwrap(),
+            "2026.10.0-alpha"
+        );
+        assert_eq!(
+            next("2026.10.0-alpha", false, true).unwrap(),
+            "2026.10.1-alpha"
+        );
+        assert_eq!(next("2026.10.1-rc.2", true, false).unwrap(), "2026.10.1");
+
+        // Never go backwards
+        let err = next("2026.11.0", false, false).unwrap_err();
+        assert!(err.contains("falls before"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn release_date_uses_configured_timezone() {
+        let cfg = Config {
+            changelog_release_date_timezone: Some("+05:00".to_string()),
+            ..Config::default()
+        };
+        let now = Utc.with_ymd_and_hms(2026, 10, 31, 21, 0, 0).unwrap();
+        assert_eq!(
+            release_date(&cfg, now).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 11, 1).unwrap()
+        );
+    }
+
     #[test]
     fn test_stabilize_version_from_parsed() {
         fn stabilize(v: &str, bump: Bump) -> String {
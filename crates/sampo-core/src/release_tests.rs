@@ -185,6 +185,13 @@ mod tests {
             self
         }
 
+        fn add_raw_changeset(&self, filename: &str, content: &str) -> &Self {
+            let dir = self.root.join(".sampo/changesets");
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join(filename), content).unwrap();
+            self
+        }
+
         fn add_preserved_changeset(
             &self,
             packages: &[&str],
@@ -240,6 +247,30 @@ mod tests {
             run_release(&self.root, dry_run)
         }
 
+        fn run_release_at(
+            &self,
+            dry_run: bool,
+            now: chrono::DateTime<chrono::Utc>,
+        ) -> crate::errors::Result<ReleaseOutput> {
+            crate::release::run_release_with_now(
+                &self.root,
+                dry_run,
+                &ReleaseSelection::default(),
+                now,
+                true,
+            )
+        }
+
+        fn run_release_for(
+            &self,
+            packages: &[&str],
+            dry_run: bool,
+        ) -> crate::errors::Result<ReleaseOutput> {
+            let selection =
+                ReleaseSelection::packages(packages.iter().map(|name| format!("cargo/{}", name)));
+            run_release_with_selection(&self.root, dry_run, &selection)
+        }
+
         fn assert_crate_version(&self, crate_name: &str, expected_version: &str) {
             let crate_dir = self.crates.get(crate_name).expect("crate must exist");
             let manifest = fs::read_to_string(crate_dir.join("Cargo.toml")).unwrap();
@@ -348,6 +379,746 @@ mod tests {
         assert!(output.dry_run);
     }
 
+    #[test]
+    fn package_release_branches_restrict_the_release() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .add_crate("bar", "0.1.0")
+            .set_config("[packages.\"cargo/bar\".git]\nrelease_branches = [\"bar-next\"]\ndefault_branch = \"bar-next\"\n")
+            .add_changeset(&["foo"], Bump::Minor, "feat-foo")
+            .add_changeset(&["bar"], Bump::Minor, "feat-bar");
+
+        {
+            let _guard = EnvVarGuard::set("SAMPO_RELEASE_BRANCH", "main");
+            workspace.run_release(false).unwrap();
+        }
+        workspace.assert_crate_version("foo", "0.2.0");
+        workspace.assert_crate_version("bar", "0.1.0");
+
+        {
+            let _guard = EnvVarGuard::set("SAMPO_RELEASE_BRANCH", "bar-next");
+            workspace.run_release(false).unwrap();
+        }
+        workspace.assert_crate_version("bar", "0.2.0");
+    }
+
+    #[test]
+    fn package_changelog_path_override() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .set_config(
+                "[packages.\"cargo/foo\".changelog]\npath = \"docs/CHANGES.md\"\nshow_release_date = false\n",
+            )
+            .add_changeset(&["foo"], Bump::Patch, "fix-foo");
+
+        workspace.run_release(false).unwrap();
+        let crate_dir = workspace.crates.get("foo").unwrap();
+        let changelog = fs::read_to_string(crate_dir.join("docs/CHANGES.md")).unwrap();
+        assert!(changelog.contains("## 0.1.1\n\n### Patch changes\n\n- fix-foo"));
+        assert!(!crate_dir.join("CHANGELOG.md").exists());
+    }
+
+    #[test]
+    fn version_files_are_rewritten_on_release() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .set_config(
+                "[[packages.version_files]]\npath = \"README.md\"\npackage = \"foo\"\ntemplate = 'foo = \"{major}.{minor}\"'\n",
+            )
+            .add_changeset(&["foo"], Bump::Minor, "feat-foo");
+        let readme = workspace.root.join("README.md");
+        fs::write(&readme, "```toml\nfoo = \"0.1\"\n```\n").unwrap();
+
+        workspace.run_release(true).unwrap();
+        assert_eq!(
+            fs::read_to_string(&readme).unwrap(),
+            "```toml\nfoo = \"0.1\"\n```\n"
+        );
+
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("foo", "0.2.0");
+        assert_eq!(
+            fs::read_to_string(&readme).unwrap(),
+            "```toml\nfoo = \"0.2\"\n```\n"
+        );
+    }
+
+    #[test]
+    fn version_files_skip_manifests_and_changelogs() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .set_config(
+                "[[packages.version_files]]\npath = \"**/*.md\"\npackage = \"foo\"\ntemplate = 'foo@{version}'\n",
+            )
+            .add_changeset(&["foo"], Bump::Minor, "feat-foo");
+        let readme = workspace.root.join("README.md");
+        fs::write(&readme, "Install foo@0.1.0.\n").unwrap();
+        let changelog = workspace.crates.get("foo").unwrap().join("CHANGELOG.md");
+        fs::write(
+            &changelog,
+            "# foo\n\n## 0.1.0\n\n- First release of foo@0.1.0\n",
+        )
+        .unwrap();
+
+        workspace.run_release(false).unwrap();
+        assert_eq!(fs::read_to_string(&readme).unwrap(), "Install foo@0.2.0.\n");
+        let changelog = fs::read_to_string(&changelog).unwrap();
+        assert!(changelog.contains("## 0.2.0"), "{changelog}");
+        assert!(changelog.contains("- feat-foo"), "{changelog}");
+        assert!(
+            changelog.contains("- First release of foo@0.1.0"),
+            "{changelog}"
+        );
+    }
+
+    #[test]
+    fn stale_version_file_fails_the_release() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .set_config(
+                "[[packages.version_files]]\npath = \"install.sh\"\npackage = \"foo\"\nregex = 'FOO_VERSION=(\\S+)'\n",
+            )
+            .add_changeset(&["foo"], Bump::Patch, "fix-foo");
+        fs::write(workspace.root.join("install.sh"), "VERSION=0.1.0\n").unwrap();
+
+        let err = workspace.run_release(true).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("no version of 'foo' found in 'install.sh'"),
+            "{err}"
+        );
+        workspace.assert_crate_version("foo", "0.1.0");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn release_hooks_receive_the_plan() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .set_config(
+                "[hooks]\npre_release = [\"sh\", \"-c\", \"cat > pre.json; echo $SAMPO_DRY_RUN > dry-run.txt\"]\npost_release = [\"sh\", \"-c\", \"grep -c 0.2.0 crates/foo/Cargo.toml > post.txt; exit 0\"]\n",
+            )
+            .add_changeset(&["foo"], Bump::Minor, "feat-foo");
+
+        workspace.run_release(true).unwrap();
+        assert_eq!(
+            fs::read_to_string(workspace.root.join("dry-run.txt")).unwrap(),
+            "true\n"
+        );
+        // The dry-run post_release hook still sees the old manifest.
+        assert_eq!(
+            fs::read_to_string(workspace.root.join("post.txt")).unwrap(),
+            "0\n"
+        );
+
+        workspace.run_release(false).unwrap();
+        let input: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(workspace.root.join("pre.json")).unwrap())
+                .unwrap();
+        assert_eq!(input["hook"], "pre_release");
+        assert_eq!(input["dry_run"], false);
+        assert_eq!(input["packages"][0]["package"], "cargo/foo");
+        assert_eq!(input["packages"][0]["old_version"], "0.1.0");
+        assert_eq!(input["packages"][0]["new_version"], "0.2.0");
+        assert_eq!(
+            fs::read_to_string(workspace.root.join("post.txt")).unwrap(),
+            "1\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn release_plan_skips_hooks() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .set_config("[hooks]\npre_release = [\"false\"]\n")
+            .add_changeset(&["foo"], Bump::Minor, "feat-foo");
+
+        let output = plan_release(&workspace.root, &ReleaseSelection::default()).unwrap();
+        assert_eq!(output.released_packages[0].new_version, "0.2.0");
+        workspace.assert_crate_version("foo", "0.1.0");
+
+        // The dry-run preview still runs them.
+        let err = workspace.run_release(true).unwrap_err();
+        assert!(err.to_string().contains("pre_release hook failed"), "{err}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failing_pre_release_hook_aborts_the_release() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .set_config("[hooks]\npre_release = [\"false\"]\n")
+            .add_changeset(&["foo"], Bump::Patch, "fix-foo");
+
+        let err = workspace.run_release(false).unwrap_err();
+        assert!(err.to_string().contains("pre_release hook failed"), "{err}");
+        workspace.assert_crate_version("foo", "0.1.0");
+        assert!(workspace.root.join(".sampo/changesets/fix-foo.md").exists());
+    }
+
+    #[test]
+    fn cascade_none_bumps_dependents_only_when_constraint_breaks() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .add_crate("bar", "0.1.0")
+            .add_dependency("foo", "bar", "0.1.0")
+            .set_config("[packages]\ncascade = \"none\"\n")
+            .add_changeset(&["bar"], Bump::Patch, "fix-bar");
+
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("bar", "0.1.1");
+        workspace.assert_crate_version("foo", "0.1.0");
+
+        workspace.add_changeset(&["bar"], Bump::Minor, "feat-bar");
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("bar", "0.2.0");
+        workspace.assert_crate_version("foo", "0.1.1");
+        workspace.assert_dependency_version("foo", "bar", "0.2.0");
+    }
+
+    #[test]
+    fn cascade_match_and_group_overrides() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "1.0.0")
+            .add_crate("baz", "1.0.0")
+            .add_crate("bar", "1.0.0")
+            .add_dependency("foo", "bar", "1.0.0")
+            .add_dependency("baz", "bar", "1.0.0")
+            .set_config(
+                "[packages]\ncascade = \"match\"\n\n[[packages.cascade_groups]]\npackages = [\"baz\"]\ncascade = \"patch\"\n",
+            )
+            .add_changeset(&["bar"], Bump::Minor, "feat-bar");
+
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("bar", "1.1.0");
+        workspace.assert_crate_version("foo", "1.1.0");
+        workspace.assert_crate_version("baz", "1.0.1");
+    }
+
+    #[test]
+    fn cascade_can_skip_dev_dependency_edges() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .add_crate("bar", "0.1.0")
+            .set_config("[packages]\ncascade_dev_dependencies = false\n")
+            .add_changeset(&["bar"], Bump::Patch, "fix-bar");
+        let manifest = workspace.crates.get("foo").unwrap().join("Cargo.toml");
+        let mut content = fs::read_to_string(&manifest).unwrap();
+        content.push_str("\n[dev-dependencies]\nbar = { path=\"../bar\", version=\"0.1\" }\n");
+        fs::write(&manifest, content).unwrap();
+
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("bar", "0.1.1");
+        workspace.assert_crate_version("foo", "0.1.0");
+        assert!(
+            fs::read_to_string(&manifest)
+                .unwrap()
+                .contains("version=\"0.1\"")
+        );
+    }
+
+    #[test]
+    fn dev_dependents_are_bumped_when_their_constraint_breaks() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .add_crate("bar", "0.1.0")
+            .set_config("[packages]\ncascade_dev_dependencies = false\n")
+            .add_changeset(&["bar"], Bump::Minor, "feat-bar");
+        let manifest = workspace.crates.get("foo").unwrap().join("Cargo.toml");
+        let mut content = fs::read_to_string(&manifest).unwrap();
+        content.push_str("\n[dev-dependencies]\nbar = { path=\"../bar\", version=\"0.1.0\" }\n");
+        fs::write(&manifest, content).unwrap();
+
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("bar", "0.2.0");
+        workspace.assert_crate_version("foo", "0.1.1");
+
+        // The workspace still resolves: foo's dev-dependency follows bar to 0.2.0.
+        let metadata = std::process::Command::new("cargo")
+            .args(["metadata", "--format-version", "1", "--offline"])
+            .current_dir(&workspace.root)
+            .output()
+            .unwrap();
+        assert!(
+            metadata.status.success(),
+            "{}",
+            String::from_utf8_lossy(&metadata.stderr)
+        );
+        let content = fs::read_to_string(&manifest).unwrap();
+        assert!(content.contains("0.2.0"), "{content}");
+    }
+
+    #[test]
+    fn aggregate_changelog_lists_released_packages() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .add_crate("bar", "0.1.0")
+            .set_config(
+                "[changelog]\naggregate = \"CHANGELOG.md\"\naggregate_version = \"foo\"\nshow_release_date = false\n",
+            )
+            .add_changeset(&["foo"], Bump::Minor, "feat-foo")
+            .add_changeset(&["bar"], Bump::Patch, "fix-bar");
+
+        workspace.run_release(false).unwrap();
+        let aggregate = fs::read_to_string(workspace.root.join("CHANGELOG.md")).unwrap();
+        assert_eq!(
+            aggregate,
+            "# Changelog\n\n## 0.2.0\n\n### bar 0.1.1\n\n#### Patch changes\n\n- fix-bar\n\n### foo 0.2.0\n\n#### Minor changes\n\n- feat-foo\n"
+        );
+    }
+
+    #[test]
+    fn aggregate_changelog_merges_sections_with_the_same_date() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .add_crate("bar", "0.1.0")
+            .set_config(
+                "[changelog]\naggregate = \"docs/RELEASES.md\"\nrelease_date_format = \"today\"\n",
+            )
+            .add_changeset(&["foo"], Bump::Patch, "fix-foo");
+        workspace.run_release(false).unwrap();
+
+        workspace.add_changeset(&["bar"], Bump::Patch, "fix-bar");
+        workspace.run_release(false).unwrap();
+
+        let aggregate = fs::read_to_string(workspace.root.join("docs/RELEASES.md")).unwrap();
+        assert_eq!(aggregate.matches("## today").count(), 1, "{aggregate}");
+        let bar = aggregate.find("### bar 0.1.1").unwrap();
+        let foo = aggregate.find("### foo 0.1.1").unwrap();
+        assert!(bar < foo, "{aggregate}");
+    }
+
+    #[test]
+    fn aggregate_changelog_cannot_be_a_package_changelog() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.1.0")
+            .set_config("[changelog]\naggregate = \"crates/foo/CHANGELOG.md\"\n")
+            .add_changeset(&["foo"], Bump::Patch, "fix-foo");
+
+        let err = workspace.run_release(false).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("changelog.aggregate 'crates/foo/CHANGELOG.md' is the changelog of foo"),
+            "{err}"
+        );
+        workspace.assert_crate_version("foo", "0.1.0");
+    }
+
+    #[test]
+    fn run_release_reports_consumed_changesets() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.1.0");
+        workspace.add_crate("bar", "0.1.0");
+        workspace.add_changeset(&["foo"], Bump::Minor, "feat-foo");
+        workspace.add_changeset(&["bar"], Bump::Patch, "fix-bar");
+
+        let expected = vec![
+            workspace.root.join(".sampo/changesets/feat-foo.md"),
+            workspace.root.join(".sampo/changesets/fix-bar.md"),
+        ];
+
+        let preview = workspace.run_release(true).unwrap();
+        assert_eq!(preview.consumed_changesets, expected);
+
+        let output = workspace.run_release(false).unwrap();
+        assert_eq!(output.consumed_changesets, expected);
+        assert!(expected.iter().all(|path| !path.exists()));
+    }
+
+    #[test]
+    fn selection_consumes_only_covered_changesets() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.1.0");
+        workspace.add_crate("bar", "0.1.0");
+        workspace.add_crate("baz", "0.1.0");
+        workspace.add_changeset(&["foo"], Bump::Minor, "feat-foo");
+        workspace.add_changeset(&["bar"], Bump::Major, "break-bar");
+        workspace.add_changeset(&["foo", "baz"], Bump::Patch, "fix-foo-baz");
+
+        let output = workspace.run_release_for(&["foo"], false).unwrap();
+
+        let released: Vec<&str> = output
+            .released_packages
+            .iter()
+            .map(|pkg| pkg.name.as_str())
+            .collect();
+        assert_eq!(released, vec!["foo"]);
+        workspace.assert_crate_version("foo", "0.2.0");
+        workspace.assert_crate_version("bar", "0.1.0");
+        workspace.assert_crate_version("baz", "0.1.0");
+
+        let changesets = workspace.root.join(".sampo/changesets");
+        assert!(!changesets.join("feat-foo.md").exists());
+        assert!(changesets.join("break-bar.md").exists());
+        assert!(changesets.join("fix-foo-baz.md").exists());
+    }
+
+    #[test]
+    fn selection_keeps_dependency_cascade() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("core", "0.1.0");
+        workspace.add_crate("app", "0.1.0");
+        workspace.add_dependency("app", "core", "0.1.0");
+        workspace.add_changeset(&["core"], Bump::Minor, "feat-core");
+
+        let output = workspace.run_release_for(&["core"], true).unwrap();
+
+        let mut released: Vec<&str> = output
+            .released_packages
+            .iter()
+            .map(|pkg| pkg.name.as_str())
+            .collect();
+        released.sort();
+        assert_eq!(released, vec!["app", "core"]);
+    }
+
+    #[test]
+    fn selection_without_covered_changesets_releases_nothing() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.1.0");
+        workspace.add_crate("bar", "0.1.0");
+        workspace.add_changeset(&["bar"], Bump::Patch, "fix-bar");
+
+        let output = workspace.run_release_for(&["foo"], false).unwrap();
+        assert!(output.released_packages.is_empty());
+        assert!(workspace.root.join(".sampo/changesets/fix-bar.md").exists());
+    }
+
+    #[test]
+    fn selection_excluded_packages_stay_pending() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.1.0");
+        workspace.add_crate("bar", "0.1.0");
+        workspace.add_changeset(&["foo"], Bump::Minor, "feat-foo");
+        workspace.add_changeset(&["bar"], Bump::Patch, "fix-bar");
+
+        let selection = ReleaseSelection {
+            excluded: ["cargo/bar".to_string()].into(),
+            ..ReleaseSelection::default()
+        };
+        let output = run_release_with_selection(&workspace.root, false, &selection).unwrap();
+
+        let released: Vec<&str> = output
+            .released_packages
+            .iter()
+            .map(|pkg| pkg.name.as_str())
+            .collect();
+        assert_eq!(released, vec!["foo"]);
+        workspace.assert_crate_version("bar", "0.1.0");
+        assert!(workspace.root.join(".sampo/changesets/fix-bar.md").exists());
+    }
+
+    #[test]
+    fn selection_splits_mixed_changesets() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.1.0");
+        workspace.add_crate("baz", "0.1.0");
+        workspace.add_changeset(&["foo", "baz"], Bump::Patch, "fix-foo-baz");
+
+        let selection = ReleaseSelection {
+            split_mixed: true,
+            ..ReleaseSelection::packages(["cargo/foo"])
+        };
+        let output = run_release_with_selection(&workspace.root, false, &selection).unwrap();
+
+        let released: Vec<&str> = output
+            .released_packages
+            .iter()
+            .map(|pkg| pkg.name.as_str())
+            .collect();
+        assert_eq!(released, vec!["foo"]);
+        workspace.assert_crate_version("foo", "0.1.1");
+        workspace.assert_crate_version("baz", "0.1.0");
+        workspace.assert_changelog_contains("foo", "fix-foo-baz");
+
+        let changesets_dir = workspace.root.join(".sampo/changesets");
+        assert!(!changesets_dir.join("fix-foo-baz-1.md").exists());
+        let remaining = fs::read_to_string(changesets_dir.join("fix-foo-baz.md")).unwrap();
+        assert!(remaining.contains("baz: patch"));
+        assert!(!remaining.contains("foo: patch"));
+        assert!(remaining.contains("fix-foo-baz"));
+
+        // The remaining entry is released later with the same message
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("baz", "0.1.1");
+        workspace.assert_changelog_contains("baz", "fix-foo-baz");
+    }
+
+    #[test]
+    fn split_changesets_keep_their_commit_attribution() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.1.0");
+        workspace.add_crate("baz", "0.1.0");
+        workspace.set_config("[changelog]\nshow_acknowledgments = false\n");
+        workspace.add_changeset(&["foo", "baz"], Bump::Patch, "fix-foo-baz");
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .arg("-C")
+                .arg(&workspace.root)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {args:?} failed");
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test User"]);
+        git(&["add", "-A"]);
+        git(&["commit", "-qm", "Add changeset"]);
+        let source = git(&["rev-parse", "--short", "HEAD"]);
+
+        let selection = ReleaseSelection {
+            split_mixed: true,
+            ..ReleaseSelection::packages(["cargo/foo"])
+        };
+        run_release_with_selection(&workspace.root, false, &selection).unwrap();
+        git(&["add", "-A"]);
+        git(&["commit", "-qm", "Release foo"]);
+        workspace.run_release(false).unwrap();
+
+        workspace.assert_changelog_contains("foo", &format!("{source} fix-foo-baz"));
+        workspace.assert_changelog_contains("baz", &format!("{source} fix-foo-baz"));
+    }
+
+    #[test]
+    fn explicit_version_sets_target_and_category() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.9.3");
+        workspace.add_raw_changeset("stable.md", "---\nfoo: =1.0.0\n---\n\nfeat: stable API\n");
+
+        let output = workspace.run_release(false).unwrap();
+        assert_eq!(output.released_packages[0].new_version, "1.0.0");
+        workspace.assert_crate_version("foo", "1.0.0");
+        workspace.assert_changelog_contains("foo", "### Major changes\n\n- feat: stable API");
+    }
+
+    #[test]
+    fn explicit_version_must_be_greater_than_current() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "1.2.0");
+        workspace.add_raw_changeset("older.md", "---\nfoo: =1.2.0\n---\n\nfix: nothing\n");
+
+        let err = workspace.run_release(true).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("not greater than the current version"),
+            "unexpected error: {err}"
+        );
+        workspace.assert_crate_version("foo", "1.2.0");
+    }
+
+    #[test]
+    fn explicit_version_aligns_fixed_group() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.4.0");
+        workspace.add_crate("bar", "0.4.0");
+        workspace.set_config("[packages]\nfixed = [[\"foo\", \"bar\"]]\n");
+        workspace.add_raw_changeset("stable.md", "---\nfoo: =1.0.0\n---\n\nfeat: stable API\n");
+
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("foo", "1.0.0");
+        workspace.assert_crate_version("bar", "1.0.0");
+    }
+
+    #[test]
+    fn explicit_version_leaves_fixed_members_on_another_version_to_their_bump() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.4.0");
+        workspace.add_crate("bar", "0.3.2");
+        workspace.set_config("[packages]\nfixed = [[\"foo\", \"bar\"]]\n");
+        workspace.add_raw_changeset("skip.md", "---\nfoo: =0.4.5\n---\n\nfix: skip ahead\n");
+
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("foo", "0.4.5");
+        workspace.assert_crate_version("bar", "0.3.3");
+    }
+
+    #[test]
+    fn explicit_version_cannot_fall_short_of_other_bumps() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "1.2.3");
+        workspace.add_raw_changeset("target.md", "---\nfoo: =1.2.4\n---\n\nfix: small\n");
+        workspace.add_changeset(&["foo"], Bump::Minor, "feat: larger");
+
+        let err = workspace.run_release(true).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("only a patch release, but other changes require a minor bump"),
+            "unexpected error: {err}"
+        );
+        workspace.assert_crate_version("foo", "1.2.3");
+    }
+
+    #[test]
+    fn explicit_versions_must_agree() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.4.0");
+        workspace.add_raw_changeset("one.md", "---\nfoo: =1.0.0\n---\n\nfeat: one\n");
+        workspace.add_raw_changeset("two.md", "---\nfoo: =2.0.0\n---\n\nfeat: two\n");
+
+        let err = workspace.run_release(true).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("conflicting versions for 'cargo/foo'"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn none_changeset_alone_stays_pending() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.1.0");
+        workspace.add_changeset(&["foo"], Bump::None, "docs-foo");
+
+        let output = workspace.run_release(false).unwrap();
+        assert!(output.released_packages.is_empty());
+        workspace.assert_crate_version("foo", "0.1.0");
+        assert!(
+            workspace
+                .root
+                .join(".sampo/changesets/docs-foo.md")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn none_changeset_attaches_to_next_release() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.1.0");
+        workspace.add_changeset(&["foo"], Bump::None, "docs-foo");
+        workspace.add_changeset(&["foo"], Bump::Patch, "fix-foo");
+
+        let output = workspace.run_release(false).unwrap();
+        assert_eq!(output.released_packages.len(), 1);
+        assert_eq!(output.released_packages[0].bump, Bump::Patch);
+        workspace.assert_crate_version("foo", "0.1.1");
+        workspace.assert_changelog_contains("foo", "### Patch changes\n\n- fix-foo");
+        workspace.assert_changelog_contains("foo", "### Notes\n\n- docs-foo");
+        assert!(
+            !workspace
+                .root
+                .join(".sampo/changesets/docs-foo.md")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn none_entry_does_not_cascade() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("core", "0.1.0");
+        workspace.add_crate("app", "0.1.0");
+        workspace.add_crate("other", "0.1.0");
+        workspace.add_dependency("app", "core", "0.1.0");
+        workspace.add_changeset(&["core"], Bump::None, "refactor-core");
+        workspace.add_changeset(&["other"], Bump::Minor, "feat-other");
+
+        let output = workspace.run_release(false).unwrap();
+        let released: Vec<&str> = output
+            .released_packages
+            .iter()
+            .map(|pkg| pkg.name.as_str())
+            .collect();
+        assert_eq!(released, vec!["other"]);
+        workspace.assert_crate_version("core", "0.1.0");
+        workspace.assert_crate_version("app", "0.1.0");
+        assert!(
+            workspace
+                .root
+                .join(".sampo/changesets/refactor-core.md")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn none_entries_of_unreleased_packages_are_kept() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "0.1.0");
+        workspace.add_crate("bar", "0.1.0");
+        let path = workspace.root.join(".sampo/changesets/mixed.md");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            "---\nfoo: minor\nbar: none\n---\n\nfeat: shared change\n",
+        )
+        .unwrap();
+
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("foo", "0.2.0");
+        workspace.assert_crate_version("bar", "0.1.0");
+        workspace.assert_changelog_contains("foo", "feat: shared change");
+
+        let remaining = fs::read_to_string(&path).unwrap();
+        assert!(remaining.contains("bar: none"));
+        assert!(!remaining.contains("foo:"));
+        assert!(remaining.contains("feat: shared change"));
+
+        workspace.add_changeset(&["bar"], Bump::Patch, "fix-bar");
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("bar", "0.1.1");
+        workspace.assert_changelog_contains("bar", "feat: shared change");
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn pending_notes_are_not_duplicated_across_prereleases() {
+        let mut workspace = TestWorkspace::new();
+        workspace.add_crate("foo", "1.0.0-alpha");
+        workspace.add_crate("bar", "1.0.0-alpha");
+        workspace.add_crate("baz", "1.0.0");
+        workspace.add_raw_changeset(
+            "mixed.md",
+            "---\nfoo: minor\nbar: none\nbaz: none\n---\n\nfeat: shared change\n",
+        );
+
+        workspace.run_release(false).unwrap();
+        workspace.add_changeset(&["bar"], Bump::Patch, "fix: bar alpha");
+        workspace.run_release(false).unwrap();
+
+        let preserved_dir = workspace.root.join(".sampo/prerelease");
+        assert!(
+            !preserved_dir.join("mixed-1.md").exists(),
+            "the changeset should stay in one preserved file"
+        );
+        let preserved = fs::read_to_string(preserved_dir.join("mixed.md")).unwrap();
+        assert!(preserved.contains("foo: minor"), "{preserved}");
+        assert!(preserved.contains("bar: none"), "{preserved}");
+        let pending =
+            fs::read_to_string(workspace.root.join(".sampo/changesets/mixed.md")).unwrap();
+        assert!(pending.contains("baz: none"), "{pending}");
+        assert!(!pending.contains("bar"), "{pending}");
+
+        workspace.add_changeset(&["foo"], Bump::Patch, "fix: foo alpha");
+        workspace.run_release(false).unwrap();
+        workspace.add_changeset(&["baz"], Bump::Patch, "fix: baz");
+        workspace.run_release(false).unwrap();
+
+        for name in ["foo", "bar", "baz"] {
+            let changelog = workspace.read_changelog(name);
+            assert_eq!(
+                changelog.matches("shared change").count(),
+                1,
+                "{name}: {changelog}"
+            );
+        }
+    }
+
     #[test]
     fn pre_release_preserves_changesets() {
         let mut workspace = TestWorkspace::new();
@@ -1641,6 +2412,7 @@ tempfile = "3.0"
                 Bump::Minor,
                 None,
             )],
+            target_versions: BTreeMap::new(),
             message: "feat: new feature".to_string(),
             path: PathBuf::from("/test/.sampo/changesets/test.md"),
         }];
@@ -1987,6 +2759,138 @@ bar = { version = "1.0.0", path = "crates/bar" }
         );
     }
 
+    #[test]
+    fn zero_major_minor_stays_on_zero() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.4.2")
+            .add_crate("bar", "0.4.2")
+            .add_crate("baz", "1.2.0")
+            .set_config("[versioning]\nzero_major = \"minor\"\n")
+            .add_changeset(&["foo", "baz"], Bump::Major, "breaking-change")
+            .add_changeset(&["bar"], Bump::Minor, "new-feature");
+
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("foo", "0.5.0");
+        workspace.assert_crate_version("bar", "0.4.3");
+        workspace.assert_crate_version("baz", "2.0.0");
+        workspace.assert_changelog_contains("foo", "### Major changes\n\n- breaking-change");
+    }
+
+    #[test]
+    fn zero_major_package_override() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("foo", "0.4.2")
+            .add_crate("bar", "0.4.2")
+            .set_config(
+                "[versioning]\nzero_major = \"minor\"\n\n[packages.\"cargo/bar\"]\nzero_major = \"major\"\n",
+            )
+            .add_changeset(&["foo", "bar"], Bump::Major, "breaking-change");
+
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("foo", "0.5.0");
+        workspace.assert_crate_version("bar", "1.0.0");
+    }
+
+    #[test]
+    fn zero_major_minor_feature_keeps_caret_range() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("a", "0.4.0")
+            .add_crate("b", "0.4.0")
+            .add_dependency_with_constraint("a", "b", "^0.4")
+            .set_config("[versioning]\nzero_major = \"minor\"\n")
+            .add_changeset(&["b"], Bump::Minor, "feat: b feature");
+
+        workspace.run_release(false).unwrap();
+        workspace.assert_crate_version("b", "0.4.1");
+        workspace.assert_dependency_version("a", "b", "^0.4");
+    }
+
+    #[test]
+    fn zero_major_minor_breaking_change_violates_caret_range() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("a", "0.4.0")
+            .add_crate("b", "0.4.0")
+            .add_dependency_with_constraint("a", "b", "^0.4")
+            .set_config(
+                "[versioning]\nzero_major = \"minor\"\n\n[packages]\nfixed = [[\"cargo/a\", \"cargo/b\"]]\n",
+            )
+            .add_changeset(&["cargo/b"], Bump::Major, "breaking b"); // b -> 0.5.0, outside ^0.4
+
+        let err = workspace.run_release(true).unwrap_err();
+        assert!(
+            err.to_string().contains("^0.4"),
+            "Error should mention the violated range, got: {err}"
+        );
+    }
+
+    #[test]
+    fn zero_major_policy_must_match_within_fixed_group() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("a", "0.4.0")
+            .add_crate("b", "0.4.0")
+            .set_config(
+                "[versioning]\nzero_major = \"minor\"\n\n[packages]\nfixed = [[\"a\", \"b\"]]\n\n[packages.\"cargo/b\"]\nzero_major = \"major\"\n",
+            )
+            .add_changeset(&["a"], Bump::Major, "breaking a");
+
+        let err = workspace.run_release(true).unwrap_err();
+        assert!(
+            err.to_string().contains("zero_major"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn calver_package_uses_release_date() {
+        use chrono::TimeZone;
+
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("app", "2020.1.4")
+            .add_crate("data", "2026.10.3")
+            .add_crate("lib", "0.1.0")
+            .set_config(
+                "[changelog]\nrelease_date_timezone = \"UTC\"\n\n[packages.\"cargo/app\"]\nversioning = \"calver:YYYY.MM.MICRO\"\n\n[packages.\"cargo/data\"]\nversioning = \"calver:YYYY.MM.MICRO\"\n",
+            )
+            .add_changeset(&["app", "data", "lib"], Bump::Major, "breaking-change");
+
+        workspace
+            .run_release_at(
+                false,
+                chrono::Utc
+                    .with_ymd_and_hms(2026, 10, 31, 23, 59, 59)
+                    .unwrap(),
+            )
+            .unwrap();
+        workspace.assert_crate_version("app", "2026.10.0");
+        workspace.assert_crate_version("data", "2026.10.4");
+        workspace.assert_crate_version("lib", "1.0.0");
+        workspace.assert_changelog_contains("app", "### Major changes\n\n- breaking-change");
+    }
+
+    #[test]
+    fn versioning_scheme_must_match_within_fixed_group() {
+        let mut workspace = TestWorkspace::new();
+        workspace
+            .add_crate("a", "2026.10.0")
+            .add_crate("b", "1.0.0")
+            .set_config(
+                "[packages]\nfixed = [[\"a\", \"b\"]]\n\n[packages.\"cargo/a\"]\nversioning = \"calver:YYYY.MM.MICRO\"\n",
+            )
+            .add_changeset(&["b"], Bump::Minor, "feature b");
+
+        let err = workspace.run_release(true).unwrap_err();
+        assert!(
+            err.to_string().contains("same versioning scheme"),
+            "unexpected error: {err}"
+        );
+    }
+
     #[test]
     fn constraint_violation_without_group_allows_release() {
         let mut workspace = TestWorkspace::new();
@@ -7,26 +7,134 @@ use semver::Version;
 pub enum Placeholder {
     Ecosystem,
     PackageName,
+    /// The package name with npm scopes and other separators flattened (`@acme/ui` → `acme-ui`).
+    PackageSlug,
+    /// The package directory relative to the workspace root (`crates/foo`).
+    Path,
     Version,
+    Major,
+    Minor,
+    Patch,
+    /// Pre-release identifiers (`rc.1`), empty for stable versions.
+    Prerelease,
 }
 
 impl Placeholder {
+    const TOKENS: [(&'static str, Placeholder); 9] = [
+        ("ecosystem", Self::Ecosystem),
+        ("package_name", Self::PackageName),
+        ("package_slug", Self::PackageSlug),
+        ("path", Self::Path),
+        ("version", Self::Version),
+        ("major", Self::Major),
+        ("minor", Self::Minor),
+        ("patch", Self::Patch),
+        ("prerelease", Self::Prerelease),
+    ];
+
     fn from_token(token: &str) -> Option<Self> {
-        match token {
-            "ecosystem" => Some(Self::Ecosystem),
-            "package_name" => Some(Self::PackageName),
-            "version" => Some(Self::Version),
-            _ => None,
-        }
+        Self::TOKENS
+            .iter()
+            .find(|(name, _)| *name == token)
+            .map(|(_, placeholder)| *placeholder)
     }
 }
 
+/// `package_name` as `{package_slug}` renders it: a leading `@` is dropped and every
+/// character other than ASCII letters, digits, `.`, `_` and `-` becomes `-`.
+pub fn package_slug(package_name: &str) -> String {
+    package_name
+        .trim_start_matches('@')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Segment {
     Literal(String),
     Placeholder(Placeholder),
 }
 
+/// Characters that separate a placeholder from its neighbours, dropped along with a
+/// placeholder that renders empty.
+const SEPARATORS: [char; 5] = ['-', '/', '.', '_', '+'];
+
+/// `segments` without `placeholder` nor one separator next to it, preferably the one
+/// before: `{path}/v{version}` becomes `v{version}`, and `v{major}.{minor}.{patch}-{prerelease}`
+/// becomes `v{major}.{minor}.{patch}`.
+fn elide(segments: &[Segment], placeholder: Placeholder) -> Vec<Segment> {
+    let mut out: Vec<Segment> = Vec::new();
+    let mut strip_next = false;
+    for segment in segments {
+        match segment {
+            Segment::Placeholder(p) if *p == placeholder => {
+                strip_next = true;
+                if let Some(Segment::Literal(text)) = out.last_mut()
+                    && text.ends_with(SEPARATORS)
+                {
+                    text.pop();
+                    if text.is_empty() {
+                        out.pop();
+                    }
+                    strip_next = false;
+                }
+            }
+            Segment::Literal(text) => {
+                let text = if std::mem::take(&mut strip_next) {
+                    text.strip_prefix(SEPARATORS).unwrap_or(text)
+                } else {
+                    text
+                };
+                match out.last_mut() {
+                    Some(Segment::Literal(previous)) => previous.push_str(text),
+                    _ if text.is_empty() => {}
+                    _ => out.push(Segment::Literal(text.to_string())),
+                }
+            }
+            Segment::Placeholder(_) => {
+                strip_next = false;
+                out.push(segment.clone());
+            }
+        }
+    }
+    out
+}
+
+/// Checks `tag` against the rules of `git check-ref-format` for `refs/tags/<tag>`.
+pub fn validate_tag_name(tag: &str) -> Result<(), String> {
+    let invalid = |reason: &str| Err(format!("'{tag}' is not a valid git tag name: {reason}"));
+    if tag.is_empty() || tag == "@" {
+        return invalid("it is empty");
+    }
+    if let Some(c) = tag.chars().find(|c| {
+        c.is_ascii_control() || matches!(c, ' ' | '~' | '^' | ':' | '?' | '*' | '[' | '\\')
+    }) {
+        return invalid(&format!("it contains {c:?}"));
+    }
+    if tag.contains("..") || tag.contains("@{") {
+        return invalid("it contains '..' or '@{'");
+    }
+    if tag.ends_with('.') {
+        return invalid("it ends with '.'");
+    }
+    for component in tag.split('/') {
+        if component.is_empty() {
+            return invalid("it has an empty path component");
+        }
+        if component.starts_with('.') || component.ends_with(".lock") {
+            return invalid("a path component starts with '.' or ends with '.lock'");
+        }
+    }
+    Ok(())
+}
+
 /// A parsed git tag template (e.g. `"{ecosystem}-{package_name}-v{version}"`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TagTemplate {
@@ -39,11 +147,16 @@ pub struct TagTemplate {
 pub struct TagMatch {
     pub ecosystem: Option<PackageKind>,
     pub package_name: Option<String>,
+    pub package_slug: Option<String>,
+    pub path: Option<String>,
+    /// `{version}`, or the version assembled from `{major}`, `{minor}`, `{patch}` and
+    /// `{prerelease}`.
     pub version: Option<String>,
 }
 
 impl TagTemplate {
-    /// Recognised placeholders: `{ecosystem}`, `{package_name}`, `{version}`.
+    /// Recognised placeholders: `{ecosystem}`, `{package_name}`, `{package_slug}`,
+    /// `{path}`, `{version}`, `{major}`, `{minor}`, `{patch}`, `{prerelease}`.
     /// `{` / `}` are not escapable.
     pub fn parse(input: &str) -> Result<Self, SampoError> {
         let trimmed = input.trim();
@@ -69,7 +182,8 @@ impl TagTemplate {
                     let placeholder = Placeholder::from_token(token).ok_or_else(|| {
                         SampoError::Config(format!(
                             "tag format template '{trimmed}' contains unknown placeholder '{{{token}}}'. \
-                             Allowed placeholders: {{ecosystem}}, {{package_name}}, {{version}}."
+                             Allowed placeholders: {}.",
+                            Placeholder::TOKENS.map(|(name, _)| format!("{{{name}}}")).join(", ")
                         ))
                     })?;
                     if !buffer.is_empty() {
@@ -97,12 +211,17 @@ impl TagTemplate {
             segments.push(Segment::Literal(buffer));
         }
 
-        if !segments
-            .iter()
-            .any(|s| matches!(s, Segment::Placeholder(Placeholder::Version)))
-        {
+        let has = |placeholder: Placeholder| {
+            segments
+                .iter()
+                .any(|s| matches!(s, Segment::Placeholder(p) if *p == placeholder))
+        };
+        let has_components =
+            has(Placeholder::Major) && has(Placeholder::Minor) && has(Placeholder::Patch);
+        if !has(Placeholder::Version) && !has_components {
             return Err(SampoError::Config(format!(
-                "tag format template '{trimmed}' must include the {{version}} placeholder"
+                "tag format template '{trimmed}' must include the {{version}} placeholder, \
+                 or all of {{major}}, {{minor}} and {{patch}}"
             )));
         }
 
@@ -117,10 +236,17 @@ impl TagTemplate {
             }
         }
 
-        Ok(Self {
+        let template = Self {
             raw: trimmed.to_string(),
             segments,
-        })
+        };
+        let sample = template.render_with_path(PackageKind::Cargo, "pkg", "pkg", "1.0.0-rc.1");
+        validate_tag_name(&sample).map_err(|err| {
+            SampoError::Config(format!(
+                "tag format template '{trimmed}' renders invalid tags: {err}"
+            ))
+        })?;
+        Ok(template)
     }
 
     pub fn as_str(&self) -> &str {
@@ -139,32 +265,89 @@ impl TagTemplate {
             .any(|s| matches!(s, Segment::Placeholder(p) if *p == placeholder))
     }
 
+    /// Renders the tag with an empty `{path}`; use [`TagTemplate::render_with_path`] for
+    /// packages of a workspace.
     pub fn render(&self, kind: PackageKind, package_name: &str, version: &str) -> String {
+        self.render_with_path(kind, package_name, "", version)
+    }
+
+    /// `path` is the package directory relative to the workspace root, with `/` separators.
+    /// A placeholder that renders empty, like `{path}` for the root package or
+    /// `{prerelease}` for a stable version, is dropped with the separator next to it.
+    pub fn render_with_path(
+        &self,
+        kind: PackageKind,
+        package_name: &str,
+        path: &str,
+        version: &str,
+    ) -> String {
+        let core = version.split('+').next().unwrap_or_default();
+        let (release, prerelease) = core.split_once('-').unwrap_or((core, ""));
+        let mut components = release.split('.');
+        let (major, minor, patch) = (
+            components.next().unwrap_or_default(),
+            components.next().unwrap_or_default(),
+            components.next().unwrap_or_default(),
+        );
+
+        let path = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>()
+            .join("/");
+        let value = |placeholder: Placeholder| match placeholder {
+            Placeholder::Ecosystem => kind.as_str().to_string(),
+            Placeholder::PackageName => package_name.to_string(),
+            Placeholder::PackageSlug => package_slug(package_name),
+            Placeholder::Path => path.clone(),
+            Placeholder::Version => version.to_string(),
+            Placeholder::Major => major.to_string(),
+            Placeholder::Minor => minor.to_string(),
+            Placeholder::Patch => patch.to_string(),
+            Placeholder::Prerelease => prerelease.to_string(),
+        };
+
+        let mut segments = self.segments.clone();
+        for (_, placeholder) in Placeholder::TOKENS {
+            if self.contains(placeholder) && value(placeholder).is_empty() {
+                segments = elide(&segments, placeholder);
+            }
+        }
+
         let mut out = String::new();
-        for segment in &self.segments {
+        for segment in &segments {
             match segment {
                 Segment::Literal(text) => out.push_str(text),
-                Segment::Placeholder(Placeholder::Ecosystem) => out.push_str(kind.as_str()),
-                Segment::Placeholder(Placeholder::PackageName) => out.push_str(package_name),
-                Segment::Placeholder(Placeholder::Version) => out.push_str(version),
+                Segment::Placeholder(placeholder) => out.push_str(&value(*placeholder)),
             }
         }
         out
     }
 
     /// Returns `None` when the tag doesn't fit the template, the `{ecosystem}`
-    /// capture isn't a known kind, or `{version}` isn't valid semver.
+    /// capture isn't a known kind, or the version isn't valid semver.
+    /// Tags rendered with an empty `{path}` or `{prerelease}` match too.
     pub fn match_tag(&self, tag: &str) -> Option<TagMatch> {
-        let mut state = MatchState::default();
-        if match_segments(&self.segments, tag, &mut state) {
-            Some(TagMatch {
+        let mut variants = vec![self.segments.clone()];
+        for placeholder in [Placeholder::Prerelease, Placeholder::Path] {
+            if self.contains(placeholder) {
+                let elided: Vec<_> = variants
+                    .iter()
+                    .map(|segments| elide(segments, placeholder))
+                    .collect();
+                variants.extend(elided);
+            }
+        }
+        variants.into_iter().find_map(|segments| {
+            let mut state = MatchState::default();
+            match_segments(&segments, tag, &mut state).then_some(TagMatch {
                 ecosystem: state.ecosystem,
                 package_name: state.package_name,
+                package_slug: state.package_slug,
+                path: state.path,
                 version: state.version,
             })
-        } else {
-            None
-        }
+        })
     }
 }
 
@@ -172,12 +355,51 @@ impl TagTemplate {
 struct MatchState {
     ecosystem: Option<PackageKind>,
     package_name: Option<String>,
+    package_slug: Option<String>,
+    path: Option<String>,
     version: Option<String>,
+    major: Option<String>,
+    minor: Option<String>,
+    patch: Option<String>,
+    prerelease: Option<String>,
+}
+
+impl MatchState {
+    /// Reconciles `{version}` with the version components once the whole tag matched.
+    fn resolve_version(&mut self) -> bool {
+        if let Some(version) = &self.version {
+            // Any component alongside `{version}` has to agree with it.
+            let Ok(parsed) = Version::parse(version) else {
+                return false;
+            };
+            let agrees = |component: &Option<String>, expected: String| {
+                component.as_ref().is_none_or(|value| *value == expected)
+            };
+            return agrees(&self.major, parsed.major.to_string())
+                && agrees(&self.minor, parsed.minor.to_string())
+                && agrees(&self.patch, parsed.patch.to_string())
+                && agrees(&self.prerelease, parsed.pre.to_string());
+        }
+        let (Some(major), Some(minor), Some(patch)) = (&self.major, &self.minor, &self.patch)
+        else {
+            return false;
+        };
+        let mut assembled = format!("{major}.{minor}.{patch}");
+        if let Some(pre) = self.prerelease.as_deref().filter(|pre| !pre.is_empty()) {
+            assembled.push('-');
+            assembled.push_str(pre);
+        }
+        if Version::parse(&assembled).is_err() {
+            return false;
+        }
+        self.version = Some(assembled);
+        true
+    }
 }
 
 fn match_segments(segments: &[Segment], input: &str, state: &mut MatchState) -> bool {
     if segments.is_empty() {
-        return input.is_empty();
+        return input.is_empty() && state.resolve_version();
     }
 
     match &segments[0] {
@@ -250,8 +472,17 @@ fn enumerate_literal_positions(input: &str, literal: &str) -> Vec<usize> {
     positions
 }
 
+/// Records `captured` in `slot`, failing when the same placeholder captured something else.
+fn capture_once(slot: &mut Option<String>, captured: &str) -> bool {
+    if slot.as_deref().is_some_and(|existing| existing != captured) {
+        return false;
+    }
+    *slot = Some(captured.to_string());
+    true
+}
+
 fn apply_capture(state: &mut MatchState, placeholder: Placeholder, captured: &str) -> bool {
-    if captured.is_empty() {
+    if captured.is_empty() && placeholder != Placeholder::Prerelease {
         return false;
     }
     match placeholder {
@@ -288,6 +519,20 @@ fn apply_capture(state: &mut MatchState, placeholder: Placeholder, captured: &st
             state.version = Some(captured.to_string());
             true
         }
+        Placeholder::PackageSlug => capture_once(&mut state.package_slug, captured),
+        Placeholder::Path => capture_once(&mut state.path, captured),
+        Placeholder::Major | Placeholder::Minor | Placeholder::Patch => {
+            // Semver forbids leading zeros in numeric components.
+            let numeric = captured.bytes().all(|b| b.is_ascii_digit())
+                && (captured == "0" || !captured.starts_with('0'));
+            let slot = match placeholder {
+                Placeholder::Major => &mut state.major,
+                Placeholder::Minor => &mut state.minor,
+                _ => &mut state.patch,
+            };
+            numeric && capture_once(slot, captured)
+        }
+        Placeholder::Prerelease => capture_once(&mut state.prerelease, captured),
     }
 }
 
@@ -450,4 +695,139 @@ mod tests {
         let m = t.match_tag("release-9.9.9").unwrap();
         assert_eq!(m.version.as_deref(), Some("9.9.9"));
     }
+
+    #[test]
+    fn renders_and_matches_version_components() {
+        let t = template("{package_name}/v{major}.{minor}.{patch}-{prerelease}");
+        assert_eq!(
+            t.render(PackageKind::Cargo, "foo", "1.2.3-rc.1+build.5"),
+            "foo/v1.2.3-rc.1"
+        );
+        let m = t.match_tag("foo/v1.2.3-rc.1").unwrap();
+        assert_eq!(m.package_name.as_deref(), Some("foo"));
+        assert_eq!(m.version.as_deref(), Some("1.2.3-rc.1"));
+
+        let m = t.match_tag("foo/v1.2.3-").unwrap();
+        assert_eq!(m.version.as_deref(), Some("1.2.3"));
+
+        assert!(t.match_tag("foo/v01.2.3-").is_none());
+        assert!(t.match_tag("foo/vx.2.3-").is_none());
+    }
+
+    #[test]
+    fn components_must_agree_with_version() {
+        let t = template("v{major}/{version}");
+        assert_eq!(t.render(PackageKind::Cargo, "foo", "2.0.1"), "v2/2.0.1");
+        let m = t.match_tag("v2/2.0.1").unwrap();
+        assert_eq!(m.version.as_deref(), Some("2.0.1"));
+        assert!(t.match_tag("v1/2.0.1").is_none());
+    }
+
+    #[test]
+    fn rejects_partial_version_components() {
+        let err = TagTemplate::parse("v{major}.{minor}").unwrap_err();
+        assert!(format!("{err}").contains("{patch}"));
+    }
+
+    #[test]
+    fn renders_and_matches_path_and_slug() {
+        let t = template("{path}@{version}");
+        assert_eq!(
+            t.render_with_path(PackageKind::Npm, "@acme/ui", "packages/ui", "1.0.0"),
+            "packages/ui@1.0.0"
+        );
+        let m = t.match_tag("packages/ui@1.0.0").unwrap();
+        assert_eq!(m.path.as_deref(), Some("packages/ui"));
+        assert_eq!(m.version.as_deref(), Some("1.0.0"));
+
+        let t = template("{package_slug}-v{version}");
+        assert_eq!(
+            t.render(PackageKind::Npm, "@acme/ui", "1.0.0"),
+            "acme-ui-v1.0.0"
+        );
+        let m = t.match_tag("acme-ui-v1.0.0").unwrap();
+        assert_eq!(m.package_slug.as_deref(), Some("acme-ui"));
+    }
+
+    #[test]
+    fn drops_empty_path_with_its_separator() {
+        let t = template("{path}/v{version}");
+        assert_eq!(
+            t.render_with_path(PackageKind::Cargo, "root", "", "1.2.0"),
+            "v1.2.0"
+        );
+        assert_eq!(
+            t.render_with_path(PackageKind::Cargo, "foo", "crates//foo/", "1.2.0"),
+            "crates/foo/v1.2.0"
+        );
+        let m = t.match_tag("v1.2.0").unwrap();
+        assert_eq!(m.path, None);
+        assert_eq!(m.version.as_deref(), Some("1.2.0"));
+
+        let t = template("{ecosystem}/{path}/v{version}");
+        assert_eq!(
+            t.render_with_path(PackageKind::Npm, "root", "", "1.2.0"),
+            "npm/v1.2.0"
+        );
+        assert!(t.match_tag("npm/v1.2.0").is_some());
+    }
+
+    #[test]
+    fn drops_empty_prerelease_with_its_separator() {
+        let t = template("{package_name}/v{major}.{minor}.{patch}-{prerelease}");
+        assert_eq!(t.render(PackageKind::Cargo, "foo", "1.2.3"), "foo/v1.2.3");
+        assert_eq!(
+            t.render(PackageKind::Cargo, "foo", "1.2.3-rc.1"),
+            "foo/v1.2.3-rc.1"
+        );
+        let m = t.match_tag("foo/v1.2.3").unwrap();
+        assert_eq!(m.version.as_deref(), Some("1.2.3"));
+    }
+
+    #[test]
+    fn validates_tag_names_like_git() {
+        for valid in ["v1.2.0", "crates/foo/v1.2.0", "npm-@scope/foo-v1.0.0"] {
+            assert!(validate_tag_name(valid).is_ok(), "{valid}");
+        }
+        for invalid in [
+            "",
+            "@",
+            "/v1.0.0",
+            "v1.0.0/",
+            "a//v1",
+            "v1..0",
+            "v1.0.",
+            "a b",
+            "v1~1",
+            "v^1",
+            "a:b",
+            "v?",
+            "v*",
+            "v[1",
+            "a\\b",
+            "x@{1}",
+            ".hidden/v1",
+            "a/v1.lock",
+        ] {
+            assert!(validate_tag_name(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn rejects_templates_rendering_invalid_tags() {
+        for raw in ["/v{version}", "v {version}", "{package_name}..{version}"] {
+            let err = TagTemplate::parse(raw).unwrap_err();
+            assert!(
+                err.to_string().contains("renders invalid tags"),
+                "{raw}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn slugs_package_names() {
+        assert_eq!(package_slug("@acme/ui"), "acme-ui");
+        assert_eq!(package_slug("com.example/my-lib"), "com.example-my-lib");
+        assert_eq!(package_slug("sampo_core"), "sampo_core");
+    }
 }
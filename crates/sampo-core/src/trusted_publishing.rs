@@ -0,0 +1,427 @@
+//! Trusted publishing: exchanging the CI's OIDC identity token for a short-lived
+//! registry token, so no long-lived publish secret has to be stored.
+
+use crate::USER_AGENT;
+use crate::adapters::{classify_request_error, classify_response_status};
+use crate::errors::{Result, SampoError};
+use reqwest::blocking::{Client, Response};
+use serde_json::{Value, json};
+use std::fmt;
+use std::time::Duration;
+
+/// Set by GitHub Actions in jobs granted `permissions: id-token: write`.
+pub const OIDC_REQUEST_URL_ENV: &str = "ACTIONS_ID_TOKEN_REQUEST_URL";
+/// Bearer token authorizing requests to [`OIDC_REQUEST_URL_ENV`].
+pub const OIDC_REQUEST_TOKEN_ENV: &str = "ACTIONS_ID_TOKEN_REQUEST_TOKEN";
+
+/// Registries that mint publish tokens for a trusted OIDC identity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustedRegistry {
+    CratesIo,
+    PyPI,
+}
+
+impl TrustedRegistry {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::CratesIo => "crates.io",
+            Self::PyPI => "PyPI",
+        }
+    }
+
+    fn base_url(self) -> &'static str {
+        match self {
+            Self::CratesIo => "https://crates.io",
+            Self::PyPI => "https://pypi.org",
+        }
+    }
+}
+
+/// The OIDC identity provider of the running CI job.
+#[derive(Clone, PartialEq, Eq)]
+pub struct OidcProvider {
+    request_url: String,
+    request_token: String,
+}
+
+impl fmt::Debug for OidcProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OidcProvider")
+            .field("request_url", &self.request_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OidcProvider {
+    pub fn new(request_url: impl Into<String>, request_token: impl Into<String>) -> Self {
+        Self {
+            request_url: request_url.into(),
+            request_token: request_token.into(),
+        }
+    }
+
+    /// The provider GitHub Actions exposes, if the job may request identity tokens.
+    pub fn from_env() -> Option<Self> {
+        let request_url = std::env::var(OIDC_REQUEST_URL_ENV).ok()?;
+        let request_token = std::env::var(OIDC_REQUEST_TOKEN_ENV).ok()?;
+        if request_url.trim().is_empty() || request_token.trim().is_empty() {
+            return None;
+        }
+        Some(Self::new(request_url, request_token))
+    }
+
+    /// Request an identity token (a JWT) for `audience`.
+    pub fn id_token(&self, audience: &str) -> Result<String> {
+        let mut url = reqwest::Url::parse(&self.request_url).map_err(|e| {
+            SampoError::Publish(format!(
+                "trusted publishing: invalid OIDC request URL '{}': {e}",
+                self.request_url
+            ))
+        })?;
+        url.query_pairs_mut().append_pair("audience", audience);
+        let response = client()?
+            .get(url)
+            .bearer_auth(&self.request_token)
+            .send()
+            .map_err(|e| request_failed(&e, "OIDC token request"))?;
+        let body = read_json(response, "OIDC token request")?;
+        string_field(&body, "value", "OIDC token request")
+    }
+}
+
+/// A short-lived publish token minted by a [`TrustedRegistry`].
+pub struct MintedToken {
+    registry: TrustedRegistry,
+    token: String,
+    base_url: String,
+}
+
+impl fmt::Debug for MintedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MintedToken")
+            .field("registry", &self.registry)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MintedToken {
+    pub fn registry(&self) -> TrustedRegistry {
+        self.registry
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Revoke the token once it is no longer needed. Crates.io tokens live for
+    /// half an hour otherwise; PyPI offers no revocation. Failures only warn:
+    /// the token expires on its own.
+    pub fn revoke(&self) {
+        if self.registry != TrustedRegistry::CratesIo {
+            return;
+        }
+        let url = format!("{}/api/v1/trusted_publishing/tokens", self.base_url);
+        let result = client().and_then(|client| {
+            client
+                .delete(&url)
+                .bearer_auth(&self.token)
+                .send()
+                .map_err(|e| request_failed(&e, "token revocation"))
+        });
+        match result {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => eprintln!(
+                "Warning: could not revoke the crates.io publish token: {}",
+                response.status()
+            ),
+            Err(e) => eprintln!("Warning: could not revoke the crates.io publish token: {e}"),
+        }
+    }
+}
+
+/// Exchange an identity token from `oidc` for a publish token of `registry`.
+pub fn mint_token(registry: TrustedRegistry, oidc: &OidcProvider) -> Result<MintedToken> {
+    mint_token_at(registry, oidc, registry.base_url())
+}
+
+/// [`mint_token`] against the registry served at `base_url`.
+pub(crate) fn mint_token_at(
+    registry: TrustedRegistry,
+    oidc: &OidcProvider,
+    base_url: &str,
+) -> Result<MintedToken> {
+    let base_url = base_url.trim_end_matches('/');
+    let client = client()?;
+    let token = match registry {
+        TrustedRegistry::CratesIo => {
+            let jwt = oidc.id_token("crates.io")?;
+            let response = client
+                .post(format!("{base_url}/api/v1/trusted_publishing/tokens"))
+                .json(&json!({ "jwt": jwt }))
+                .send()
+                .map_err(|e| request_failed(&e, "crates.io token exchange"))?;
+            let body = read_json(response, "crates.io token exchange")?;
+            string_field(&body, "token", "crates.io token exchange")?
+        }
+        TrustedRegistry::PyPI => {
+            let response = client
+                .get(format!("{base_url}/_/oidc/audience"))
+                .send()
+                .map_err(|e| request_failed(&e, "PyPI audience request"))?;
+            let body = read_json(response, "PyPI audience request")?;
+            let audience = string_field(&body, "audience", "PyPI audience request")?;
+            let jwt = oidc.id_token(&audience)?;
+            let response = client
+                .post(format!("{base_url}/_/oidc/mint-token"))
+                .json(&json!({ "token": jwt }))
+                .send()
+                .map_err(|e| request_failed(&e, "PyPI token exchange"))?;
+            let body = read_json(response, "PyPI token exchange")?;
+            string_field(&body, "token", "PyPI token exchange")?
+        }
+    };
+    Ok(MintedToken {
+        registry,
+        token,
+        base_url: base_url.to_string(),
+    })
+}
+
+fn client() -> Result<Client> {
+    Client::builder()
+        .timeout(Duration::from_secs(30))
+        .user_agent(USER_AGENT)
+        .build()
+        .map_err(|e| SampoError::Publish(format!("failed to build HTTP client: {}", e)))
+}
+
+fn request_failed(error: &reqwest::Error, what: &str) -> SampoError {
+    classify_request_error(
+        error,
+        SampoError::Publish(format!("trusted publishing: {what} failed: {error}")),
+    )
+}
+
+/// The JSON body of a successful response; the status and a snippet of the body otherwise.
+fn read_json(response: Response, what: &str) -> Result<Value> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().unwrap_or_default();
+        let snippet: String = body.trim().chars().take(500).collect();
+        let snippet = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+        let body_part = if snippet.is_empty() {
+            String::new()
+        } else {
+            format!(": {}", snippet)
+        };
+        return Err(classify_response_status(
+            status,
+            SampoError::Publish(format!(
+                "trusted publishing: {what} returned {status}{body_part}"
+            )),
+        ));
+    }
+    response.json().map_err(|e| {
+        SampoError::Publish(format!(
+            "trusted publishing: {what} returned invalid JSON: {e}"
+        ))
+    })
+}
+
+fn string_field(body: &Value, field: &str, what: &str) -> Result<String> {
+    body.get(field)
+        .and_then(Value::as_str)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            SampoError::Publish(format!(
+                "trusted publishing: {what} response has no '{field}'"
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// A request received by [`StubServer`]: request line, authorization header and body.
+    #[derive(Debug, Clone)]
+    struct Received {
+        line: String,
+        authorization: Option<String>,
+        body: String,
+    }
+
+    /// Answers each request with the first route whose prefix matches its request line.
+    struct StubServer {
+        base_url: String,
+        received: Arc<Mutex<Vec<Received>>>,
+    }
+
+    impl StubServer {
+        fn serve(routes: Vec<(&'static str, u16, &'static str)>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            let received = Arc::new(Mutex::new(Vec::new()));
+            let log = Arc::clone(&received);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { continue };
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let mut content_length = 0;
+                    let mut authorization = None;
+                    loop {
+                        let mut header = String::new();
+                        reader.read_line(&mut header).unwrap();
+                        let header = header.trim_end();
+                        if header.is_empty() {
+                            break;
+                        }
+                        let (name, value) = header.split_once(':').unwrap();
+                        match name.to_ascii_lowercase().as_str() {
+                            "content-length" => content_length = value.trim().parse().unwrap(),
+                            "authorization" => authorization = Some(value.trim().to_string()),
+                            _ => {}
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    reader.read_exact(&mut body).unwrap();
+                    let line = line.trim_end().to_string();
+                    let (status, response) = routes
+                        .iter()
+                        .find(|(prefix, _, _)| line.starts_with(prefix))
+                        .map(|(_, status, response)| (*status, *response))
+                        .unwrap_or((404, ""));
+                    log.lock().unwrap().push(Received {
+                        line,
+                        authorization,
+                        body: String::from_utf8(body).unwrap(),
+                    });
+                    let reply = format!(
+                        "HTTP/1.1 {status} Stub\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response}",
+                        response.len()
+                    );
+                    let _ = stream.write_all(reply.as_bytes());
+                }
+            });
+            Self { base_url, received }
+        }
+
+        fn received(&self) -> Vec<Received> {
+            self.received.lock().unwrap().clone()
+        }
+    }
+
+    fn oidc(server: &StubServer) -> OidcProvider {
+        OidcProvider::new(
+            format!("{}/oidc/token?api-version=2.0", server.base_url),
+            "runtime-token",
+        )
+    }
+
+    #[test]
+    fn mints_a_crates_io_token_from_the_oidc_identity() {
+        let server = StubServer::serve(vec![
+            ("GET /oidc/token", 200, r#"{"value":"jwt-for-crates"}"#),
+            (
+                "POST /api/v1/trusted_publishing/tokens",
+                200,
+                r#"{"token":"cio-short-lived"}"#,
+            ),
+            ("DELETE /api/v1/trusted_publishing/tokens", 204, ""),
+        ]);
+
+        let token = mint_token_at(TrustedRegistry::CratesIo, &oidc(&server), &server.base_url)
+            .expect("token exchange succeeds");
+        assert_eq!(token.token(), "cio-short-lived");
+        token.revoke();
+
+        let received = server.received();
+        assert_eq!(received.len(), 3);
+        assert!(
+            received[0]
+                .line
+                .contains("api-version=2.0&audience=crates.io")
+        );
+        assert_eq!(
+            received[0].authorization.as_deref(),
+            Some("Bearer runtime-token")
+        );
+        let exchange: Value = serde_json::from_str(&received[1].body).unwrap();
+        assert_eq!(exchange, json!({ "jwt": "jwt-for-crates" }));
+        assert!(received[2].line.starts_with("DELETE "));
+        assert_eq!(
+            received[2].authorization.as_deref(),
+            Some("Bearer cio-short-lived")
+        );
+    }
+
+    #[test]
+    fn mints_a_pypi_token_for_the_advertised_audience() {
+        let server = StubServer::serve(vec![
+            ("GET /_/oidc/audience", 200, r#"{"audience":"testpypi"}"#),
+            ("GET /oidc/token", 200, r#"{"value":"jwt-for-pypi"}"#),
+            (
+                "POST /_/oidc/mint-token",
+                200,
+                r#"{"success":true,"token":"pypi-short-lived"}"#,
+            ),
+        ]);
+
+        let token = mint_token_at(TrustedRegistry::PyPI, &oidc(&server), &server.base_url)
+            .expect("token exchange succeeds");
+        assert_eq!(token.token(), "pypi-short-lived");
+        // PyPI tokens cannot be revoked, so this sends nothing.
+        token.revoke();
+
+        let received = server.received();
+        assert_eq!(received.len(), 3);
+        assert!(received[1].line.contains("audience=testpypi"));
+        let exchange: Value = serde_json::from_str(&received[2].body).unwrap();
+        assert_eq!(exchange, json!({ "token": "jwt-for-pypi" }));
+    }
+
+    #[test]
+    fn rejected_exchanges_fail_and_outages_are_retryable() {
+        let server = StubServer::serve(vec![
+            ("GET /oidc/token", 200, r#"{"value":"jwt"}"#),
+            (
+                "POST /api/v1/trusted_publishing/tokens",
+                400,
+                r#"{"errors":[{"detail":"No matching Trusted Publishing config found"}]}"#,
+            ),
+            ("POST /_/oidc/mint-token", 503, ""),
+            ("GET /_/oidc/audience", 200, r#"{"audience":"pypi"}"#),
+        ]);
+
+        let err =
+            mint_token_at(TrustedRegistry::CratesIo, &oidc(&server), &server.base_url).unwrap_err();
+        assert!(!err.is_retryable());
+        assert!(
+            err.to_string()
+                .contains("No matching Trusted Publishing config found")
+        );
+
+        let err =
+            mint_token_at(TrustedRegistry::PyPI, &oidc(&server), &server.base_url).unwrap_err();
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn debug_output_hides_secrets() {
+        let provider = OidcProvider::new("https://oidc.example/token", "runtime-secret");
+        assert!(!format!("{provider:?}").contains("runtime-secret"));
+        let token = MintedToken {
+            registry: TrustedRegistry::PyPI,
+            token: "publish-secret".to_string(),
+            base_url: "https://pypi.org".to_string(),
+        };
+        assert!(!format!("{token:?}").contains("publish-secret"));
+    }
+}
@@ -1,4 +1,5 @@
-use std::collections::BTreeSet;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
 use std::str::FromStr;
 
@@ -47,6 +48,11 @@ impl PackageKind {
         }
     }
 
+    /// Whether packages of this ecosystem can list several `registries` to publish to.
+    pub fn supports_registries(&self) -> bool {
+        matches!(self, Self::Cargo | Self::Npm)
+    }
+
     /// Parse a kind from a case-insensitive string.
     pub fn parse(value: &str) -> Option<Self> {
         match value.to_ascii_lowercase().as_str() {
@@ -156,17 +162,101 @@ pub struct ReleasedPackage {
 pub struct ReleaseOutput {
     /// Packages that were released
     pub released_packages: Vec<ReleasedPackage>,
+    /// Changeset files consumed (or that would be consumed, in dry-run) by the release
+    pub consumed_changesets: Vec<PathBuf>,
     /// Whether this was a dry-run (no files modified)
     pub dry_run: bool,
 }
 
-/// Output information from a publish operation
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Output information from a publish operation, serialized as the publish report
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
 pub struct PublishOutput {
     /// Tags that were created (non-dry-run) or would be created (dry-run)
     pub tags: Vec<String>,
+    /// The `git.floating_tags` among `tags`: they move between releases, so they must be
+    /// pushed with `--force`
+    pub floating_tags: Vec<String>,
     /// Whether this was a dry-run (no packages actually published)
     pub dry_run: bool,
+    /// Transient failures that were retried, in order
+    pub retries: Vec<PublishRetry>,
+    /// Provenance statements written for the published packages, keyed by release tag
+    pub provenance: BTreeMap<String, PathBuf>,
+    /// Status of each registry of the packages published to several `registries`
+    pub registries: Vec<RegistryPublish>,
+}
+
+/// What `sampo publish` found or did on one registry of a package with several `registries`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RegistryPublish {
+    /// Canonical identifier of the package (`cargo/foo`)
+    pub package: String,
+    /// The registry, as named in `registries`
+    pub registry: String,
+    #[serde(flatten)]
+    pub status: RegistryStatus,
+}
+
+/// Serialized as a `status` field, next to an `error` field for [`RegistryStatus::Failed`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "kebab-case")]
+pub enum RegistryStatus {
+    /// The registry already served the version
+    AlreadyPublished,
+    /// The version was published
+    Published,
+    /// The dry run of the publish command succeeded
+    DryRun,
+    /// The publish was skipped in a dry run, the ecosystem being unable to simulate it
+    Skipped,
+    /// The registry rejected the version, or it could not be published there
+    Failed { error: String },
+}
+
+impl RegistryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AlreadyPublished => "already published",
+            Self::Published => "published",
+            Self::DryRun => "dry run",
+            Self::Skipped => "skipped",
+            Self::Failed { .. } => "failed",
+        }
+    }
+}
+
+/// A registry lookup or publish that failed for a transient reason and was retried
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PublishRetry {
+    /// Canonical identifier of the package (`cargo/foo`)
+    pub package: String,
+    pub operation: RetriedOperation,
+    /// Number of the retry, starting at 1
+    pub attempt: u32,
+    /// The failure that caused the retry
+    pub error: String,
+}
+
+/// The step of `sampo publish` a [`PublishRetry`] repeated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RetriedOperation {
+    /// Checking whether the version is already on the registry
+    RegistryCheck,
+    /// Running the ecosystem's publish command
+    Publish,
+    /// Exchanging the CI's OIDC token for a trusted publishing token
+    TokenExchange,
+}
+
+impl RetriedOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RegistryCheck => "registry check",
+            Self::Publish => "publish",
+            Self::TokenExchange => "token exchange",
+        }
+    }
 }
 
 /// Information about a package in the workspace
@@ -199,6 +289,19 @@ impl PackageInfo {
     pub fn dependency_identifier(kind: PackageKind, name: &str) -> String {
         format!("{}/{}", kind.as_str(), name)
     }
+
+    /// Package URL (purl) of this version, following each ecosystem's purl conventions.
+    pub fn package_url(&self) -> String {
+        let (purl_type, name) = match self.kind {
+            PackageKind::Cargo => ("cargo", self.name.clone()),
+            PackageKind::Npm => ("npm", self.name.replacen('@', "%40", 1)),
+            PackageKind::Hex => ("hex", self.name.clone()),
+            PackageKind::PyPI => ("pypi", self.name.to_ascii_lowercase().replace('_', "-")),
+            PackageKind::Packagist => ("composer", self.name.clone()),
+            PackageKind::Maven => ("maven", self.name.replacen(':', "/", 1)),
+        };
+        format!("pkg:{}/{}@{}", purl_type, name, self.version)
+    }
 }
 
 /// Represents a workspace with its package members
@@ -334,6 +437,8 @@ pub fn strip_wrapping_quotes(value: &str) -> &str {
 /// Semantic version bump types, ordered by impact
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Bump {
+    /// Changelog-only entry: attached to the package's next release, never bumps by itself
+    None,
     Patch,
     Minor,
     Major,
@@ -344,11 +449,12 @@ impl FromStr for Bump {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
+            "none" => Ok(Self::None),
             "patch" => Ok(Self::Patch),
             "minor" => Ok(Self::Minor),
             "major" => Ok(Self::Major),
             _ => Err(format!(
-                "invalid bump level '{}': expected patch, minor, or major",
+                "invalid bump level '{}': expected none, patch, minor, or major",
                 s
             )),
         }
@@ -364,6 +470,7 @@ impl Bump {
     /// Convert bump to string
     pub fn as_str(&self) -> &'static str {
         match self {
+            Self::None => "none",
             Self::Patch => "patch",
             Self::Minor => "minor",
             Self::Major => "major",
@@ -385,6 +492,9 @@ impl TryFrom<changesets::ChangeType> for Bump {
             changesets::ChangeType::Patch => Ok(Self::Patch),
             changesets::ChangeType::Minor => Ok(Self::Minor),
             changesets::ChangeType::Major => Ok(Self::Major),
+            changesets::ChangeType::Custom(custom) if custom.eq_ignore_ascii_case("none") => {
+                Ok(Self::None)
+            }
             changesets::ChangeType::Custom(_) => Err(()),
         }
     }
@@ -418,19 +528,22 @@ impl ChangelogCategory {
                 Bump::Major => "Major changes".to_string(),
                 Bump::Minor => "Minor changes".to_string(),
                 Bump::Patch => "Patch changes".to_string(),
+                Bump::None => "Notes".to_string(),
             },
             Self::Tag(tag) => tag.clone(),
         }
     }
 
     /// Returns a sort key for ordering categories.
-    /// Tags are sorted alphabetically first, then bump types by severity (Major, Minor, Patch).
+    /// Tags are sorted alphabetically first, then bump types by severity (Major, Minor, Patch),
+    /// and changelog-only notes last.
     pub fn sort_key(&self) -> (u8, String) {
         match self {
             Self::Tag(tag) => (0, tag.to_lowercase()),
             Self::Bump(Bump::Major) => (1, String::new()),
             Self::Bump(Bump::Minor) => (2, String::new()),
             Self::Bump(Bump::Patch) => (3, String::new()),
+            Self::Bump(Bump::None) => (4, String::new()),
         }
     }
 }
@@ -537,10 +650,15 @@ impl std::fmt::Display for ConstraintViolation {
 /// Parses formats like:
 /// - `minor` -> bump=Minor, tag=None
 /// - `minor (Added)` -> bump=Minor, tag=Some("Added")
+/// - `=1.0.0` -> version=Some("1.0.0")
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParsedChangeType {
+    /// For explicit versions, a placeholder until the release infers the actual level
+    /// from the current version.
     pub bump: Bump,
     pub tag: Option<String>,
+    /// Exact next version requested with the `=<version>` form.
+    pub version: Option<String>,
 }
 
 impl ParsedChangeType {
@@ -548,6 +666,8 @@ impl ParsedChangeType {
     ///
     /// Supports formats:
     /// - `patch`, `minor`, `major` - standard semver bumps
+    /// - `none` - changelog-only entry that never bumps the version by itself
+    /// - `=1.0.0` - explicit next version
     /// - `minor (Added)` - semver bump with custom tag for changelog categorization
     ///
     /// The tag must be enclosed in parentheses at the end of the string.
@@ -564,9 +684,9 @@ impl ParsedChangeType {
             let bump_part = trimmed[..paren_start].trim();
             let tag_part = trimmed[paren_start + 1..paren_end].trim();
 
-            let bump = Bump::parse(bump_part).ok_or_else(|| {
+            let (bump, version) = Self::parse_level(bump_part).ok_or_else(|| {
                 format!(
-                    "Invalid bump level '{}'. Expected 'patch', 'minor', or 'major'.",
+                    "Invalid bump level '{}'. Expected 'none', 'patch', 'minor', 'major', or '=<version>'.",
                     bump_part
                 )
             })?;
@@ -590,6 +710,7 @@ impl ParsedChangeType {
                         return Ok(Self {
                             bump,
                             tag: Some(tag.clone()),
+                            version,
                         });
                     }
                     None => {
@@ -603,14 +724,30 @@ impl ParsedChangeType {
         }
 
         // Standard bump format without tag
-        let bump = Bump::parse(trimmed).ok_or_else(|| {
+        let (bump, version) = Self::parse_level(trimmed).ok_or_else(|| {
             format!(
-                "Invalid change type '{}'. Expected 'patch', 'minor', 'major', or 'bump (Tag)' format.",
+                "Invalid change type '{}'. Expected 'none', 'patch', 'minor', 'major', '=<version>', or 'bump (Tag)' format.",
                 trimmed
             )
         })?;
 
-        Ok(Self { bump, tag: None })
+        Ok(Self {
+            bump,
+            tag: None,
+            version,
+        })
+    }
+
+    /// Parse a bump level or an explicit `=<version>`.
+    fn parse_level(input: &str) -> Option<(Bump, Option<String>)> {
+        match input.strip_prefix('=') {
+            Some(version) => {
+                let version = version.trim();
+                semver::Version::parse(version).ok()?;
+                Some((Bump::Patch, Some(version.to_string())))
+            }
+            None => Bump::parse(input).map(|bump| (bump, None)),
+        }
     }
 
     /// Convert to a ChangelogCategory based on whether a tag is present.
@@ -690,6 +827,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn package_urls_follow_ecosystem_conventions() {
+        assert_eq!(make_package("app").package_url(), "pkg:cargo/app@0.1.0");
+
+        let mut scoped = make_package("@acme/ui");
+        scoped.kind = PackageKind::Npm;
+        assert_eq!(scoped.package_url(), "pkg:npm/%40acme/ui@0.1.0");
+
+        let mut python = make_package("Foo_Bar");
+        python.kind = PackageKind::PyPI;
+        assert_eq!(python.package_url(), "pkg:pypi/foo-bar@0.1.0");
+    }
+
     #[test]
     fn parsed_change_type_simple_bump() {
         let result = ParsedChangeType::parse("minor", &[]).unwrap();
@@ -697,6 +847,27 @@ mod tests {
         assert_eq!(result.tag, None);
     }
 
+    #[test]
+    fn parsed_change_type_none() {
+        let result = ParsedChangeType::parse("none", &[]).unwrap();
+        assert_eq!(result.bump, Bump::None);
+        assert_eq!(result.to_category().heading(), "Notes");
+        assert!(Bump::None < Bump::Patch);
+    }
+
+    #[test]
+    fn parsed_change_type_explicit_version() {
+        let result = ParsedChangeType::parse("=1.0.0", &[]).unwrap();
+        assert_eq!(result.version.as_deref(), Some("1.0.0"));
+
+        let allowed = vec!["Added".to_string()];
+        let result = ParsedChangeType::parse("=2.0.0-rc.1 (Added)", &allowed).unwrap();
+        assert_eq!(result.version.as_deref(), Some("2.0.0-rc.1"));
+        assert_eq!(result.tag, Some("Added".to_string()));
+
+        assert!(ParsedChangeType::parse("=abc", &[]).is_err());
+    }
+
     #[test]
     fn parsed_change_type_with_tag() {
         let allowed = vec!["Added".to_string()];
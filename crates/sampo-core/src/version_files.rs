@@ -0,0 +1,343 @@
+use crate::errors::{SampoError, io_error_with_path};
+use regex::Regex;
+use semver::Version;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Placeholders a version file template can use.
+const PLACEHOLDERS: &[&str] = &["version", "major", "minor", "patch"];
+
+/// A `[[packages.version_files]]` entry: files outside the manifest that carry a
+/// package's version (READMEs, install scripts, constants...).
+///
+/// With only a `template`, every occurrence of the template rendered with the current
+/// version is replaced by the template rendered with the new version. With a `regex`,
+/// its first capture group is replaced by the rendered template (`{version}` by
+/// default), which selects the places to update more finely.
+#[derive(Debug, Clone)]
+pub struct VersionFile {
+    /// Glob relative to the repository root.
+    pub path: String,
+    /// Package reference whose version is written (e.g. `sampo` or `cargo/sampo`).
+    pub package: String,
+    pub template: Option<String>,
+    pub regex: Option<Regex>,
+}
+
+impl VersionFile {
+    pub(crate) fn parse(value: &toml::Value, index: usize) -> Result<Self, SampoError> {
+        let key = format!("packages.version_files[{index}]");
+        let table = value
+            .as_table()
+            .ok_or_else(|| SampoError::Config(format!("{key} must be a table")))?;
+        let string = |name: &str| {
+            table
+                .get(name)
+                .map(|v| {
+                    v.as_str()
+                        .filter(|s| !s.trim().is_empty())
+                        .map(|s| s.to_string())
+                        .ok_or_else(|| {
+                            SampoError::Config(format!("{key}.{name} must be a non-empty string"))
+                        })
+                })
+                .transpose()
+        };
+
+        let path =
+            string("path")?.ok_or_else(|| SampoError::Config(format!("{key}.path is required")))?;
+        let package = string("package")?
+            .ok_or_else(|| SampoError::Config(format!("{key}.package is required")))?;
+        let template = string("template")?;
+        if let Some(template) = &template {
+            validate_template(template, &key)?;
+        }
+        let regex = string("regex")?
+            .map(|raw| {
+                let regex = Regex::new(&raw).map_err(|err| {
+                    SampoError::Config(format!("{key}.regex is not a valid regex: {err}"))
+                })?;
+                if regex.captures_len() < 2 {
+                    return Err(SampoError::Config(format!(
+                        "{key}.regex must have a capture group around the version"
+                    )));
+                }
+                Ok(regex)
+            })
+            .transpose()?;
+        if template.is_none() && regex.is_none() {
+            return Err(SampoError::Config(format!(
+                "{key} needs a template, a regex, or both"
+            )));
+        }
+
+        Ok(Self {
+            path: path.trim().to_string(),
+            package: package.trim().to_string(),
+            template,
+            regex,
+        })
+    }
+
+    /// Rewrite `content` from `old` to `new`, returning the result and how many places
+    /// matched.
+    fn rewrite(&self, content: &str, old: &Version, new: &Version) -> (String, usize) {
+        let replacement = render(self.template.as_deref().unwrap_or("{version}"), new);
+        match &self.regex {
+            Some(regex) => {
+                let mut matches = 0;
+                let updated = regex.replace_all(content, |caps: &regex::Captures| {
+                    matches += 1;
+                    let whole = caps.get(0).expect("group 0 always matches");
+                    match caps.get(1) {
+                        Some(group) => format!(
+                            "{}{}{}",
+                            &content[whole.start()..group.start()],
+                            replacement,
+                            &content[group.end()..whole.end()]
+                        ),
+                        None => whole.as_str().to_string(),
+                    }
+                });
+                (updated.into_owned(), matches)
+            }
+            None => {
+                let current = render(self.template.as_deref().unwrap_or("{version}"), old);
+                let matches = content.matches(&current).count();
+                (content.replace(&current, &replacement), matches)
+            }
+        }
+    }
+}
+
+fn validate_template(template: &str, key: &str) -> Result<(), SampoError> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            return Err(SampoError::Config(format!(
+                "{key}.template has an unclosed '{{'"
+            )));
+        };
+        let name = &rest[start + 1..start + end];
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(SampoError::Config(format!(
+                "{key}.template uses unknown placeholder '{{{name}}}'. Supported: {{version}}, {{major}}, {{minor}}, {{patch}}."
+            )));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    Ok(())
+}
+
+fn render(template: &str, version: &Version) -> String {
+    template
+        .replace("{version}", &version.to_string())
+        .replace("{major}", &version.major.to_string())
+        .replace("{minor}", &version.minor.to_string())
+        .replace("{patch}", &version.patch.to_string())
+}
+
+/// A file rewritten by `[[packages.version_files]]`.
+#[derive(Debug, Clone)]
+pub(crate) struct VersionFileUpdate {
+    pub path: PathBuf,
+    pub original: String,
+    pub updated: String,
+}
+
+/// Compute the rewrites of `entries`, each paired with the old and new version of its
+/// package. Files in `managed` (manifests and changelogs the release writes itself) are
+/// never matched. An entry whose glob or pattern matches nothing is an error, so stale
+/// entries fail the release instead of silently leaving the old version behind.
+pub(crate) fn plan_version_file_updates(
+    root: &Path,
+    entries: &[(&VersionFile, &str, &str)],
+    managed: &BTreeSet<PathBuf>,
+) -> Result<Vec<VersionFileUpdate>, SampoError> {
+    let mut files: BTreeMap<PathBuf, (String, String)> = BTreeMap::new();
+
+    for (entry, old, new) in entries {
+        let parse = |version: &str| {
+            Version::parse(version).map_err(|err| {
+                SampoError::Release(format!(
+                    "Invalid version '{version}' for '{}': {err}",
+                    entry.package
+                ))
+            })
+        };
+        let (old, new) = (parse(old)?, parse(new)?);
+
+        let pattern = root.join(&entry.path);
+        let paths = glob::glob(&pattern.to_string_lossy())
+            .map_err(|err| {
+                SampoError::Config(format!(
+                    "packages.version_files: invalid path '{}': {err}",
+                    entry.path
+                ))
+            })?
+            .filter_map(|path| path.ok())
+            .filter(|path| path.is_file() && !managed.contains(path))
+            .collect::<Vec<_>>();
+        if paths.is_empty() {
+            return Err(SampoError::Release(format!(
+                "packages.version_files: '{}' matches no file",
+                entry.path
+            )));
+        }
+
+        let mut matches = 0;
+        for path in paths {
+            if !files.contains_key(&path) {
+                let content =
+                    fs::read_to_string(&path).map_err(|err| io_error_with_path(err, &path))?;
+                files.insert(path.clone(), (content.clone(), content));
+            }
+            let (_, current) = files.get_mut(&path).expect("file was just loaded");
+            let (updated, count) = entry.rewrite(current, &old, &new);
+            *current = updated;
+            matches += count;
+        }
+        if matches == 0 {
+            return Err(SampoError::Release(format!(
+                "packages.version_files: no version of '{}' found in '{}'. Update or remove the entry.",
+                entry.package, entry.path
+            )));
+        }
+    }
+
+    Ok(files
+        .into_iter()
+        .filter(|(_, (original, updated))| original != updated)
+        .map(|(path, (original, updated))| VersionFileUpdate {
+            path,
+            original,
+            updated,
+        })
+        .collect())
+}
+
+/// Print the changed lines of each update, for `--dry-run`.
+pub(crate) fn print_version_file_diff(root: &Path, updates: &[VersionFileUpdate]) {
+    for update in updates {
+        let display = update.path.strip_prefix(root).unwrap_or(&update.path);
+        println!("--- {}", display.display());
+        println!("+++ {}", display.display());
+        let original: Vec<&str> = update.original.lines().collect();
+        let updated: Vec<&str> = update.updated.lines().collect();
+        if original.len() == updated.len() {
+            for (before, after) in original.iter().zip(&updated) {
+                if before != after {
+                    println!("-{before}");
+                    println!("+{after}");
+                }
+            }
+        } else {
+            original.iter().for_each(|line| println!("-{line}"));
+            updated.iter().for_each(|line| println!("+{line}"));
+        }
+    }
+}
+
+pub(crate) fn write_version_files(updates: &[VersionFileUpdate]) -> Result<(), SampoError> {
+    for update in updates {
+        fs::write(&update.path, &update.updated)
+            .map_err(|err| io_error_with_path(err, &update.path))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(toml: &str) -> Result<VersionFile, SampoError> {
+        VersionFile::parse(&toml::from_str::<toml::Value>(toml).unwrap(), 0)
+    }
+
+    #[test]
+    fn template_rewrites_rendered_version() {
+        let file = entry(
+            "path = 'README.md'\npackage = 'sampo'\ntemplate = 'sampo = \"{major}.{minor}\"'",
+        )
+        .unwrap();
+        let (updated, matches) = file.rewrite(
+            "[dependencies]\nsampo = \"1.2\"\n",
+            &Version::new(1, 2, 3),
+            &Version::new(1, 3, 0),
+        );
+        assert_eq!(matches, 1);
+        assert_eq!(updated, "[dependencies]\nsampo = \"1.3\"\n");
+    }
+
+    #[test]
+    fn template_rewrites_every_occurrence() {
+        let file = entry("path = 'README.md'\npackage = 'sampo'\ntemplate = 'v{version}'").unwrap();
+        let (updated, matches) = file.rewrite(
+            "Install v1.2.3, or pin v1.2.3 in CI. v1.2.0 is older.\n",
+            &Version::new(1, 2, 3),
+            &Version::new(1, 3, 0),
+        );
+        assert_eq!(matches, 2);
+        assert_eq!(
+            updated,
+            "Install v1.3.0, or pin v1.3.0 in CI. v1.2.0 is older.\n"
+        );
+    }
+
+    #[test]
+    fn managed_files_are_not_rewritten() {
+        let temp = tempfile::tempdir().unwrap();
+        let readme = temp.path().join("README.md");
+        let changelog = temp.path().join("CHANGELOG.md");
+        fs::write(&readme, "sampo = \"1.2.3\"\n").unwrap();
+        fs::write(&changelog, "## 1.2.3\n").unwrap();
+        let file = entry("path = '*.md'\npackage = 'sampo'\ntemplate = '{version}'").unwrap();
+
+        let managed = BTreeSet::from([changelog]);
+        let updates =
+            plan_version_file_updates(temp.path(), &[(&file, "1.2.3", "1.3.0")], &managed).unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].path, readme);
+        assert_eq!(updates[0].updated, "sampo = \"1.3.0\"\n");
+    }
+
+    #[test]
+    fn regex_replaces_first_capture_group() {
+        let file = entry("path = 'install.sh'\npackage = 'sampo'\nregex = 'VERSION=\"([^\"]+)\"'")
+            .unwrap();
+        let (updated, matches) = file.rewrite(
+            "VERSION=\"0.9.0\"\necho $VERSION\n",
+            &Version::new(0, 9, 0),
+            &Version::new(1, 0, 0),
+        );
+        assert_eq!(matches, 1);
+        assert_eq!(updated, "VERSION=\"1.0.0\"\necho $VERSION\n");
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        let cases = [
+            (
+                "package = 'sampo'\ntemplate = '{version}'",
+                "path is required",
+            ),
+            (
+                "path = 'a'\npackage = 'sampo'",
+                "needs a template, a regex, or both",
+            ),
+            (
+                "path = 'a'\npackage = 'sampo'\ntemplate = 'v{build}'",
+                "unknown placeholder",
+            ),
+            (
+                "path = 'a'\npackage = 'sampo'\nregex = 'v\\d+'",
+                "capture group",
+            ),
+        ];
+        for (toml, expected) in cases {
+            let err = entry(toml).unwrap_err().to_string();
+            assert!(err.contains(expected), "{err}");
+        }
+    }
+}
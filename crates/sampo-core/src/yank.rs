@@ -0,0 +1,424 @@
+use crate::adapters::{PackageAdapter, YankRequest};
+use crate::errors::{Result, SampoError, io_error_with_path};
+use crate::release::header_matches_release_version;
+use crate::types::{PackageInfo, PackageSpecifier, SpecResolution, format_ambiguity_options};
+use crate::{Config, USER_AGENT, detect_github_repo_slug_with_config, discover_workspace};
+use reqwest::StatusCode;
+use reqwest::blocking::Client;
+use serde_json::Value as JsonValue;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// What `sampo yank` does besides pulling the version from its registry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct YankOptions {
+    /// Why the version is yanked, shown where the registry records it and in the notes.
+    pub reason: Option<String>,
+    /// Unpublish an npm version instead of deprecating it.
+    pub unpublish: bool,
+    /// Add a yank note to the version's changelog section.
+    pub changelog: bool,
+    /// Add a yank note to the version's GitHub release.
+    pub github_release: bool,
+    /// Print what would happen without touching the registry, the changelog or GitHub.
+    pub dry_run: bool,
+}
+
+/// What `sampo yank` did, or would do in a dry run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YankOutput {
+    /// Canonical identifier of the package, like `cargo/foo`.
+    pub package: String,
+    pub version: String,
+    /// The changelog that got a yank note.
+    pub changelog: Option<PathBuf>,
+    /// The tag of the GitHub release that got a yank note.
+    pub github_release: Option<String>,
+    pub dry_run: bool,
+}
+
+/// Yanks `<package>@<version>` from its registry, with `cargo yank`, `npm deprecate`
+/// (or `npm unpublish`) and the `hex retire` of the package's Hex client. PyPI, Packagist
+/// and Maven Central offer no way to do so, and fail with an explanation.
+///
+/// The changelog section and the GitHub release are checked before the registry is
+/// touched, so a missing section or credentials abort without yanking anything.
+pub fn run_yank(root: &Path, target: &str, options: &YankOptions) -> Result<YankOutput> {
+    let (spec, version) = parse_yank_target(target)?;
+    let workspace = discover_workspace(root)?;
+    let config = Config::load(&workspace.root)?;
+
+    let package = match workspace.resolve_specifier(&spec) {
+        SpecResolution::Match(info) => info,
+        SpecResolution::NotFound { query } => {
+            return Err(SampoError::NotFound(format!(
+                "Package '{}' not found in workspace",
+                query.display()
+            )));
+        }
+        SpecResolution::Ambiguous { query, matches } => {
+            return Err(SampoError::Yank(format!(
+                "Package '{}' is ambiguous. Disambiguate using one of: {}.",
+                query.base_name(),
+                format_ambiguity_options(&matches)
+            )));
+        }
+    };
+
+    // A blank reason is no reason: npm would read an empty message as lifting the
+    // deprecation.
+    let reason = options
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+    let note = yank_note(reason);
+
+    let changelog = options
+        .changelog
+        .then(|| changelog_path(&config, package))
+        .transpose()?;
+    if let Some(path) = &changelog {
+        let text = fs::read_to_string(path).map_err(|e| io_error_with_path(e, path))?;
+        if mark_changelog_yanked(&text, &version, &note).is_none() {
+            return Err(SampoError::Yank(format!(
+                "{} has no section for {}",
+                path.display(),
+                version
+            )));
+        }
+    }
+
+    let github = if options.github_release {
+        let repo = detect_github_repo_slug_with_config(
+            &workspace.root,
+            config.github_repository.as_deref(),
+        )
+        .ok_or_else(|| {
+            SampoError::GitHub(
+                "cannot annotate the GitHub release: no GitHub repository detected".to_string(),
+            )
+        })?;
+        let token = std::env::var("GITHUB_TOKEN")
+            .or_else(|_| std::env::var("GH_TOKEN"))
+            .map_err(|_| {
+                SampoError::GitHub(
+                    "cannot annotate the GitHub release: GITHUB_TOKEN or GH_TOKEN must be set"
+                        .to_string(),
+                )
+            })?;
+        let tag = config.package_tag_name(&workspace.root, package, &version);
+        Some((repo, token, tag))
+    } else {
+        None
+    };
+
+    let adapter = PackageAdapter::from_kind(package.kind);
+    let manifest = adapter.manifest_path(&package.path);
+    adapter.yank(
+        &manifest,
+        &YankRequest {
+            name: &package.name,
+            version: &version,
+            reason,
+            unpublish: options.unpublish,
+            dry_run: options.dry_run,
+        },
+    )?;
+
+    if let Some(path) = &changelog {
+        if options.dry_run {
+            println!("Dry-run: would add a yank note to {}", path.display());
+        } else {
+            let text = fs::read_to_string(path).map_err(|e| io_error_with_path(e, path))?;
+            match mark_changelog_yanked(&text, &version, &note) {
+                Some(updated) if updated != text => {
+                    fs::write(path, updated).map_err(|e| io_error_with_path(e, path))?;
+                    println!("Added a yank note to {}", path.display());
+                }
+                _ => println!("{} already notes the yank", path.display()),
+            }
+        }
+    }
+
+    let github_release = match github {
+        Some((repo, token, tag)) => {
+            if options.dry_run {
+                println!("Dry-run: would add a yank note to the GitHub release {tag}");
+            } else if annotate_github_release(&repo, &token, &tag, &note)? {
+                println!("Added a yank note to the GitHub release {tag}");
+            } else {
+                println!("The GitHub release {tag} already notes the yank");
+            }
+            Some(tag)
+        }
+        None => None,
+    };
+
+    Ok(YankOutput {
+        package: package.canonical_identifier().to_string(),
+        version,
+        changelog,
+        github_release,
+        dry_run: options.dry_run,
+    })
+}
+
+/// Splits `<package>@<version>` on its last `@`, so scoped npm names keep theirs.
+fn parse_yank_target(target: &str) -> Result<(PackageSpecifier, String)> {
+    let invalid = || {
+        SampoError::Yank(format!(
+            "expected <package>@<version>, found '{}'",
+            target.trim()
+        ))
+    };
+    let (package, version) = target.trim().rsplit_once('@').ok_or_else(invalid)?;
+    let version = version.trim();
+    if package.is_empty() || package.ends_with('/') || version.is_empty() {
+        return Err(invalid());
+    }
+    let spec = PackageSpecifier::parse(package).map_err(|reason| {
+        SampoError::Yank(format!(
+            "Invalid package reference '{}': {}",
+            package, reason
+        ))
+    })?;
+    Ok((spec, version.to_string()))
+}
+
+fn changelog_path(config: &Config, package: &PackageInfo) -> Result<PathBuf> {
+    let path = package
+        .path
+        .join(&config.for_package(package).changelog_path);
+    if path.exists() {
+        Ok(path)
+    } else {
+        Err(SampoError::Yank(format!(
+            "cannot note the yank: {} does not exist",
+            path.display()
+        )))
+    }
+}
+
+fn yank_note(reason: Option<&str>) -> String {
+    match reason {
+        Some(reason) => format!("**Yanked:** {reason}"),
+        None => "**Yanked.**".to_string(),
+    }
+}
+
+/// Inserts `note` right under the `##` heading of `version`. Returns `None` when the
+/// changelog has no such section, and the text unchanged when it already notes a yank.
+fn mark_changelog_yanked(text: &str, version: &str, note: &str) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let heading = lines.iter().position(|line| {
+        line.trim_start()
+            .strip_prefix("## ")
+            .is_some_and(|heading| header_matches_release_version(heading.trim(), version))
+    })?;
+
+    let already_noted = lines[heading + 1..]
+        .iter()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(|line| line.starts_with("**Yanked"));
+    if already_noted {
+        return Some(text.to_string());
+    }
+
+    let mut updated: Vec<&str> = Vec::with_capacity(lines.len() + 2);
+    updated.extend(&lines[..=heading]);
+    updated.push("");
+    updated.push(note);
+    if lines
+        .get(heading + 1)
+        .is_some_and(|line| !line.trim().is_empty())
+    {
+        updated.push("");
+    }
+    updated.extend(&lines[heading + 1..]);
+
+    let mut result = updated.join("\n");
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    Some(result)
+}
+
+/// The release notes with `note` as a warning on top, or `None` when they already
+/// carry one.
+fn annotated_release_body(body: &str, note: &str) -> Option<String> {
+    if body.contains("**Yanked") {
+        return None;
+    }
+    let warning = format!("> [!WARNING]\n> {note}");
+    if body.trim().is_empty() {
+        Some(warning)
+    } else {
+        Some(format!("{warning}\n\n{body}"))
+    }
+}
+
+/// Adds `note` to the GitHub release of `tag`. Returns false when it was already there.
+fn annotate_github_release(repo: &str, token: &str, tag: &str, note: &str) -> Result<bool> {
+    let client = Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| SampoError::GitHub(format!("failed to build HTTP client: {e}")))?;
+    let request = |builder: reqwest::blocking::RequestBuilder| {
+        builder
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+    };
+
+    let url = format!("https://api.github.com/repos/{repo}/releases/tags/{tag}");
+    let response = request(client.get(&url))
+        .send()
+        .map_err(|e| SampoError::GitHub(format!("failed to fetch the release {tag}: {e}")))?;
+    match response.status() {
+        StatusCode::OK => {}
+        StatusCode::NOT_FOUND => {
+            return Err(SampoError::GitHub(format!(
+                "no GitHub release found for tag {tag} in {repo}"
+            )));
+        }
+        status => {
+            return Err(SampoError::GitHub(format!(
+                "GitHub returned {status} for the release {tag}"
+            )));
+        }
+    }
+    let release: JsonValue = response
+        .json()
+        .map_err(|e| SampoError::GitHub(format!("invalid release {tag}: {e}")))?;
+    let id = release
+        .get("id")
+        .and_then(JsonValue::as_u64)
+        .ok_or_else(|| SampoError::GitHub(format!("the release {tag} has no id")))?;
+    let body = release
+        .get("body")
+        .and_then(JsonValue::as_str)
+        .unwrap_or_default();
+
+    let Some(body) = annotated_release_body(body, note) else {
+        return Ok(false);
+    };
+    let url = format!("https://api.github.com/repos/{repo}/releases/{id}");
+    let response = request(client.patch(&url))
+        .json(&serde_json::json!({ "body": body }))
+        .send()
+        .map_err(|e| SampoError::GitHub(format!("failed to update the release {tag}: {e}")))?;
+    if !response.status().is_success() {
+        return Err(SampoError::GitHub(format!(
+            "GitHub returned {} when updating the release {tag}",
+            response.status()
+        )));
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PackageKind;
+
+    #[test]
+    fn parses_yank_targets() {
+        let (spec, version) = parse_yank_target("foo@1.2.0").unwrap();
+        assert_eq!((spec.kind, spec.name.as_str()), (None, "foo"));
+        assert_eq!(version, "1.2.0");
+
+        let (spec, version) = parse_yank_target("npm/@scope/pkg@2.0.0-rc.1").unwrap();
+        assert_eq!(spec.kind, Some(PackageKind::Npm));
+        assert_eq!(spec.name, "@scope/pkg");
+        assert_eq!(version, "2.0.0-rc.1");
+
+        for invalid in ["foo", "foo@", "@1.2.0", "npm/@1.2.0"] {
+            assert!(parse_yank_target(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn notes_the_yank_under_the_version_heading() {
+        let changelog = "# foo\n\n## 1.1.0\n\n### Patch changes\n\n- Fix\n\n## 1.0.0 — 2024-01-01\n\n### Major changes\n\n- Initial\n";
+
+        let updated = mark_changelog_yanked(changelog, "1.0.0", "**Yanked:** broken").unwrap();
+        assert_eq!(
+            updated,
+            "# foo\n\n## 1.1.0\n\n### Patch changes\n\n- Fix\n\n## 1.0.0 — 2024-01-01\n\n**Yanked:** broken\n\n### Major changes\n\n- Initial\n"
+        );
+        assert_eq!(
+            mark_changelog_yanked(&updated, "1.0.0", "**Yanked.**").as_deref(),
+            Some(updated.as_str())
+        );
+        assert_eq!(
+            mark_changelog_yanked(changelog, "0.9.0", "**Yanked.**"),
+            None
+        );
+    }
+
+    #[test]
+    fn warns_on_top_of_release_notes_once() {
+        let body = annotated_release_body("- Fix", "**Yanked:** broken").unwrap();
+        assert_eq!(body, "> [!WARNING]\n> **Yanked:** broken\n\n- Fix");
+        assert_eq!(annotated_release_body(&body, "**Yanked.**"), None);
+        assert_eq!(
+            annotated_release_body("", "**Yanked.**").as_deref(),
+            Some("> [!WARNING]\n> **Yanked.**")
+        );
+    }
+
+    #[test]
+    fn dry_run_checks_the_changelog_without_changing_it() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join(".sampo")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        let crate_dir = root.join("crates/foo");
+        fs::create_dir_all(crate_dir.join("src")).unwrap();
+        fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"foo\"\nversion = \"1.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        fs::write(crate_dir.join("src/lib.rs"), "").unwrap();
+        let changelog = "# foo\n\n## 1.1.0\n\n- Fix\n\n## 1.0.0\n\n- Initial\n";
+        fs::write(crate_dir.join("CHANGELOG.md"), changelog).unwrap();
+
+        let options = YankOptions {
+            reason: Some("broken".to_string()),
+            changelog: true,
+            dry_run: true,
+            ..Default::default()
+        };
+        let output = run_yank(root, "foo@1.0.0", &options).unwrap();
+        assert_eq!(output.package, "cargo/foo");
+        assert_eq!(output.version, "1.0.0");
+        assert_eq!(output.changelog, Some(crate_dir.join("CHANGELOG.md")));
+        assert_eq!(
+            fs::read_to_string(crate_dir.join("CHANGELOG.md")).unwrap(),
+            changelog
+        );
+
+        let err = run_yank(root, "foo@0.9.0", &options).unwrap_err();
+        assert!(err.to_string().contains("no section for 0.9.0"), "{err}");
+
+        let err = run_yank(
+            root,
+            "foo@1.0.0",
+            &YankOptions {
+                unpublish: true,
+                dry_run: true,
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("only npm packages"), "{err}");
+    }
+}
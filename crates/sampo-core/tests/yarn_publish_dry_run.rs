@@ -104,7 +104,7 @@ fn yarn_classic_dry_run_is_skipped_without_spawning_a_publish() {
     let fixture = Fixture::new("1.22.22");
 
     let outcome = PackageAdapter::Npm
-        .publish(&fixture.manifest, true, &[])
+        .publish(&fixture.manifest, true, &[], None)
         .expect("a dry run yarn cannot simulate must be skipped, not failed");
 
     assert_eq!(
@@ -164,7 +164,7 @@ fn yarn_berry_below_the_dry_run_floor_is_skipped_without_spawning_a_publish() {
     let fixture = Fixture::new("4.9.2");
 
     let outcome = PackageAdapter::Npm
-        .publish(&fixture.manifest, true, &[])
+        .publish(&fixture.manifest, true, &[], None)
         .expect("`yarn npm publish` gained --dry-run in 4.9.3");
 
     assert_eq!(outcome, PublishOutcome::DryRunSkipped);
@@ -180,7 +180,7 @@ fn yarn_berry_at_the_dry_run_floor_reaches_yarn_npm_publish() {
     let fixture = Fixture::new("4.9.3");
 
     let err = PackageAdapter::Npm
-        .publish(&fixture.manifest, true, &[])
+        .publish(&fixture.manifest, true, &[], None)
         .expect_err("the stub yarn fails the publish");
     assert!(
         err.to_string().contains("yarn npm publish failed"),
@@ -199,7 +199,7 @@ fn a_real_publish_is_never_skipped_on_a_yarn_without_a_dry_run() {
     let fixture = Fixture::new("1.22.22");
 
     let err = PackageAdapter::Npm
-        .publish(&fixture.manifest, false, &[])
+        .publish(&fixture.manifest, false, &[], None)
         .expect_err("the stub yarn fails the publish");
     assert!(
         err.to_string().contains("yarn publish failed"),
@@ -219,7 +219,7 @@ fn a_dry_run_asked_for_through_the_passthrough_is_skipped_too() {
 
     // `sampo publish -- --dry-run` reaches the adapter as a plain publish carrying the flag.
     PackageAdapter::Npm
-        .publish(&fixture.manifest, false, &["--dry-run".to_string()])
+        .publish(&fixture.manifest, false, &["--dry-run".to_string()], None)
         .expect("a dry run yarn cannot simulate must be skipped, not failed");
 
     assert_eq!(
@@ -241,7 +241,7 @@ fn the_probe_wins_over_the_manifest_field() {
     let _guard = common::use_only(&bin);
 
     PackageAdapter::Npm
-        .publish(&manifest, true, &[])
+        .publish(&manifest, true, &[], None)
         .expect_err("the stub yarn fails the publish");
 
     assert_eq!(
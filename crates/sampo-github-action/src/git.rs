@@ -1,5 +1,5 @@
 use crate::error::{ActionError, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Execute git commands with error handling
@@ -56,3 +56,172 @@ pub fn setup_bot_user(workspace: &Path) -> Result<()> {
     )?;
     Ok(())
 }
+
+/// Execute a git command and return its trimmed stdout
+pub fn git_output(args: &[&str], cwd: &Path) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .map_err(ActionError::Io)?;
+
+    if !output.status.success() {
+        return Err(ActionError::SampoCommandFailed {
+            operation: "git".to_string(),
+            message: format!(
+                "git {} failed with status {}: {}",
+                args.join(" "),
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Push `HEAD` to `branch` on GitHub, authenticating with an explicit token.
+///
+/// Used when the default workflow token cannot bypass branch protection. The
+/// token never appears in error messages.
+pub fn push_with_token(cwd: &Path, repo: &str, token: &str, branch: &str) -> Result<()> {
+    let url = format!("https://x-access-token:{}@github.com/{}.git", token, repo);
+    let refspec = format!("HEAD:refs/heads/{}", branch);
+    let status = Command::new("git")
+        // `actions/checkout` persists GITHUB_TOKEN as an extra header, which
+        // would take precedence over the credentials embedded in the URL.
+        .args(["-c", "http.https://github.com/.extraheader="])
+        .args(["push", &url, &refspec])
+        .current_dir(cwd)
+        .status()
+        .map_err(ActionError::Io)?;
+
+    if !status.success() {
+        return Err(ActionError::SampoCommandFailed {
+            operation: "git".to_string(),
+            message: format!(
+                "git push to {} ({}) failed with status {}",
+                repo, branch, status
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Changeset files deleted by the most recent release commit on the current branch.
+///
+/// Follows first parents only, so a merged release PR is seen as a single commit
+/// whether it was merged, squashed or rebased.
+pub fn last_consumed_changesets(cwd: &Path) -> Result<Vec<PathBuf>> {
+    let listed = git_output(
+        &[
+            "log",
+            "--first-parent",
+            "--diff-merges=first-parent",
+            "--no-renames",
+            "--diff-filter=D",
+            "--name-only",
+            "--format=",
+            "-1",
+            "--",
+            ".sampo/changesets",
+        ],
+        cwd,
+    )?;
+
+    Ok(listed
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| cwd.join(line))
+        .collect())
+}
+
+/// Read a file as it was at `commit`
+pub fn show_file_at(cwd: &Path, commit: &str, path: &Path) -> Result<String> {
+    let relative = path.strip_prefix(cwd).unwrap_or(path);
+    git_output(
+        &[
+            "show",
+            &format!("{}:./{}", commit, relative.to_string_lossy()),
+        ],
+        cwd,
+    )
+}
+
+/// A path staged in the index, as reported by `git diff --cached --raw`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagedChange {
+    /// Path relative to the repository root
+    pub path: String,
+    /// Git file mode of the new content (e.g. `100644`), meaningless for deletions
+    pub mode: String,
+    /// Whether the path was removed
+    pub deleted: bool,
+}
+
+/// List the paths staged in the index (run `git add -A` first to include the working tree)
+pub fn staged_changes(cwd: &Path) -> Result<Vec<StagedChange>> {
+    let output = Command::new("git")
+        .args(["diff", "--cached", "--raw", "-z", "--no-renames"])
+        .current_dir(cwd)
+        .output()
+        .map_err(ActionError::Io)?;
+
+    if !output.status.success() {
+        return Err(ActionError::SampoCommandFailed {
+            operation: "git-diff".to_string(),
+            message: format!("git diff --cached failed: {}", output.status),
+        });
+    }
+
+    Ok(parse_raw_diff(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git diff --raw -z` output (`:<old mode> <new mode> <old sha> <new sha> <status>\0<path>\0`)
+fn parse_raw_diff(raw: &str) -> Vec<StagedChange> {
+    let mut changes = Vec::new();
+    let mut fields = raw.split('\0');
+    while let Some(header) = fields.next() {
+        let Some(path) = fields.next() else {
+            break;
+        };
+        let mut parts = header.trim_start_matches(':').split_whitespace();
+        let _old_mode = parts.next();
+        let new_mode = parts.next().unwrap_or("100644");
+        let status = parts.nth(2).unwrap_or("M");
+        changes.push(StagedChange {
+            path: path.to_string(),
+            mode: new_mode.to_string(),
+            deleted: status.starts_with('D'),
+        });
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_raw_diff_reports_modes_and_deletions() {
+        let raw = ":100644 100644 1111111 2222222 M\0crates/a/Cargo.toml\0\
+:000000 100644 0000000 3333333 A\0crates/a/CHANGELOG.md\0\
+:100644 000000 4444444 0000000 D\0.sampo/changesets/brave-fox.md\0\
+:100644 100755 5555555 6666666 M\0scripts/release.sh\0";
+        let changes = parse_raw_diff(raw);
+        assert_eq!(changes.len(), 4);
+        assert_eq!(changes[0].path, "crates/a/Cargo.toml");
+        assert!(!changes[0].deleted);
+        assert_eq!(changes[1].mode, "100644");
+        assert_eq!(changes[2].path, ".sampo/changesets/brave-fox.md");
+        assert!(changes[2].deleted);
+        assert_eq!(changes[3].mode, "100755");
+    }
+
+    #[test]
+    fn parse_raw_diff_handles_empty_output() {
+        assert!(parse_raw_diff("").is_empty());
+    }
+}
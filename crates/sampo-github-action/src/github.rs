@@ -1,6 +1,10 @@
 use crate::error::{ActionError, Result};
-use reqwest::Url;
-use reqwest::blocking::Client;
+use crate::git::StagedChange;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::{StatusCode, Url};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::time::Duration;
@@ -28,6 +32,98 @@ struct CreateReleasePayload {
     prerelease: bool,
 }
 
+#[derive(Debug, Serialize)]
+struct CreateBlobPayload {
+    content: String,
+    encoding: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct TreeEntry {
+    path: String,
+    mode: String,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    /// `None` serializes as `null`, which removes the path from the tree
+    sha: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateTreePayload {
+    base_tree: String,
+    tree: Vec<TreeEntry>,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateCommitPayload {
+    message: String,
+    tree: String,
+    parents: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateTagPayload {
+    tag: String,
+    message: String,
+    object: String,
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateRefPayload {
+    #[serde(rename = "ref")]
+    reference: String,
+    sha: String,
+}
+
+#[derive(Debug, Serialize)]
+struct UpdateRefPayload {
+    sha: String,
+    force: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateCommentPayload {
+    body: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AddLabelsPayload {
+    labels: Vec<String>,
+}
+
+/// A pull request associated with a commit
+#[derive(Debug, Deserialize)]
+pub struct AssociatedPullRequest {
+    pub number: u64,
+    pub body: Option<String>,
+}
+
+/// An open pull request and the branch it is opened from
+#[derive(Debug, Deserialize)]
+pub struct OpenPullRequest {
+    pub number: u64,
+    pub body: Option<String>,
+    pub head: PullRequestHead,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PullRequestHead {
+    #[serde(rename = "ref")]
+    pub branch: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ClosePullRequestPayload {
+    state: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitObject {
+    sha: String,
+}
+
 #[derive(Debug, Serialize)]
 struct GraphQLRequest {
     query: String,
@@ -109,6 +205,300 @@ impl GitHubClient {
         format!("Bearer {}", self.token)
     }
 
+    /// Send a REST API request and decode the JSON it returns.
+    fn send_json<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+        operation: &str,
+    ) -> Result<T> {
+        self.send(request, operation)?
+            .json()
+            .map_err(|e| ActionError::SampoCommandFailed {
+                operation: operation.to_string(),
+                message: format!("Failed to parse response: {}", e),
+            })
+    }
+
+    /// Send a REST API `GET` request for a list, following the `Link` header through
+    /// every page.
+    fn send_paginated<T: DeserializeOwned>(&self, url: Url, operation: &str) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        let mut next = Some(url.to_string());
+        while let Some(url) = next {
+            let response = self.send(self.client.get(&url), operation)?;
+            next = response
+                .headers()
+                .get("link")
+                .and_then(|value| value.to_str().ok())
+                .and_then(next_page_link);
+            let page: Vec<T> = response
+                .json()
+                .map_err(|e| ActionError::SampoCommandFailed {
+                    operation: operation.to_string(),
+                    message: format!("Failed to parse response: {}", e),
+                })?;
+            items.extend(page);
+        }
+        Ok(items)
+    }
+
+    /// Send a REST API request, failing on an unsuccessful status.
+    fn send(&self, request: RequestBuilder, operation: &str) -> Result<Response> {
+        let response = request
+            .header("Authorization", self.auth_header())
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()
+            .map_err(|e| ActionError::SampoCommandFailed {
+                operation: operation.to_string(),
+                message: format!("HTTP request failed: {}", e),
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().unwrap_or_default();
+            return Err(ActionError::SampoCommandFailed {
+                operation: operation.to_string(),
+                message: format!("GitHub API error ({}): {}", status, error_text),
+            });
+        }
+        Ok(response)
+    }
+
+    /// Create a commit on top of `parent` through the Git Data API.
+    ///
+    /// Commits created this way are signed by GitHub, so they show as verified and
+    /// satisfy "require signed commits" rules. `changes` is read from the index of
+    /// the local checkout at `workspace`. Returns the new commit SHA; no ref is moved.
+    pub fn create_commit(
+        &self,
+        workspace: &Path,
+        parent: &str,
+        base_tree: &str,
+        message: &str,
+        changes: &[StagedChange],
+    ) -> Result<String> {
+        let api_base = format!("https://api.github.com/repos/{}/git", self.repo);
+
+        let mut tree = Vec::with_capacity(changes.len());
+        for change in changes {
+            if change.deleted {
+                tree.push(TreeEntry {
+                    path: change.path.clone(),
+                    mode: "100644".to_string(),
+                    kind: "blob",
+                    sha: None,
+                });
+                continue;
+            }
+
+            let bytes = blob_content(&workspace.join(&change.path)).map_err(ActionError::Io)?;
+            let blob: GitObject = self.send_json(
+                self.client
+                    .post(format!("{}/blobs", api_base))
+                    .json(&CreateBlobPayload {
+                        content: BASE64.encode(bytes),
+                        encoding: "base64",
+                    }),
+                "github-create-blob",
+            )?;
+            tree.push(TreeEntry {
+                path: change.path.clone(),
+                mode: change.mode.clone(),
+                kind: "blob",
+                sha: Some(blob.sha),
+            });
+        }
+
+        let tree: GitObject = self.send_json(
+            self.client
+                .post(format!("{}/trees", api_base))
+                .json(&CreateTreePayload {
+                    base_tree: base_tree.to_string(),
+                    tree,
+                }),
+            "github-create-tree",
+        )?;
+
+        let commit: GitObject = self.send_json(
+            self.client
+                .post(format!("{}/commits", api_base))
+                .json(&CreateCommitPayload {
+                    message: message.to_string(),
+                    tree: tree.sha,
+                    parents: vec![parent.to_string()],
+                }),
+            "github-create-commit",
+        )?;
+
+        Ok(commit.sha)
+    }
+
+    /// Point `refs/heads/<branch>` at `sha`, creating the branch when it does not exist.
+    ///
+    /// Without `force`, GitHub rejects the update unless it is a fast-forward.
+    pub fn update_branch(&self, branch: &str, sha: &str, force: bool) -> Result<()> {
+        self.update_ref(&format!("heads/{}", branch), sha, force)
+    }
+
+    /// Point the lightweight tag `tag` at `sha`, creating it or moving it.
+    pub fn move_tag(&self, tag: &str, sha: &str) -> Result<()> {
+        self.update_ref(&format!("tags/{}", tag), sha, true)
+    }
+
+    fn update_ref(&self, reference: &str, sha: &str, force: bool) -> Result<()> {
+        let ref_url = format!(
+            "https://api.github.com/repos/{}/git/refs/{}",
+            self.repo, reference
+        );
+        let response = self
+            .client
+            .get(&ref_url)
+            .header("Authorization", self.auth_header())
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()
+            .map_err(|e| ActionError::SampoCommandFailed {
+                operation: "github-get-ref".to_string(),
+                message: format!("HTTP request to {} failed: {}", ref_url, e),
+            })?;
+        // Only a 404 means the ref is missing: auth failures and outages must not turn
+        // into an attempt to create it.
+        let exists = match response.status() {
+            status if status.is_success() => true,
+            StatusCode::NOT_FOUND => false,
+            status => {
+                let error_text = response.text().unwrap_or_default();
+                return Err(ActionError::SampoCommandFailed {
+                    operation: "github-get-ref".to_string(),
+                    message: format!("GitHub API error ({}): {}", status, error_text),
+                });
+            }
+        };
+
+        if exists {
+            let _: serde_json::Value = self.send_json(
+                self.client.patch(&ref_url).json(&UpdateRefPayload {
+                    sha: sha.to_string(),
+                    force,
+                }),
+                "github-update-ref",
+            )?;
+        } else {
+            self.create_ref(&format!("refs/{}", reference), sha)?;
+        }
+
+        Ok(())
+    }
+
+    /// List the pull requests that introduced `sha` into the repository.
+    pub fn pull_requests_for_commit(&self, sha: &str) -> Result<Vec<AssociatedPullRequest>> {
+        self.send_json(
+            self.client.get(format!(
+                "https://api.github.com/repos/{}/commits/{}/pulls",
+                self.repo, sha
+            )),
+            "github-commit-pulls",
+        )
+    }
+
+    /// List the open pull requests targeting `base_branch`.
+    pub fn open_pull_requests(&self, base_branch: &str) -> Result<Vec<OpenPullRequest>> {
+        let mut url = Url::parse(&format!("https://api.github.com/repos/{}/pulls", self.repo))
+            .map_err(|e| ActionError::SampoCommandFailed {
+                operation: "github-list-prs".to_string(),
+                message: format!("Invalid repository '{}': {}", self.repo, e),
+            })?;
+        url.query_pairs_mut()
+            .append_pair("state", "open")
+            .append_pair("base", base_branch)
+            .append_pair("per_page", "100");
+        self.send_paginated(url, "github-list-prs")
+    }
+
+    /// Close a pull request, leaving `comment` to explain why.
+    pub fn close_pull_request(&self, number: u64, comment: &str) -> Result<()> {
+        self.comment_on_issue(number, comment)?;
+        let _: serde_json::Value = self.send_json(
+            self.client
+                .patch(format!(
+                    "https://api.github.com/repos/{}/pulls/{}",
+                    self.repo, number
+                ))
+                .json(&ClosePullRequestPayload { state: "closed" }),
+            "github-close-pr",
+        )?;
+        Ok(())
+    }
+
+    /// Comment on an issue or pull request.
+    pub fn comment_on_issue(&self, number: u64, body: &str) -> Result<()> {
+        let _: serde_json::Value = self.send_json(
+            self.client
+                .post(format!(
+                    "https://api.github.com/repos/{}/issues/{}/comments",
+                    self.repo, number
+                ))
+                .json(&CreateCommentPayload {
+                    body: body.to_string(),
+                }),
+            "github-create-comment",
+        )?;
+        Ok(())
+    }
+
+    /// Add a label to an issue or pull request, creating the label if needed.
+    pub fn add_label(&self, number: u64, label: &str) -> Result<()> {
+        let _: serde_json::Value = self.send_json(
+            self.client
+                .post(format!(
+                    "https://api.github.com/repos/{}/issues/{}/labels",
+                    self.repo, number
+                ))
+                .json(&AddLabelsPayload {
+                    labels: vec![label.to_string()],
+                }),
+            "github-add-label",
+        )?;
+        Ok(())
+    }
+
+    /// Create an annotated tag through the Git Data API and point `refs/tags/<tag>` at it.
+    pub fn create_annotated_tag(&self, tag: &str, message: &str, target: &str) -> Result<()> {
+        let tag_object: GitObject = self.send_json(
+            self.client
+                .post(format!(
+                    "https://api.github.com/repos/{}/git/tags",
+                    self.repo
+                ))
+                .json(&CreateTagPayload {
+                    tag: tag.to_string(),
+                    message: message.to_string(),
+                    object: target.to_string(),
+                    kind: "commit",
+                }),
+            "github-create-tag",
+        )?;
+
+        self.create_ref(&format!("refs/tags/{}", tag), &tag_object.sha)
+    }
+
+    fn create_ref(&self, reference: &str, sha: &str) -> Result<()> {
+        let _: serde_json::Value = self.send_json(
+            self.client
+                .post(format!(
+                    "https://api.github.com/repos/{}/git/refs",
+                    self.repo
+                ))
+                .json(&CreateRefPayload {
+                    reference: reference.to_string(),
+                    sha: sha.to_string(),
+                }),
+            "github-create-ref",
+        )?;
+        Ok(())
+    }
+
     /// Create or update a GitHub Pull Request
     pub fn ensure_pull_request(
         &self,
@@ -655,6 +1045,39 @@ impl GitHubClient {
     }
 }
 
+/// The content of the blob git stores for `path`: the target of a symlink (mode `120000`),
+/// not the file it points to.
+fn blob_content(path: &Path) -> std::io::Result<Vec<u8>> {
+    if std::fs::symlink_metadata(path)?.file_type().is_symlink() {
+        let target = std::fs::read_link(path)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            return Ok(target.as_os_str().as_bytes().to_vec());
+        }
+        #[cfg(not(unix))]
+        return Ok(target.to_string_lossy().replace('\\', "/").into_bytes());
+    }
+    std::fs::read(path)
+}
+
+/// The URL of the next page in a `Link` response header, e.g.
+/// `<https://api.github.com/...&page=2>; rel="next", <...>; rel="last"`.
+fn next_page_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|link| {
+        let (url, params) = link.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == "rel=\"next\"")
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -689,6 +1112,64 @@ mod tests {
         assert!(json.contains("\"prerelease\":true"));
     }
 
+    #[test]
+    fn test_tree_entry_serializes_deletions_as_null() {
+        let entries = vec![
+            TreeEntry {
+                path: "crates/a/CHANGELOG.md".to_string(),
+                mode: "100644".to_string(),
+                kind: "blob",
+                sha: Some("abc123".to_string()),
+            },
+            TreeEntry {
+                path: ".sampo/changesets/brave-fox.md".to_string(),
+                mode: "100644".to_string(),
+                kind: "blob",
+                sha: None,
+            },
+        ];
+
+        let json = serde_json::to_value(&entries).expect("tree entries should serialize");
+        assert_eq!(json[0]["type"], "blob");
+        assert_eq!(json[0]["sha"], "abc123");
+        assert!(json[1]["sha"].is_null());
+
+        let reference = serde_json::to_value(CreateRefPayload {
+            reference: "refs/tags/sampo-v1.0.0".to_string(),
+            sha: "def456".to_string(),
+        })
+        .expect("ref payload should serialize");
+        assert_eq!(reference["ref"], "refs/tags/sampo-v1.0.0");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_committed_as_their_target() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("target.txt"), "content").unwrap();
+        std::os::unix::fs::symlink("target.txt", temp.path().join("link")).unwrap();
+
+        assert_eq!(
+            blob_content(&temp.path().join("link")).unwrap(),
+            b"target.txt"
+        );
+        assert_eq!(
+            blob_content(&temp.path().join("target.txt")).unwrap(),
+            b"content"
+        );
+    }
+
+    #[test]
+    fn next_page_link_follows_rel_next() {
+        let header = "<https://api.github.com/repositories/1/pulls?page=2>; rel=\"next\", <https://api.github.com/repositories/1/pulls?page=5>; rel=\"last\"";
+        assert_eq!(
+            next_page_link(header).as_deref(),
+            Some("https://api.github.com/repositories/1/pulls?page=2")
+        );
+        let last = "<https://api.github.com/repositories/1/pulls?page=4>; rel=\"prev\", <https://api.github.com/repositories/1/pulls?page=1>; rel=\"first\"";
+        assert_eq!(next_page_link(last), None);
+    }
+
     #[test]
     fn test_github_client_creation() {
         let result = GitHubClient::new("owner/repo".to_string(), "token".to_string());
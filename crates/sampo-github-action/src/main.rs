@@ -2,13 +2,19 @@ mod error;
 mod git;
 mod github;
 mod sampo;
+mod supply_chain;
 
 use crate::error::{ActionError, Result};
-use crate::sampo::ReleasePlan;
+use crate::sampo::{ReleaseGroup, ReleasePlan, ReleasePrGrouping};
 use glob::glob;
+use sampo_core::config::{DEFAULT_CHANGELOG_PATH, DEFAULT_RELEASE_COMMIT_MESSAGE};
 use sampo_core::errors::SampoError;
+use sampo_core::types::PackageSpecifier;
 use sampo_core::workspace::discover_workspace;
-use sampo_core::{Config as SampoConfig, PublishExtraArgs, current_branch};
+use sampo_core::{
+    Config as SampoConfig, PublishExtraArgs, PublishOptions, current_branch,
+    extract_changelog_section, get_commit_hash_for_path, parse_changeset,
+};
 use semver::Version;
 use std::collections::{BTreeMap, BTreeSet};
 use std::fs::OpenOptions;
@@ -22,21 +28,50 @@ use std::process::ExitCode;
 struct GitHubReleaseOptions {
     /// Create GitHub releases for newly created tags during publish
     create_github_release: bool,
+    /// Create one release for the whole publish from the aggregate changelog
+    umbrella_release: bool,
     /// Filter for which packages should have GitHub Discussions opened
     open_discussion: DiscussionFilter,
     /// Preferred Discussions category slug (e.g., "announcements")
     discussion_category: Option<String>,
     /// Release asset patterns provided by the workflow (already-built artifacts)
     asset_specs: Vec<AssetSpec>,
+    /// Create annotated tags through the GitHub API instead of pushing local tags
+    api_tags: bool,
+    /// Comment on the pull requests whose changesets were released
+    comment_released: bool,
+    /// Label added to released pull requests
+    released_label: Option<String>,
+    /// Also comment on issues closed by released pull requests
+    comment_linked_issues: bool,
+    /// Checksums, signature and SBOM uploaded alongside the release assets
+    supply_chain: supply_chain::SupplyChainOptions,
 }
 
 impl GitHubReleaseOptions {
     fn from_config(config: &Config) -> Self {
         Self {
             create_github_release: config.create_github_release,
+            umbrella_release: config.umbrella_release,
             open_discussion: config.open_discussion.clone(),
             discussion_category: config.discussion_category.clone(),
             asset_specs: parse_asset_specs(config.release_assets.as_deref()),
+            api_tags: config.verified_commits,
+            comment_released: config.comment_released,
+            released_label: config.released_label.clone(),
+            comment_linked_issues: config.comment_linked_issues,
+            supply_chain: supply_chain::SupplyChainOptions {
+                checksums: config.release_checksums,
+                checksums_name: config
+                    .checksums_name
+                    .clone()
+                    .unwrap_or_else(|| supply_chain::DEFAULT_CHECKSUMS_NAME.to_string()),
+                signing_method: config.signing_method,
+                signing_key: config.signing_key.clone(),
+                signing_key_password: config.signing_key_password.clone(),
+                sbom: config.sbom,
+                sbom_name: config.sbom_name.clone(),
+            },
         }
     }
 }
@@ -71,6 +106,28 @@ impl Mode {
     }
 }
 
+/// How `auto` mode lands a pending release on the release branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReleaseStrategy {
+    /// Prepare (or refresh) a release PR, publish once it is merged
+    Pr,
+    /// Commit the release straight to the release branch and publish in the same run
+    Commit,
+}
+
+impl ReleaseStrategy {
+    fn parse(s: &str) -> Self {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "commit" | "direct" => ReleaseStrategy::Commit,
+            _ => ReleaseStrategy::Pr,
+        }
+    }
+}
+
+/// Trailer appended to every release commit created by the `commit` strategy,
+/// so a workflow run triggered by that commit does not release again.
+const RELEASE_COMMIT_TRAILER: &str = "Sampo-Release-Commit: true";
+
 /// Filter for which packages should have GitHub Discussions opened.
 ///
 /// Supports:
@@ -179,9 +236,27 @@ struct Config {
     /// Title to use for the Stabilize PR (default: 'Release stable (<branch>)')
     stabilize_pr_title: Option<String>,
 
+    /// How `auto` mode lands pending releases (release PR or direct commit)
+    release_strategy: ReleaseStrategy,
+
+    /// Message template for release commits created by the `commit` strategy
+    commit_message: Option<String>,
+
+    /// How pending changesets are split across release PRs
+    release_pr_grouping: ReleasePrGrouping,
+
+    /// Token used to push release commits, e.g. one allowed to bypass branch protection
+    push_token: Option<String>,
+
+    /// Create release commits and annotated tags through the GitHub API so they are verified
+    verified_commits: bool,
+
     /// Create GitHub releases for newly created tags during publish
     create_github_release: bool,
 
+    /// Create a single GitHub release per publish, from the `[changelog] aggregate` file
+    umbrella_release: bool,
+
     /// Filter for which packages should have GitHub Discussions opened
     open_discussion: DiscussionFilter,
 
@@ -190,6 +265,42 @@ struct Config {
 
     /// Paths or glob patterns to upload as release assets (comma or newline separated)
     release_assets: Option<String>,
+
+    /// Comment "Released in ..." on pull requests whose changesets were published
+    comment_released: bool,
+
+    /// Label added to released pull requests (default: 'released', empty to disable)
+    released_label: Option<String>,
+
+    /// Also comment on issues referenced with closing keywords in released pull requests
+    comment_linked_issues: bool,
+
+    /// Upload a SHA256SUMS file covering the release assets
+    release_checksums: bool,
+
+    /// Asset name template for the checksums file (default: 'SHA256SUMS')
+    checksums_name: Option<String>,
+
+    /// How to sign the checksums file (none, minisign or ssh)
+    signing_method: supply_chain::SigningMethod,
+
+    /// Private key used to sign the checksums file
+    signing_key: Option<String>,
+
+    /// Password protecting the signing key
+    signing_key_password: Option<String>,
+
+    /// SBOM format uploaded with each release (none, spdx or cyclonedx)
+    sbom: supply_chain::SbomFormat,
+
+    /// Asset name template for the SBOM
+    sbom_name: Option<String>,
+
+    /// Overrides `[publish] trusted_publishing` (unset: use the workspace config)
+    trusted_publishing: Option<bool>,
+
+    /// Overrides `[publish] provenance` (unset: use the workspace config)
+    provenance: Option<bool>,
 }
 
 impl Config {
@@ -260,10 +371,38 @@ impl Config {
             .ok()
             .filter(|v| !v.is_empty());
 
+        let release_strategy = std::env::var("INPUT_RELEASE_STRATEGY")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .map(|v| ReleaseStrategy::parse(&v))
+            .unwrap_or(ReleaseStrategy::Pr);
+
+        let commit_message = std::env::var("INPUT_COMMIT_MESSAGE")
+            .ok()
+            .filter(|v| !v.trim().is_empty());
+
+        let release_pr_grouping = std::env::var("INPUT_RELEASE_PR_GROUPING")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .map(|v| ReleasePrGrouping::parse(&v))
+            .unwrap_or(ReleasePrGrouping::Single);
+
+        let push_token = std::env::var("INPUT_PUSH_TOKEN")
+            .ok()
+            .filter(|v| !v.is_empty());
+
+        let verified_commits = std::env::var("INPUT_VERIFIED_COMMITS")
+            .map(|v| v.eq_ignore_ascii_case("true") || v.trim() == "1")
+            .unwrap_or(false);
+
         let create_github_release = std::env::var("INPUT_CREATE_GITHUB_RELEASE")
             .map(|v| v.eq_ignore_ascii_case("true") || v.trim() == "1")
             .unwrap_or(false);
 
+        let umbrella_release = std::env::var("INPUT_UMBRELLA_RELEASE")
+            .map(|v| v.eq_ignore_ascii_case("true") || v.trim() == "1")
+            .unwrap_or(false);
+
         let open_discussion = std::env::var("INPUT_OPEN_DISCUSSION")
             .map(|v| DiscussionFilter::parse(&v))
             .unwrap_or(DiscussionFilter::None);
@@ -276,6 +415,58 @@ impl Config {
             .ok()
             .filter(|v| !v.is_empty());
 
+        let comment_released = std::env::var("INPUT_COMMENT_RELEASED")
+            .map(|v| v.eq_ignore_ascii_case("true") || v.trim() == "1")
+            .unwrap_or(false);
+
+        let released_label = match std::env::var("INPUT_RELEASED_LABEL") {
+            Ok(v) => Some(v.trim().to_string()).filter(|v| !v.is_empty()),
+            Err(_) => Some("released".to_string()),
+        };
+
+        let comment_linked_issues = std::env::var("INPUT_COMMENT_LINKED_ISSUES")
+            .map(|v| v.eq_ignore_ascii_case("true") || v.trim() == "1")
+            .unwrap_or(false);
+
+        let release_checksums = std::env::var("INPUT_RELEASE_CHECKSUMS")
+            .map(|v| v.eq_ignore_ascii_case("true") || v.trim() == "1")
+            .unwrap_or(false);
+
+        let checksums_name = std::env::var("INPUT_CHECKSUMS_NAME")
+            .ok()
+            .filter(|v| !v.trim().is_empty());
+
+        let signing_method = std::env::var("INPUT_SIGNING_METHOD")
+            .map(|v| supply_chain::SigningMethod::parse(&v))
+            .unwrap_or(supply_chain::SigningMethod::None);
+
+        let signing_key = std::env::var("INPUT_SIGNING_KEY")
+            .ok()
+            .filter(|v| !v.trim().is_empty());
+
+        let signing_key_password = std::env::var("INPUT_SIGNING_KEY_PASSWORD")
+            .ok()
+            .filter(|v| !v.is_empty());
+
+        let sbom = std::env::var("INPUT_SBOM")
+            .map(|v| supply_chain::SbomFormat::parse(&v))
+            .unwrap_or(supply_chain::SbomFormat::None);
+
+        let sbom_name = std::env::var("INPUT_SBOM_NAME")
+            .ok()
+            .filter(|v| !v.trim().is_empty());
+
+        // Empty means the input was not set: the workspace config decides.
+        let trusted_publishing = std::env::var("INPUT_TRUSTED_PUBLISHING")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| v.eq_ignore_ascii_case("true") || v.trim() == "1");
+
+        let provenance = std::env::var("INPUT_PROVENANCE")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| v.eq_ignore_ascii_case("true") || v.trim() == "1");
+
         Self {
             mode,
             dry_run,
@@ -293,11 +484,49 @@ impl Config {
             pr_title,
             stabilize_pr_branch,
             stabilize_pr_title,
+            release_strategy,
+            commit_message,
+            release_pr_grouping,
+            push_token,
+            verified_commits,
             create_github_release,
+            umbrella_release,
             open_discussion,
             discussion_category,
             release_assets,
+            comment_released,
+            released_label,
+            comment_linked_issues,
+            release_checksums,
+            checksums_name,
+            signing_method,
+            signing_key,
+            signing_key_password,
+            sbom,
+            sbom_name,
+            trusted_publishing,
+            provenance,
+        }
+    }
+
+    /// Reject input combinations that would otherwise be silently ignored.
+    fn validate(&self, repo_config: &SampoConfig) -> Result<()> {
+        if self.umbrella_release && repo_config.changelog_aggregate.is_none() {
+            return Err(SampoError::Config(
+                "umbrella-release publishes the [changelog] aggregate file, so it requires \
+                 changelog.aggregate in .sampo/config.toml"
+                    .to_string(),
+            )
+            .into());
         }
+        if self.signing_method != supply_chain::SigningMethod::None && !self.release_checksums {
+            return Err(SampoError::Config(
+                "signing-method signs the checksums file, so it requires release-checksums: true"
+                    .to_string(),
+            )
+            .into());
+        }
+        Ok(())
     }
 }
 
@@ -317,9 +546,16 @@ fn run() -> Result<()> {
     let workspace = determine_workspace(&config)?;
 
     let repo_config = SampoConfig::load(&workspace).unwrap_or_default();
+    config.validate(&repo_config)?;
 
+    // Packages with their own `[packages."<package>".git]` branches release from those.
     let branch = current_branch()?;
-    if !repo_config.is_release_branch(&branch) {
+    let package_branch = discover_workspace(&workspace).is_ok_and(|ws| {
+        ws.members
+            .iter()
+            .any(|info| repo_config.for_package(info).is_release_branch(&branch))
+    });
+    if !repo_config.is_release_branch(&branch) && !package_branch {
         return Err(SampoError::Release(format!(
             "Branch '{}' is not listed in git.release_branches (allowed: {:?})",
             branch,
@@ -375,21 +611,40 @@ fn execute_operations(
     match config.mode {
         Mode::Auto => {
             let plan = sampo::capture_release_plan(workspace)?;
-            if plan.has_changes {
+            if plan.has_changes && config.release_strategy == ReleaseStrategy::Commit {
+                println!(
+                    "Detected {} pending release package(s); committing release to '{}'.",
+                    plan.releases.len(),
+                    branch
+                );
+                (released, published) =
+                    commit_release_and_publish(workspace, config, branch, &plan)?;
+            } else if plan.has_changes {
                 println!(
                     "Detected {} pending release package(s); preparing release PR.",
                     plan.releases.len()
                 );
                 let plan_requires_stabilize = plan_includes_prerelease(&plan.releases);
                 let github_client = create_github_client()?;
-                let release_prepared = prepare_release_pr(
-                    workspace,
-                    config,
-                    repo_config,
-                    branch,
-                    &github_client,
-                    Some(plan),
-                )?;
+                let release_prepared = if config.release_pr_grouping == ReleasePrGrouping::Single {
+                    prepare_release_pr(
+                        workspace,
+                        config,
+                        repo_config,
+                        branch,
+                        &github_client,
+                        Some(plan),
+                        None,
+                    )?
+                } else {
+                    prepare_grouped_release_prs(
+                        workspace,
+                        config,
+                        repo_config,
+                        branch,
+                        &github_client,
+                    )?
+                };
                 let stabilize_prepared = if release_prepared && plan_requires_stabilize {
                     prepare_stabilize_pr(workspace, config, repo_config, branch, &github_client)?
                 } else {
@@ -423,21 +678,7 @@ fn execute_operations(
                     "No pending changesets found on branch '{}'. Checking for merged releases to publish.",
                     branch
                 );
-                let github_options = GitHubReleaseOptions::from_config(config);
-                let github_client = if github_options.create_github_release {
-                    Some(create_github_client()?)
-                } else {
-                    None
-                };
-                let extra_args = build_publish_extra_args(config);
-                published = post_merge_publish(
-                    workspace,
-                    config.dry_run,
-                    &extra_args,
-                    config.cargo_token.as_deref(),
-                    &github_options,
-                    github_client.as_ref(),
-                )?;
+                published = publish_with_config(workspace, config, None)?;
             }
         }
         Mode::Release => {
@@ -445,34 +686,204 @@ fn execute_operations(
             released = true;
         }
         Mode::Publish => {
-            let github_options = GitHubReleaseOptions::from_config(config);
-            let github_client = if github_options.create_github_release {
-                Some(create_github_client()?)
-            } else {
-                None
-            };
-            let extra_args = build_publish_extra_args(config);
-            published = post_merge_publish(
-                workspace,
-                config.dry_run,
-                &extra_args,
-                config.cargo_token.as_deref(),
-                &github_options,
-                github_client.as_ref(),
-            )?;
+            published = publish_with_config(workspace, config, None)?;
         }
     }
 
     Ok((released, published))
 }
 
+/// Run `post_merge_publish` with the GitHub options and publish arguments from the inputs.
+fn publish_with_config(
+    workspace: &Path,
+    config: &Config,
+    consumed_changesets: Option<&[PathBuf]>,
+) -> Result<bool> {
+    let github_options = GitHubReleaseOptions::from_config(config);
+    let github_client = if github_options.create_github_release
+        || github_options.api_tags
+        || github_options.comment_released
+    {
+        Some(create_github_client()?)
+    } else {
+        None
+    };
+    let publish = PublishInputs {
+        dry_run: config.dry_run,
+        extra_args: build_publish_extra_args(config),
+        options: PublishOptions {
+            trusted_publishing: config.trusted_publishing,
+            provenance: config.provenance,
+            ..Default::default()
+        },
+        cargo_token: config.cargo_token.as_deref(),
+    };
+    post_merge_publish(
+        workspace,
+        &publish,
+        &github_options,
+        github_client.as_ref(),
+        consumed_changesets,
+    )
+}
+
+/// Commit the pending release straight to the release branch, then publish it.
+///
+/// Refuses to release when `origin/<branch>` moved past the checked-out commit, and
+/// pushes without `--force` so a commit landing in the meantime fails the run instead
+/// of being overwritten. When the workflow is triggered by a release commit created
+/// here, the release step is skipped and only the publish step runs.
+fn commit_release_and_publish(
+    workspace: &Path,
+    config: &Config,
+    branch: &str,
+    plan: &ReleasePlan,
+) -> Result<(bool, bool)> {
+    let head_message = git::git_output(&["log", "-1", "--format=%B"], workspace)?;
+    if is_release_commit(&head_message) {
+        println!("HEAD is a release commit created by Sampo; skipping release step.");
+        return Ok((false, publish_with_config(workspace, config, None)?));
+    }
+
+    // The `commit-message` input wins over `git.release_commit_message`.
+    let template = config.commit_message.clone().or_else(|| {
+        SampoConfig::load(workspace)
+            .ok()
+            .map(|cfg| cfg.git_release_commit_message)
+    });
+    let commit_message = render_release_commit_message(template.as_deref(), &plan.releases);
+
+    if config.dry_run {
+        sampo::run_release(workspace, true, config.cargo_token.as_deref())?;
+        println!(
+            "Would commit and push the release to '{}' (skipped in dry-run mode):\n{}",
+            branch, commit_message
+        );
+        // Nothing was committed nor pushed.
+        return Ok((false, false));
+    }
+
+    git::setup_bot_user(workspace)?;
+    git::git(&["fetch", "origin", branch], Some(workspace))?;
+    let local_head = git::git_output(&["rev-parse", "HEAD"], workspace)?;
+    let remote_head = git::git_output(&["rev-parse", &format!("origin/{}", branch)], workspace)?;
+    if local_head != remote_head {
+        return Err(ActionError::SampoCommandFailed {
+            operation: "release-commit".to_string(),
+            message: format!(
+                "origin/{} ({}) does not match the checked-out commit ({}); another push landed in the meantime. The next workflow run will release from the latest commit.",
+                branch, remote_head, local_head
+            ),
+        });
+    }
+
+    let consumed_changesets = sampo::run_release(workspace, false, config.cargo_token.as_deref())?;
+
+    if !git::has_changes(workspace)? {
+        println!("No file changes after release. Skipping commit.");
+        return Ok((false, false));
+    }
+
+    commit_and_push(
+        workspace,
+        config,
+        &commit_message,
+        branch,
+        false,
+        config.push_token.as_deref(),
+    )?;
+
+    println!(
+        "Pushed release commit for {} package(s) to '{}'.",
+        plan.releases.len(),
+        branch
+    );
+
+    let published = publish_with_config(workspace, config, Some(&consumed_changesets))?;
+    Ok((true, published))
+}
+
+/// Commit every working tree change on top of `HEAD` and push it to `target_branch`.
+///
+/// With `verified-commits`, the commit is created through the GitHub Git Data API
+/// instead (so GitHub signs it) and the local checkout is reset onto it afterwards.
+/// `push_token` overrides the credentials used for the push or API calls.
+fn commit_and_push(
+    workspace: &Path,
+    config: &Config,
+    message: &str,
+    target_branch: &str,
+    force: bool,
+    push_token: Option<&str>,
+) -> Result<()> {
+    git::git(&["add", "-A"], Some(workspace))?;
+
+    if config.verified_commits {
+        let client = match push_token {
+            Some(token) => github::GitHubClient::new(github_repository()?, token.to_string())?,
+            None => create_github_client()?,
+        };
+        let parent = git::git_output(&["rev-parse", "HEAD"], workspace)?;
+        let base_tree = git::git_output(&["rev-parse", "HEAD^{tree}"], workspace)?;
+        let changes = git::staged_changes(workspace)?;
+
+        let sha = client.create_commit(workspace, &parent, &base_tree, message, &changes)?;
+        client.update_branch(target_branch, &sha, force)?;
+
+        git::git(&["fetch", "origin", target_branch], Some(workspace))?;
+        git::git(&["reset", "--hard", &sha], Some(workspace))?;
+        println!(
+            "Created verified commit {} on '{}' through the GitHub API.",
+            sha, target_branch
+        );
+        return Ok(());
+    }
+
+    git::git(&["commit", "-m", message], Some(workspace))?;
+    match push_token {
+        Some(token) => git::push_with_token(workspace, &github_repository()?, token, target_branch),
+        None => {
+            let refspec = format!("HEAD:refs/heads/{}", target_branch);
+            let mut args = vec!["push", "origin", refspec.as_str()];
+            if force {
+                args.push("--force");
+            }
+            git::git(&args, Some(workspace))
+        }
+    }
+}
+
+/// Render the release commit message, expanding `{releases}` to `name@version` pairs
+/// and appending the trailer used to recognise Sampo's own release commits.
+fn render_release_commit_message(
+    template: Option<&str>,
+    releases: &BTreeMap<String, (String, String, String)>,
+) -> String {
+    let message = sampo_core::render_release_commit_message(
+        template.unwrap_or(DEFAULT_RELEASE_COMMIT_MESSAGE),
+        releases
+            .values()
+            .map(|(name, old, new)| (name.as_str(), old.as_str(), new.as_str())),
+    );
+    format!("{}\n\n{}", message, RELEASE_COMMIT_TRAILER)
+}
+
+fn is_release_commit(message: &str) -> bool {
+    message
+        .lines()
+        .any(|line| line.trim() == RELEASE_COMMIT_TRAILER)
+}
+
 /// Emit a GitHub Actions output
 fn emit_github_output(key: &str, value: bool) -> Result<()> {
-    let value_str = if value { "true" } else { "false" };
+    emit_github_output_value(key, if value { "true" } else { "false" })
+}
 
+/// Emit a single-line GitHub Actions output
+fn emit_github_output_value(key: &str, value: &str) -> Result<()> {
     if let Some(path) = std::env::var_os("GITHUB_OUTPUT") {
         let mut file = OpenOptions::new().create(true).append(true).open(path)?;
-        writeln!(file, "{}={}", key, value_str)?;
+        writeln!(file, "{}={}", key, value)?;
     }
 
     Ok(())
@@ -492,6 +903,78 @@ fn create_github_client() -> Result<github::GitHubClient> {
     github::GitHubClient::new(repo, token)
 }
 
+/// The `owner/repo` slug of the repository running the workflow
+fn github_repository() -> Result<String> {
+    std::env::var("GITHUB_REPOSITORY")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .ok_or(ActionError::GitHubCredentialsNotAvailable)
+}
+
+/// Prepare one release PR per group of pending changesets (see `release-pr-grouping`),
+/// then close the grouped release PRs left from groups that no longer exist.
+///
+/// Returns whether at least one PR was prepared.
+fn prepare_grouped_release_prs(
+    workspace: &Path,
+    config: &Config,
+    repo_config: &SampoConfig,
+    branch: &str,
+    github_client: &github::GitHubClient,
+) -> Result<bool> {
+    let groups = sampo::release_groups(workspace, repo_config, config.release_pr_grouping)?;
+    println!(
+        "Split pending changesets into {} release group(s).",
+        groups.len()
+    );
+
+    let mut prepared = false;
+    // Every group still has pending changesets: its PR stays open even when this run
+    // left it unchanged.
+    let mut current_branches = BTreeSet::new();
+    for group in &groups {
+        let plan = sampo::capture_release_plan_for(workspace, &group.selection)?;
+        current_branches.insert(release_pr_branch(
+            config,
+            branch,
+            plan_includes_prerelease(&plan.releases),
+            Some(group),
+        ));
+        if prepare_release_pr(
+            workspace,
+            config,
+            repo_config,
+            branch,
+            github_client,
+            Some(plan),
+            Some(group),
+        )? {
+            prepared = true;
+        }
+    }
+
+    // Only close the grouped release PRs this action opened, recognized by their marker.
+    let base_branch = config.base_branch.as_deref().unwrap_or(branch);
+    for pr in github_client.open_pull_requests(base_branch)? {
+        let obsolete = pr
+            .body
+            .as_deref()
+            .is_some_and(|body| body.contains(RELEASE_GROUP_MARKER))
+            && !current_branches.contains(&pr.head.branch);
+        if obsolete {
+            println!(
+                "Closing release PR #{} ({}): its release group has no pending changes.",
+                pr.number, pr.head.branch
+            );
+            github_client.close_pull_request(
+                pr.number,
+                "Closing this release PR: its changes are now released through another release PR, or no longer pending.",
+            )?;
+        }
+    }
+    Ok(prepared)
+}
+
 fn prepare_release_pr(
     workspace: &Path,
     config: &Config,
@@ -499,10 +982,12 @@ fn prepare_release_pr(
     branch: &str,
     github_client: &github::GitHubClient,
     provided_plan: Option<ReleasePlan>,
+    group: Option<&ReleaseGroup>,
 ) -> Result<bool> {
+    let selection = group.map(|g| g.selection.clone()).unwrap_or_default();
     let plan = match provided_plan {
         Some(plan) => plan,
-        None => sampo::capture_release_plan(workspace)?,
+        None => sampo::capture_release_plan_for(workspace, &selection)?,
     };
 
     if !plan.has_changes {
@@ -518,24 +1003,27 @@ fn prepare_release_pr(
         .clone()
         .unwrap_or_else(|| branch.to_string());
     let is_prerelease = plan_includes_prerelease(releases);
-    let pr_branch = config
-        .pr_branch
-        .clone()
-        .unwrap_or_else(|| default_pr_branch(branch, is_prerelease));
-    let pr_title = config
+    let pr_branch = release_pr_branch(config, branch, is_prerelease, group);
+    let mut pr_title = config
         .pr_title
         .clone()
         .unwrap_or_else(|| default_pr_title(branch, is_prerelease));
+    if let Some(group) = group {
+        pr_title = format!("{} ({})", pr_title, group.key);
+    }
 
     // Build PR body BEFORE running release (release will consume changesets)
     let pr_body = {
         // Load configuration for dependency explanations
-        let body = sampo::build_release_pr_body(workspace, releases, repo_config)?;
+        let body = sampo::build_release_pr_body_for(workspace, releases, repo_config, &selection)?;
         if body.trim().is_empty() {
             println!("No applicable package changes for PR body. Skipping PR creation.");
             return Ok(false);
         }
-        body
+        match group {
+            Some(_) => format!("{}\n\n{}\n", body.trim_end(), RELEASE_GROUP_MARKER),
+            None => body,
+        }
     };
 
     // Setup git
@@ -554,7 +1042,7 @@ fn prepare_release_pr(
     )?;
 
     // Apply release (no tags)
-    sampo::run_release(workspace, false, config.cargo_token.as_deref())?;
+    sampo::run_release_for(workspace, false, config.cargo_token.as_deref(), &selection)?;
 
     // Check for changes and commit
     if !git::has_changes(workspace)? {
@@ -563,20 +1051,14 @@ fn prepare_release_pr(
         return Ok(false);
     }
 
-    git::git(&["add", "-A"], Some(workspace))?;
-    git::git(
-        &[
-            "commit",
-            "-m",
-            "chore(release): bump versions and changelogs",
-        ],
-        Some(workspace),
-    )?;
-
     // Force push to release branch (overwrites any existing branch)
-    git::git(
-        &["push", "origin", &format!("HEAD:{}", pr_branch), "--force"],
-        Some(workspace),
+    commit_and_push(
+        workspace,
+        config,
+        "chore(release): bump versions and changelogs",
+        &pr_branch,
+        true,
+        None,
     )?;
 
     // Create PR
@@ -593,6 +1075,45 @@ fn prepare_release_pr(
     Ok(true)
 }
 
+/// Hidden marker in the body of grouped release PRs, so obsolete ones can be closed
+/// without touching PRs opened by hand from similarly named branches.
+const RELEASE_GROUP_MARKER: &str = "<!-- sampo-release-group -->";
+
+/// The branch of the release PR, derived from the group key for grouped release PRs.
+fn release_pr_branch(
+    config: &Config,
+    branch: &str,
+    is_prerelease: bool,
+    group: Option<&ReleaseGroup>,
+) -> String {
+    let pr_branch = config
+        .pr_branch
+        .clone()
+        .unwrap_or_else(|| default_pr_branch(branch, is_prerelease));
+    match group {
+        Some(group) => group_pr_branch(&pr_branch, &group.key),
+        None => pr_branch,
+    }
+}
+
+/// Derive the branch of a grouped release PR from the base release PR branch.
+///
+/// The key is appended with `-` rather than `/` so the branch cannot clash with the
+/// ungrouped one as a ref directory.
+fn group_pr_branch(base: &str, key: &str) -> String {
+    let suffix: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{}-{}", base, suffix.trim_matches('-'))
+}
+
 fn prepare_stabilize_pr(
     workspace: &Path,
     config: &Config,
@@ -668,19 +1189,13 @@ fn prepare_stabilize_pr(
         return Ok(false);
     }
 
-    git::git(&["add", "-A"], Some(workspace))?;
-    git::git(
-        &[
-            "commit",
-            "-m",
-            "chore(release): stabilize versions and changelogs",
-        ],
-        Some(workspace),
-    )?;
-
-    git::git(
-        &["push", "origin", &format!("HEAD:{}", pr_branch), "--force"],
-        Some(workspace),
+    commit_and_push(
+        workspace,
+        config,
+        "chore(release): stabilize versions and changelogs",
+        &pr_branch,
+        true,
+        None,
     )?;
 
     github_client.ensure_pull_request(&pr_branch, &base_branch, &pr_title, &pr_body)?;
@@ -765,38 +1280,110 @@ fn build_publish_extra_args(config: &Config) -> PublishExtraArgs {
     }
 }
 
+/// How `post_merge_publish` runs `sampo publish`, from the action inputs.
+struct PublishInputs<'a> {
+    dry_run: bool,
+    extra_args: PublishExtraArgs,
+    options: PublishOptions,
+    cargo_token: Option<&'a str>,
+}
+
 /// Run `sampo publish` and handle the post-merge duties (tag push, GitHub releases).
 /// Returns true only when new tags were created/pushed, so the workflow can tell if a
 /// real publish happened. Combined with `sampo_core::run_publish` (which skips crates
 /// already published or marked `publish = false`), this prevents accidental publishes
 /// on commits sans changesets: the action simply logs "No new tags" and exits.
+///
+/// `consumed_changesets` lists the changesets behind the published versions; when
+/// `None`, they are recovered from the last release commit on the current branch.
 fn post_merge_publish(
     workspace: &Path,
-    dry_run: bool,
-    extra_args: &PublishExtraArgs,
-    cargo_token: Option<&str>,
+    publish: &PublishInputs,
     github_options: &GitHubReleaseOptions,
     github_client: Option<&github::GitHubClient>,
+    consumed_changesets: Option<&[PathBuf]>,
 ) -> Result<bool> {
+    // Tags created through the API would drop the signatures `git.sign_tags` asks for.
+    if github_options.api_tags
+        && SampoConfig::load(workspace).is_ok_and(|config| config.git_sign_tags.is_some())
+    {
+        return Err(ActionError::SampoCommandFailed {
+            operation: "publish".to_string(),
+            message: "verified-commits creates tags through the GitHub API, which cannot sign them; disable it or unset git.sign_tags"
+                .to_string(),
+        });
+    }
+
     // Setup git identity for tag creation
     git::setup_bot_user(workspace)?;
 
     // Publish and get information about tags created/would-be-created
-    let publish_output = sampo::run_publish(workspace, dry_run, extra_args, cargo_token)?;
-    let new_tags = publish_output.tags;
+    let dry_run = publish.dry_run;
+    let (publish_output, publish_failure) = sampo::run_publish(
+        workspace,
+        dry_run,
+        &publish.extra_args,
+        &publish.options,
+        publish.cargo_token,
+    )?;
+    let report =
+        serde_json::to_string(&publish_output).map_err(|e| ActionError::SampoCommandFailed {
+            operation: "publish-report".to_string(),
+            message: format!("Failed to serialize the publish report: {}", e),
+        })?;
+    emit_github_output_value("publish-report", &report)?;
+    // A publish failing part way still pushes and releases the tags it created, then fails.
+    let pushed_tags = publish_output.tags;
+    let floating_tags = publish_output.floating_tags;
+    // Floating tags move with each release: they are pushed, but get no release of their own.
+    let new_tags: Vec<String> = pushed_tags
+        .iter()
+        .filter(|tag| !floating_tags.contains(tag))
+        .cloned()
+        .collect();
 
-    if !dry_run && !new_tags.is_empty() {
-        println!("Pushing {} new tags", new_tags.len());
-        for tag in &new_tags {
-            git::git(&["push", "origin", tag], Some(workspace))?;
+    if !dry_run && !pushed_tags.is_empty() {
+        match github_client.filter(|_| github_options.api_tags) {
+            Some(client) => {
+                println!(
+                    "Creating {} new tags through the GitHub API",
+                    pushed_tags.len()
+                );
+                for tag in &pushed_tags {
+                    let target =
+                        git::git_output(&["rev-parse", &format!("{}^{{commit}}", tag)], workspace)?;
+                    if floating_tags.contains(tag) {
+                        client.move_tag(tag, &target)?;
+                        continue;
+                    }
+                    let message =
+                        git::git_output(&["tag", "-l", "--format=%(contents)", tag], workspace)?;
+                    client.create_annotated_tag(tag, &message, &target)?;
+                }
+            }
+            None => {
+                println!("Pushing {} new tags", pushed_tags.len());
+                for tag in &pushed_tags {
+                    if floating_tags.contains(tag) {
+                        let refspec = format!("refs/tags/{}", tag);
+                        git::git(&["push", "--force", "origin", &refspec], Some(workspace))?;
+                    } else {
+                        git::git(&["push", "origin", tag], Some(workspace))?;
+                    }
+                }
+            }
         }
-    } else if dry_run && !new_tags.is_empty() {
+    } else if dry_run && !pushed_tags.is_empty() {
         println!(
             "Would push {} new tags (skipped in dry-run mode)",
-            new_tags.len()
+            pushed_tags.len()
         );
-        for tag in &new_tags {
-            println!("  - {}", tag);
+        for tag in &pushed_tags {
+            if floating_tags.contains(tag) {
+                println!("  - {} (floating, force-pushed)", tag);
+            } else {
+                println!("  - {}", tag);
+            }
         }
     }
 
@@ -805,9 +1392,20 @@ fn post_merge_publish(
         && !new_tags.is_empty()
         && let Some(client) = github_client
     {
-        for tag in &new_tags {
+        let release_tags = if github_options.umbrella_release {
+            vec![umbrella_release_tag(workspace, &new_tags)]
+        } else {
+            new_tags.clone()
+        };
+        for tag in &release_tags {
+            // An umbrella release carries the provenance of every package it covers.
+            let provenance: Vec<&PathBuf> = if github_options.umbrella_release {
+                publish_output.provenance.values().collect()
+            } else {
+                publish_output.provenance.get(tag).into_iter().collect()
+            };
             println!("Creating GitHub release for {}", tag);
-            create_github_release_for_tag(client, tag, workspace, github_options)?;
+            create_github_release_for_tag(client, tag, workspace, github_options, &provenance)?;
         }
     } else if dry_run && github_options.create_github_release && !new_tags.is_empty() {
         println!(
@@ -819,6 +1417,22 @@ fn post_merge_publish(
         }
     }
 
+    if let Some(failure) = publish_failure {
+        return Err(failure);
+    }
+
+    if !dry_run
+        && github_options.comment_released
+        && !new_tags.is_empty()
+        && let Some(client) = github_client
+    {
+        let consumed = match consumed_changesets {
+            Some(paths) => paths.to_vec(),
+            None => git::last_consumed_changesets(workspace)?,
+        };
+        notify_released_pull_requests(client, workspace, &new_tags, &consumed, github_options);
+    }
+
     let published = !dry_run && !new_tags.is_empty();
     if !published && !dry_run {
         println!("No new tags were created during publish.");
@@ -827,18 +1441,165 @@ fn post_merge_publish(
     Ok(published)
 }
 
+/// Comment "Released in ..." on the pull requests that introduced the consumed
+/// changesets, label them, and optionally comment on the issues they close.
+///
+/// Failures only emit warnings: packages are already published at this point.
+fn notify_released_pull_requests(
+    github_client: &github::GitHubClient,
+    workspace: &Path,
+    tags: &[String],
+    consumed_changesets: &[PathBuf],
+    github_options: &GitHubReleaseOptions,
+) {
+    let config = SampoConfig::load(workspace).ok();
+    let allowed_tags = config
+        .as_ref()
+        .map(|cfg| cfg.changesets_tags.clone())
+        .unwrap_or_default();
+    let repo = github_repository().ok();
+
+    // (package name, version, tag) for every tag created by this publish
+    let released: Vec<(String, String, &String)> = tags
+        .iter()
+        .filter_map(|tag| {
+            parse_tag_with_config(tag, config.as_ref()).map(|(name, version)| (name, version, tag))
+        })
+        .collect();
+
+    let mut mentions_by_pr: BTreeMap<u64, (Option<String>, BTreeSet<String>)> = BTreeMap::new();
+    for path in consumed_changesets {
+        let Some(commit) = get_commit_hash_for_path(workspace, path) else {
+            continue;
+        };
+        let packages: BTreeSet<String> = git::show_file_at(workspace, &commit, path)
+            .ok()
+            .and_then(|text| parse_changeset(&text, path, &allowed_tags).ok().flatten())
+            .map(|changeset| {
+                changeset
+                    .entries
+                    .iter()
+                    .map(|(spec, _bump, _tag)| spec.name.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mentions: Vec<String> = released
+            .iter()
+            .filter(|(name, _, _)| packages.contains(name))
+            .map(|(name, version, tag)| {
+                release_mention(
+                    name,
+                    version,
+                    tag,
+                    repo.as_deref()
+                        .filter(|_| github_options.create_github_release),
+                )
+            })
+            .collect();
+        if mentions.is_empty() {
+            continue;
+        }
+
+        match github_client.pull_requests_for_commit(&commit) {
+            Ok(pull_requests) => {
+                for pr in pull_requests {
+                    let entry = mentions_by_pr
+                        .entry(pr.number)
+                        .or_insert_with(|| (pr.body.clone(), BTreeSet::new()));
+                    entry.1.extend(mentions.iter().cloned());
+                }
+            }
+            Err(e) => eprintln!(
+                "Warning: Failed to find pull requests for commit {}: {}",
+                commit, e
+            ),
+        }
+    }
+
+    for (number, (body, mentions)) in mentions_by_pr {
+        let comment = format!(
+            "Released in {}.",
+            mentions.into_iter().collect::<Vec<_>>().join(", ")
+        );
+        match github_client.comment_on_issue(number, &comment) {
+            Ok(()) => println!("Commented on pull request #{}", number),
+            Err(e) => eprintln!(
+                "Warning: Failed to comment on pull request #{}: {}",
+                number, e
+            ),
+        }
+
+        if let Some(label) = github_options.released_label.as_deref()
+            && let Err(e) = github_client.add_label(number, label)
+        {
+            eprintln!(
+                "Warning: Failed to add label '{}' to pull request #{}: {}",
+                label, number, e
+            );
+        }
+
+        if github_options.comment_linked_issues {
+            for issue in closing_issue_references(body.as_deref().unwrap_or_default()) {
+                if let Err(e) = github_client.comment_on_issue(issue, &comment) {
+                    eprintln!("Warning: Failed to comment on issue #{}: {}", issue, e);
+                }
+            }
+        }
+    }
+}
+
+/// Render `` `name@version` ``, linked to the GitHub release when a repository is given.
+fn release_mention(name: &str, version: &str, tag: &str, repo: Option<&str>) -> String {
+    match repo {
+        Some(repo) => format!(
+            "[`{}@{}`](https://github.com/{}/releases/tag/{})",
+            name, version, repo, tag
+        ),
+        None => format!("`{}@{}`", name, version),
+    }
+}
+
+/// Issue numbers referenced with a closing keyword (`Closes #12`, `fixes: #3`, ...).
+fn closing_issue_references(body: &str) -> Vec<u64> {
+    const KEYWORDS: [&str; 9] = [
+        "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved",
+    ];
+
+    let words: Vec<&str> = body.split_whitespace().collect();
+    let mut issues = Vec::new();
+    for pair in words.windows(2) {
+        let keyword = pair[0].trim_end_matches(':').to_ascii_lowercase();
+        if !KEYWORDS.contains(&keyword.as_str()) {
+            continue;
+        }
+        let Some(reference) = pair[1].strip_prefix('#') else {
+            continue;
+        };
+        let digits: String = reference.chars().take_while(char::is_ascii_digit).collect();
+        if let Ok(number) = digits.parse::<u64>()
+            && !issues.contains(&number)
+        {
+            issues.push(number);
+        }
+    }
+    issues
+}
+
 fn create_github_release_for_tag(
     github_client: &github::GitHubClient,
     tag: &str,
     workspace: &Path,
     github_options: &GitHubReleaseOptions,
+    provenance: &[&PathBuf],
 ) -> Result<()> {
     let config = SampoConfig::load(workspace).ok();
 
-    let body = match build_release_body_from_changelog(workspace, tag) {
-        Some(body) => body,
-        None => format!("Automated release for tag {}", tag),
-    };
+    let body =
+        match build_release_body_from_changelog(workspace, tag, github_options.umbrella_release) {
+            Some(body) => body,
+            None => format!("Automated release for tag {}", tag),
+        };
 
     let upload_url = match github_client.create_release(
         tag,
@@ -856,7 +1617,23 @@ fn create_github_release_for_tag(
     };
 
     if !upload_url.is_empty() {
-        let assets = resolve_release_assets(workspace, tag, &github_options.asset_specs)?;
+        let mut assets = resolve_release_assets(workspace, tag, &github_options.asset_specs)?;
+        for statement in provenance {
+            if let Some(name) = statement.file_name() {
+                assets.push(ResolvedAsset {
+                    path: statement.to_path_buf(),
+                    asset_name: name.to_string_lossy().into_owned(),
+                });
+            }
+        }
+        if github_options.supply_chain.is_enabled() {
+            let generated = generate_supply_chain_assets(workspace, tag, &assets, github_options);
+            assets.extend(supply_chain_assets_or_warn(
+                generated,
+                tag,
+                &github_options.supply_chain,
+            )?);
+        }
         if assets.is_empty() {
             if !github_options.asset_specs.is_empty() {
                 eprintln!(
@@ -909,37 +1686,153 @@ fn create_github_release_for_tag(
     Ok(())
 }
 
-/// Build a release body by extracting the matching section from the crate's CHANGELOG.md
-fn build_release_body_from_changelog(workspace: &Path, tag: &str) -> Option<String> {
+/// The generated supply-chain assets, or none with a warning when generating them failed.
+/// A failure is an error when signing is configured: uploading the assets anyway would
+/// ship them without the signature users are told to verify.
+fn supply_chain_assets_or_warn(
+    generated: Result<Vec<ResolvedAsset>>,
+    tag: &str,
+    options: &supply_chain::SupplyChainOptions,
+) -> Result<Vec<ResolvedAsset>> {
+    match generated {
+        Ok(generated) => Ok(generated),
+        Err(e) if options.signing_method != supply_chain::SigningMethod::None => {
+            Err(ActionError::SampoCommandFailed {
+                operation: "sign-checksums".to_string(),
+                message: format!("Not uploading the release assets of {}: {}", tag, e),
+            })
+        }
+        Err(e) => {
+            eprintln!(
+                "Warning: Failed to generate checksums/SBOM for {}: {}",
+                tag, e
+            );
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Generate the checksums, signature and SBOM configured for a release.
+fn generate_supply_chain_assets(
+    workspace: &Path,
+    tag: &str,
+    assets: &[ResolvedAsset],
+    github_options: &GitHubReleaseOptions,
+) -> Result<Vec<ResolvedAsset>> {
     let config = SampoConfig::load(workspace).ok();
-    let (crate_name, version) = parse_tag_with_config(tag, config.as_ref())?;
+    let parsed_tag = parse_tag_with_config(tag, config.as_ref());
+    let crate_name = parsed_tag.as_ref().map(|(name, _)| name.as_str());
+    let version = parsed_tag.as_ref().map(|(_, ver)| ver.as_str());
+    let ws = discover_workspace(workspace).map_err(|e| ActionError::SampoCommandFailed {
+        operation: "workspace-discovery".to_string(),
+        message: e.to_string(),
+    })?;
+    // Names can repeat across ecosystems, so the SBOM root is the package the tag was built for.
+    let default_config = SampoConfig::default();
+    let package = config
+        .as_ref()
+        .unwrap_or(&default_config)
+        .find_tagged_package(&ws.root, &ws.members, tag)
+        .map(|(info, _)| info.canonical_identifier());
+
+    supply_chain::generate_release_metadata(
+        &ws,
+        tag,
+        package,
+        assets,
+        &github_options.supply_chain,
+        &supply_chain::output_dir(tag)?,
+        |template| render_asset_template(template, tag, crate_name, version),
+    )
+}
+
+/// Build a release body by extracting the matching section from the crate's CHANGELOG.md,
+/// or the latest section of the `[changelog] aggregate` file for an umbrella release.
+fn build_release_body_from_changelog(
+    workspace: &Path,
+    tag: &str,
+    umbrella: bool,
+) -> Option<String> {
+    let config = SampoConfig::load(workspace).ok();
+    if umbrella
+        && let Some(aggregate) = config
+            .as_ref()
+            .and_then(|cfg| cfg.changelog_aggregate.as_deref())
+    {
+        return extract_changelog_section(&workspace.join(aggregate), None);
+    }
+    let ws = discover_workspace(workspace).ok();
+    let default_config = SampoConfig::default();
+    let cfg = config.as_ref().unwrap_or(&default_config);
+
+    // Templates keyed on `{path}` or `{package_slug}` only resolve against the members.
+    let tagged = ws
+        .as_ref()
+        .and_then(|ws| cfg.find_tagged_package(&ws.root, &ws.members, tag));
+    let (crate_name, version) = match tagged {
+        Some((info, version)) => (info.name.clone(), version),
+        None => parse_tag_with_config(tag, Some(cfg))?,
+    };
 
     // Find crate directory by name using the workspace API
-    let ws = discover_workspace(workspace).ok()?;
-    let crate_dir = ws
-        .members
-        .iter()
-        .find(|c| c.name == crate_name)
-        .map(|c| c.path.clone())
+    let changelog = match ws
+        .as_ref()
+        .and_then(|ws| ws.members.iter().find(|c| c.name == crate_name))
+    {
+        Some(info) => {
+            let changelog_path = cfg.for_package(info).changelog_path;
+            info.path.join(changelog_path)
+        }
         // Fallback to a conventional path if discovery failed to find it
-        .unwrap_or_else(|| workspace.join("crates").join(&crate_name));
+        None => workspace
+            .join("crates")
+            .join(&crate_name)
+            .join(DEFAULT_CHANGELOG_PATH),
+    };
+
+    extract_changelog_section(&changelog, Some(&version))
+}
 
-    let changelog = crate_dir.join("CHANGELOG.md");
-    extract_changelog_section(&changelog, &version)
+/// The tag an umbrella release is attached to: the `changelog.aggregate_version`
+/// package's tag when it was published, the first new tag otherwise.
+fn umbrella_release_tag(workspace: &Path, tags: &[String]) -> String {
+    let config = SampoConfig::load(workspace).ok();
+    let product = config
+        .as_ref()
+        .and_then(|cfg| cfg.changelog_aggregate_version.as_deref())
+        .and_then(|package| PackageSpecifier::parse(package).ok())
+        .map(|spec| spec.name);
+    tags.iter()
+        .find(|tag| {
+            product.as_deref().is_some_and(|product| {
+                parse_tag_with_config(tag, config.as_ref()).is_some_and(|(name, _)| name == product)
+            })
+        })
+        .unwrap_or(&tags[0])
+        .clone()
 }
 
 /// Parse a tag through the configured templates (or the default templates
-/// when no config is available).
+/// when no config is available). A tag the templates can't attribute to one
+/// package is reported and left unparsed.
 fn parse_tag_with_config(tag: &str, config: Option<&SampoConfig>) -> Option<(String, String)> {
-    match config {
+    let parsed = match config {
         Some(cfg) => cfg.parse_tag(tag),
         None => SampoConfig::default().parse_tag(tag),
-    }
+    };
+    parsed.unwrap_or_else(|e| {
+        eprintln!("Warning: {e}");
+        None
+    })
 }
 
 fn tag_is_prerelease_with_config(tag: &str, config: Option<&SampoConfig>) -> bool {
-    parse_tag_with_config(tag, config)
-        .and_then(|(_name, version)| Version::parse(&version).ok())
+    let default_config = SampoConfig::default();
+    config
+        .unwrap_or(&default_config)
+        .match_tag(tag)
+        .and_then(|captured| captured.version)
+        .and_then(|version| Version::parse(&version).ok())
         .map(|parsed| !parsed.pre.is_empty())
         .unwrap_or_else(|| {
             // Fallback: try to parse version directly from short tag format
@@ -953,34 +1846,6 @@ fn tag_is_prerelease_with_config(tag: &str, config: Option<&SampoConfig>) -> boo
         })
 }
 
-/// Extract the section that follows the first `##` heading until the next `##` or EOF.
-///
-/// The leading heading itself is stripped because the GitHub release title already
-/// conveys the version.
-fn extract_changelog_section(path: &Path, _version: &str) -> Option<String> {
-    let text = std::fs::read_to_string(path).ok()?;
-    let mut collecting = false;
-    let mut collected = Vec::new();
-
-    for line in text.lines() {
-        let trimmed = line.trim_start();
-        if trimmed.starts_with("## ") {
-            if collecting {
-                break;
-            }
-            collecting = true;
-            continue;
-        }
-
-        if collecting {
-            collected.push(line);
-        }
-    }
-
-    let body = collected.join("\n").trim().to_string();
-    if body.is_empty() { None } else { Some(body) }
-}
-
 fn parse_asset_specs(input: Option<&str>) -> Vec<AssetSpec> {
     input
         .map(|raw| {
@@ -1177,6 +2042,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn umbrella_release_uses_aggregate_changelog() {
+        use std::fs;
+
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".sampo")).unwrap();
+        fs::write(
+            temp.path().join(".sampo/config.toml"),
+            "[changelog]\naggregate = \"RELEASES.md\"\naggregate_version = \"cargo/app\"\n",
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("RELEASES.md"),
+            "# Changelog\n\n## 2.0.0\n\n### app 2.0.0\n\n#### Major changes\n\n- new\n\n## 1.0.0\n\n- old\n",
+        )
+        .unwrap();
+
+        let body = build_release_body_from_changelog(temp.path(), "lib-v0.3.0", true).unwrap();
+        assert_eq!(body, "### app 2.0.0\n\n#### Major changes\n\n- new");
+
+        let tags = vec!["lib-v0.3.0".to_string(), "app-v2.0.0".to_string()];
+        assert_eq!(umbrella_release_tag(temp.path(), &tags), "app-v2.0.0");
+        assert_eq!(umbrella_release_tag(temp.path(), &tags[..1]), "lib-v0.3.0");
+    }
+
     #[test]
     fn collect_prerelease_packages_detects_members() {
         use std::fs;
@@ -1271,9 +2161,106 @@ mod tests {
     }
 
     #[test]
-    fn test_determine_workspace_with_config_override() {
-        let config = Config {
-            working_directory: Some(PathBuf::from("/test/path")),
+    fn closing_issue_references_detects_keywords() {
+        let body =
+            "Adds a flag.\n\nCloses #12, fixes: #3 and resolves #12.\nRelated to #7.\nFIXED #40";
+        assert_eq!(closing_issue_references(body), vec![12, 3, 40]);
+        assert!(closing_issue_references("See #5").is_empty());
+    }
+
+    #[test]
+    fn release_mention_links_to_release_when_repo_known() {
+        assert_eq!(
+            release_mention("sampo-core", "0.17.0", "cargo-sampo-core-v0.17.0", None),
+            "`sampo-core@0.17.0`"
+        );
+        assert_eq!(
+            release_mention(
+                "sampo-core",
+                "0.17.0",
+                "cargo-sampo-core-v0.17.0",
+                Some("bruits/sampo")
+            ),
+            "[`sampo-core@0.17.0`](https://github.com/bruits/sampo/releases/tag/cargo-sampo-core-v0.17.0)"
+        );
+    }
+
+    #[test]
+    fn group_pr_branch_appends_sanitized_key() {
+        assert_eq!(
+            group_pr_branch("release/main", "cargo/a+npm/@scope/b"),
+            "release/main-cargo-a-npm--scope-b"
+        );
+        assert_eq!(
+            group_pr_branch("release/main", "cargo"),
+            "release/main-cargo"
+        );
+    }
+
+    #[test]
+    fn test_release_strategy_parsing() {
+        assert_eq!(ReleaseStrategy::parse("pr"), ReleaseStrategy::Pr);
+        assert_eq!(ReleaseStrategy::parse("Commit"), ReleaseStrategy::Commit);
+        assert_eq!(ReleaseStrategy::parse("unknown"), ReleaseStrategy::Pr);
+    }
+
+    #[test]
+    fn release_commit_message_lists_versions_and_trailer() {
+        let mut releases = BTreeMap::new();
+        releases.insert(
+            "cargo/a".to_string(),
+            ("a".to_string(), "0.1.0".to_string(), "0.2.0".to_string()),
+        );
+        releases.insert(
+            "npm/b".to_string(),
+            ("b".to_string(), "1.0.0".to_string(), "1.0.1".to_string()),
+        );
+
+        let message = render_release_commit_message(None, &releases);
+        assert_eq!(
+            message,
+            format!(
+                "chore(release): a@0.2.0, b@1.0.1\n\n{}",
+                RELEASE_COMMIT_TRAILER
+            )
+        );
+        assert!(is_release_commit(&message));
+
+        let custom =
+            render_release_commit_message(Some("release: {releases} [skip ci]"), &releases);
+        assert!(custom.starts_with("release: a@0.2.0, b@1.0.1 [skip ci]\n"));
+        assert!(!is_release_commit("chore(release): a@0.2.0"));
+    }
+
+    #[test]
+    fn failed_signing_blocks_the_upload() {
+        let failure = || {
+            Err(ActionError::SampoCommandFailed {
+                operation: "sign-checksums".to_string(),
+                message: "Signing SHA256SUMS failed".to_string(),
+            })
+        };
+        let mut options = supply_chain::SupplyChainOptions {
+            checksums: true,
+            checksums_name: supply_chain::DEFAULT_CHECKSUMS_NAME.to_string(),
+            signing_method: supply_chain::SigningMethod::Minisign,
+            signing_key: Some("key".to_string()),
+            signing_key_password: None,
+            sbom: supply_chain::SbomFormat::None,
+            sbom_name: None,
+        };
+
+        let err = supply_chain_assets_or_warn(failure(), "v1.0.0", &options).unwrap_err();
+        assert!(err.to_string().contains("Not uploading"), "{err}");
+
+        options.signing_method = supply_chain::SigningMethod::None;
+        let assets = supply_chain_assets_or_warn(failure(), "v1.0.0", &options).unwrap();
+        assert!(assets.is_empty());
+    }
+
+    fn test_config() -> Config {
+        Config {
+            working_directory: None,
             mode: Mode::Auto,
             dry_run: false,
             cargo_token: None,
@@ -1289,15 +2276,73 @@ mod tests {
             pr_title: None,
             stabilize_pr_branch: None,
             stabilize_pr_title: None,
+            release_strategy: ReleaseStrategy::Pr,
+            commit_message: None,
+            release_pr_grouping: ReleasePrGrouping::Single,
+            push_token: None,
+            verified_commits: false,
             create_github_release: false,
+            umbrella_release: false,
             open_discussion: DiscussionFilter::None,
             discussion_category: None,
             release_assets: None,
+            comment_released: false,
+            released_label: None,
+            comment_linked_issues: false,
+            release_checksums: false,
+            checksums_name: None,
+            signing_method: supply_chain::SigningMethod::None,
+            signing_key: None,
+            signing_key_password: None,
+            sbom: supply_chain::SbomFormat::None,
+            sbom_name: None,
+            trusted_publishing: None,
+            provenance: None,
+        }
+    }
+
+    #[test]
+    fn test_determine_workspace_with_config_override() {
+        let config = Config {
+            working_directory: Some(PathBuf::from("/test/path")),
+            ..test_config()
         };
         let result = determine_workspace(&config).unwrap();
         assert_eq!(result, PathBuf::from("/test/path"));
     }
 
+    #[test]
+    fn signing_requires_release_checksums() {
+        let config = Config {
+            signing_method: supply_chain::SigningMethod::Ssh,
+            ..test_config()
+        };
+        let err = config.validate(&SampoConfig::default()).unwrap_err();
+        assert!(err.to_string().contains("release-checksums"), "{err}");
+
+        let config = Config {
+            release_checksums: true,
+            ..config
+        };
+        assert!(config.validate(&SampoConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn umbrella_release_requires_an_aggregate_changelog() {
+        let config = Config {
+            umbrella_release: true,
+            ..test_config()
+        };
+        let err = config.validate(&SampoConfig::default()).unwrap_err();
+        assert!(err.to_string().contains("changelog.aggregate"), "{err}");
+
+        let repo_config = SampoConfig {
+            changelog_aggregate: Some("CHANGELOG.md".to_string()),
+            ..SampoConfig::default()
+        };
+        assert!(config.validate(&repo_config).is_ok());
+    }
+
     #[test]
     fn test_create_github_client() {
         // Test without environment variables
@@ -1641,7 +2686,7 @@ mod tests {
         let content = "# my-crate\n\n## 1.2.3 — 2024-06-17\n\n### Patch changes\n\n- Fix: foo\n\n## 1.2.2\n\n- Older";
         fs::write(&file, content).unwrap();
 
-        let got = extract_changelog_section(&file, "1.2.3").unwrap();
+        let got = extract_changelog_section(&file, Some("1.2.3")).unwrap();
         assert!(got.starts_with("### Patch changes"));
         assert!(!got.contains("## 1.2.3"));
         assert!(got.contains("Fix: foo"));
@@ -1659,7 +2704,7 @@ mod tests {
             "# my-crate\n\n## 2.0.0\n\n- New feature\n\n## 1.9.0 — 2023-12-01\n\n- Previous";
         fs::write(&file, content).unwrap();
 
-        let got = extract_changelog_section(&file, "2.0.0").unwrap();
+        let got = extract_changelog_section(&file, Some("2.0.0")).unwrap();
         assert!(got.starts_with("- New feature"));
         assert!(!got.contains("## 2.0.0"));
         assert!(!got.contains("1.9.0"));
@@ -1,17 +1,20 @@
 use crate::error::{ActionError, Result};
+use sampo_core::errors::SampoError;
 use sampo_core::format_markdown_list_item;
+use sampo_core::trusted_publishing::{OIDC_REQUEST_URL_ENV, OidcProvider};
 use sampo_core::types::{
     ChangelogCategory, PackageSpecifier, SpecResolution, format_ambiguity_options,
 };
 use sampo_core::{
-    AcknowledgmentStyle, Config, PublishExtraArgs, PublishOutput,
+    AcknowledgmentStyle, Config, PublishExtraArgs, PublishOptions, PublishOutput, ReleaseSelection,
     detect_all_dependency_explanations, detect_github_repo_slug_with_config, discover_workspace,
-    enrich_changeset_message, get_commit_hash_for_path, load_changesets,
-    run_publish as core_publish, run_release as core_release,
-    run_stabilize_release as core_stabilize_release,
+    enrich_changeset_message, get_commit_hash_for_path, infer_bump_from_versions, load_changesets,
+    plan_release, plan_stabilize_release, resolve_fixed_groups,
+    run_publish_with_options as core_publish, run_release_with_selection as core_release,
+    run_stabilize_release as core_stabilize_release, should_ignore_package,
 };
-use std::collections::BTreeMap;
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 
 fn set_cargo_env_var(value: &str) {
     unsafe {
@@ -28,8 +31,17 @@ pub struct ReleasePlan {
 
 /// Run sampo release and capture the plan
 pub fn capture_release_plan(workspace: &Path) -> Result<ReleasePlan> {
+    capture_release_plan_for(workspace, &ReleaseSelection::default())
+}
+
+/// Capture the release plan of the changesets covered by `selection`
+pub fn capture_release_plan_for(
+    workspace: &Path,
+    selection: &ReleaseSelection,
+) -> Result<ReleasePlan> {
+    // Only the plan is read here, so the `[hooks]` are left for the actual release.
     let release_output =
-        core_release(workspace, true).map_err(|e| ActionError::SampoCommandFailed {
+        plan_release(workspace, selection).map_err(|e| ActionError::SampoCommandFailed {
             operation: "release-plan".to_string(),
             message: format!("Release plan failed: {}", e),
         })?;
@@ -48,43 +60,91 @@ pub fn capture_release_plan(workspace: &Path) -> Result<ReleasePlan> {
     })
 }
 
-/// Execute sampo release
-pub fn run_release(workspace: &Path, dry_run: bool, cargo_token: Option<&str>) -> Result<()> {
+/// Execute sampo release and return the changeset files it consumed
+pub fn run_release(
+    workspace: &Path,
+    dry_run: bool,
+    cargo_token: Option<&str>,
+) -> Result<Vec<PathBuf>> {
+    run_release_for(
+        workspace,
+        dry_run,
+        cargo_token,
+        &ReleaseSelection::default(),
+    )
+}
+
+/// Execute sampo release for the changesets covered by `selection`
+pub fn run_release_for(
+    workspace: &Path,
+    dry_run: bool,
+    cargo_token: Option<&str>,
+    selection: &ReleaseSelection,
+) -> Result<Vec<PathBuf>> {
     // Set cargo token if provided
     if let Some(token) = cargo_token {
         set_cargo_env_var(token);
     }
 
-    core_release(workspace, dry_run).map_err(|e| ActionError::SampoCommandFailed {
-        operation: "release".to_string(),
-        message: format!("sampo release failed: {}", e),
+    let output = core_release(workspace, dry_run, selection).map_err(|e| {
+        ActionError::SampoCommandFailed {
+            operation: "release".to_string(),
+            message: format!("sampo release failed: {}", e),
+        }
     })?;
 
-    Ok(())
+    Ok(output.consumed_changesets)
 }
 
-/// Execute sampo publish and return information about created/would-be-created tags
+/// Execute sampo publish and return information about created/would-be-created tags.
+///
+/// A publish that fails part way still returns what it did, with the failure.
 pub fn run_publish(
     workspace: &Path,
     dry_run: bool,
     extra_args: &PublishExtraArgs,
+    options: &PublishOptions,
     cargo_token: Option<&str>,
-) -> Result<PublishOutput> {
+) -> Result<(PublishOutput, Option<ActionError>)> {
     // Set cargo token if provided
     if let Some(token) = cargo_token {
         set_cargo_env_var(token);
     }
 
-    core_publish(workspace, dry_run, extra_args).map_err(|e| ActionError::SampoCommandFailed {
+    // In a workflow, a missing OIDC token is a permissions mistake: fail instead of
+    // silently falling back to stored credentials.
+    if !dry_run
+        && options
+            .trusted_publishing
+            .unwrap_or(Config::load(workspace)?.publish_trusted_publishing)
+        && OidcProvider::from_env().is_none()
+    {
+        return Err(ActionError::SampoCommandFailed {
+            operation: "publish".to_string(),
+            message: format!(
+                "trusted publishing needs an OIDC token, but {} is not set; grant the job `permissions: id-token: write`",
+                OIDC_REQUEST_URL_ENV
+            ),
+        });
+    }
+
+    let failed = |e: SampoError| ActionError::SampoCommandFailed {
         operation: "publish".to_string(),
         message: format!("sampo publish failed: {}", e),
-    })
+    };
+    match core_publish(workspace, dry_run, extra_args, options) {
+        Ok(output) => Ok((output, None)),
+        Err(SampoError::PublishIncomplete { output, source }) => {
+            Ok((*output, Some(failed(*source))))
+        }
+        Err(e) => Err(failed(e)),
+    }
 }
 
 /// Dry-run stabilize release to compute the stable version plan.
 pub fn capture_stabilize_plan(workspace: &Path) -> Result<ReleasePlan> {
     let release_output =
-        core_stabilize_release(workspace, true).map_err(|e| ActionError::SampoCommandFailed {
+        plan_stabilize_release(workspace).map_err(|e| ActionError::SampoCommandFailed {
             operation: "stabilize-plan".to_string(),
             message: format!("Stabilize plan failed: {}", e),
         })?;
@@ -138,19 +198,34 @@ pub fn build_release_pr_body(
     workspace: &Path,
     releases: &BTreeMap<String, (String, String, String)>,
     config: &Config,
+) -> Result<String> {
+    build_release_pr_body_for(workspace, releases, config, &ReleaseSelection::default())
+}
+
+/// Same as [`build_release_pr_body`], restricted to the changesets covered by `selection`.
+pub fn build_release_pr_body_for(
+    workspace: &Path,
+    releases: &BTreeMap<String, (String, String, String)>,
+    config: &Config,
+    selection: &ReleaseSelection,
 ) -> Result<String> {
     if releases.is_empty() {
         return Ok(String::new());
     }
 
-    let changesets_dir = workspace.join(".sampo").join("changesets");
-    let changesets = load_changesets(&changesets_dir, &config.changesets_tags)?;
-
     // Load workspace for dependency explanations
     let ws = discover_workspace(workspace).map_err(|e| ActionError::SampoCommandFailed {
         operation: "workspace-discovery".into(),
         message: e.to_string(),
     })?;
+
+    let changesets_dir = workspace.join(".sampo").join("changesets");
+    let mut changesets = Vec::new();
+    for cs in load_changesets(&changesets_dir, &config.changesets_tags)? {
+        if selection.covers_changeset(&ws, config, &cs)? {
+            changesets.push(cs);
+        }
+    }
     let include_kind = ws.has_multiple_package_kinds();
 
     // Group messages per canonical package id by category
@@ -169,6 +244,10 @@ pub fn build_release_pr_body(
             if releases.contains_key(&identifier) {
                 let commit_hash = get_commit_hash_for_path(workspace, &cs.path);
                 let enriched = if let Some(hash) = commit_hash {
+                    let package_config = match ws.find_by_identifier(&identifier) {
+                        Some(info) => config.for_package(info),
+                        None => config.clone(),
+                    };
                     // This branch is rewritten on every push, so crediting someone
                     // must not subscribe them to it.
                     enrich_changeset_message(
@@ -177,8 +256,8 @@ pub fn build_release_pr_body(
                         workspace,
                         repo_slug.as_deref(),
                         github_token.as_deref(),
-                        config.changelog_show_commit_hash,
-                        config
+                        package_config.changelog_show_commit_hash,
+                        package_config
                             .changelog_show_acknowledgments
                             .then_some(AcknowledgmentStyle::Link),
                     )
@@ -187,6 +266,9 @@ pub fn build_release_pr_body(
                 };
                 let category = if let Some(t) = tag {
                     ChangelogCategory::Tag(t.clone())
+                } else if cs.target_versions.contains_key(pkg_spec) {
+                    let (_, old, new) = &releases[&identifier];
+                    ChangelogCategory::Bump(infer_bump_from_versions(old, new))
                 } else {
                     ChangelogCategory::Bump(*bump)
                 };
@@ -270,6 +352,191 @@ pub fn build_release_pr_body(
     Ok(output)
 }
 
+/// How pending changesets are split across release PRs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleasePrGrouping {
+    /// One release PR for the whole workspace
+    Single,
+    /// One release PR per package
+    Package,
+    /// One release PR per fixed group, other packages get their own
+    FixedGroup,
+    /// One release PR per ecosystem (Cargo, npm, Hex, ...)
+    Ecosystem,
+}
+
+impl ReleasePrGrouping {
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "package" => Self::Package,
+            "fixed-group" | "fixed_group" => Self::FixedGroup,
+            "ecosystem" => Self::Ecosystem,
+            _ => Self::Single,
+        }
+    }
+}
+
+/// A set of packages released together through one release PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseGroup {
+    /// Stable key naming the group, used to derive the PR branch and title
+    pub key: String,
+    pub selection: ReleaseSelection,
+}
+
+/// Split the pending changesets into release groups.
+///
+/// Packages are keyed according to `grouping`. A changeset targeting packages with
+/// different keys merges those keys into a single group, so every pending changeset
+/// belongs to exactly one group. So does every package a release would bump besides the
+/// ones it names, dependents reached by the cascade and fixed group peers, so no two
+/// release PRs bump the same package. A merged group is named after its smallest key,
+/// which keeps its PR branch stable. Groups are returned in key order.
+pub fn release_groups(
+    workspace: &Path,
+    config: &Config,
+    grouping: ReleasePrGrouping,
+) -> Result<Vec<ReleaseGroup>> {
+    let ws = discover_workspace(workspace).map_err(|e| ActionError::SampoCommandFailed {
+        operation: "workspace-discovery".into(),
+        message: e.to_string(),
+    })?;
+
+    let fixed_groups = resolve_fixed_groups(&ws, config)?;
+    let key_for = |info: &sampo_core::types::PackageInfo| -> String {
+        match grouping {
+            ReleasePrGrouping::Single => String::new(),
+            ReleasePrGrouping::Package => info.canonical_identifier().to_string(),
+            ReleasePrGrouping::Ecosystem => info.kind.as_str().to_string(),
+            ReleasePrGrouping::FixedGroup => fixed_groups
+                .iter()
+                .find(|group| group.iter().any(|id| id == info.canonical_identifier()))
+                .and_then(|group| group.iter().min())
+                .map_or_else(|| info.canonical_identifier().to_string(), Clone::clone),
+        }
+    };
+
+    let mut members_by_key: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut dependents: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for info in &ws.members {
+        if should_ignore_package(config, &ws, info)? {
+            continue;
+        }
+        members_by_key
+            .entry(key_for(info))
+            .or_default()
+            .insert(info.canonical_identifier().to_string());
+        // Dev edges count even without `cascade_dev_dependencies`: a dev-dependent is
+        // still bumped when its constraint rejects the new version.
+        for dep in info.internal_deps.iter().chain(&info.internal_dev_deps) {
+            dependents
+                .entry(dep.as_str())
+                .or_default()
+                .insert(info.canonical_identifier());
+        }
+    }
+
+    // Union-find over group keys
+    let keys: Vec<String> = members_by_key.keys().cloned().collect();
+    let key_of: BTreeMap<&str, usize> = keys
+        .iter()
+        .enumerate()
+        .flat_map(|(index, key)| {
+            members_by_key[key]
+                .iter()
+                .map(move |id| (id.as_str(), index))
+        })
+        .collect();
+    let mut parent: Vec<usize> = (0..keys.len()).collect();
+
+    let mut pending_keys: BTreeSet<usize> = BTreeSet::new();
+    let mut pending_packages: BTreeSet<String> = BTreeSet::new();
+    let mut changesets = load_changesets(
+        &workspace.join(".sampo").join("changesets"),
+        &config.changesets_tags,
+    )?;
+    changesets.extend(load_changesets(
+        &workspace.join(".sampo").join("prerelease"),
+        &config.changesets_tags,
+    )?);
+    for cs in &changesets {
+        let mut first: Option<usize> = None;
+        for (spec, _, _) in &cs.entries {
+            let identifier = resolve_specifier_identifier(&ws, spec)?;
+            let Some(&index) = key_of.get(identifier.as_str()) else {
+                continue;
+            };
+            pending_keys.insert(index);
+            pending_packages.insert(identifier);
+            match first {
+                None => first = Some(index),
+                Some(other) => union(&mut parent, other, index),
+            }
+        }
+    }
+
+    // Follow what releasing the pending packages bumps along with them
+    let mut queue: Vec<&str> = pending_packages.iter().map(String::as_str).collect();
+    let mut reached: BTreeSet<&str> = queue.iter().copied().collect();
+    while let Some(identifier) = queue.pop() {
+        let peers = fixed_groups
+            .iter()
+            .filter(|group| group.iter().any(|id| id == identifier))
+            .flatten()
+            .map(String::as_str);
+        let bumped = dependents
+            .get(identifier)
+            .into_iter()
+            .flatten()
+            .copied()
+            .chain(peers);
+        for other in bumped {
+            let (Some(&from), Some(&to)) = (key_of.get(identifier), key_of.get(other)) else {
+                continue;
+            };
+            union(&mut parent, from, to);
+            if reached.insert(other) {
+                queue.push(other);
+            }
+        }
+    }
+
+    let mut merged: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for index in 0..keys.len() {
+        let root = find_root(&mut parent, index);
+        merged.entry(root).or_default().push(index);
+    }
+
+    Ok(merged
+        .into_values()
+        .filter_map(|indices| {
+            let key = indices.iter().find(|index| pending_keys.contains(index))?;
+            let selection = ReleaseSelection::packages(
+                indices
+                    .iter()
+                    .flat_map(|index| members_by_key[&keys[*index]].iter().cloned()),
+            );
+            Some(ReleaseGroup {
+                key: keys[*key].clone(),
+                selection,
+            })
+        })
+        .collect())
+}
+
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (a, b) = (find_root(parent, a), find_root(parent, b));
+    parent[a.max(b)] = a.min(b);
+}
+
+fn find_root(parent: &mut [usize], mut index: usize) -> usize {
+    while parent[index] != index {
+        parent[index] = parent[parent[index]];
+        index = parent[index];
+    }
+    index
+}
+
 fn resolve_specifier_identifier(
     workspace: &sampo_core::Workspace,
     spec: &PackageSpecifier,
@@ -675,4 +942,155 @@ mod tests {
         assert!(pr_body.contains("### Minor changes"));
         assert!(pr_body.contains("- feat: add new thing"));
     }
+
+    fn write_grouping_workspace(root: &Path) {
+        use std::fs;
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers=[\"crates/*\"]\n",
+        )
+        .unwrap();
+        for name in ["a", "b", "c"] {
+            let dir = root.join("crates").join(name);
+            fs::create_dir_all(dir.join("src")).unwrap();
+            fs::write(
+                dir.join("Cargo.toml"),
+                format!("[package]\nname=\"{}\"\nversion=\"0.1.0\"\n", name),
+            )
+            .unwrap();
+            fs::write(dir.join("src/lib.rs"), "").unwrap();
+        }
+        fs::create_dir_all(root.join(".sampo/changesets")).unwrap();
+    }
+
+    #[test]
+    fn release_groups_split_per_package_and_merge_shared_changesets() {
+        let _branch = EnvVarGuard::set_branch("main");
+        use std::fs;
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        write_grouping_workspace(root);
+
+        let csdir = root.join(".sampo/changesets");
+        fs::write(csdir.join("a.md"), "---\na: minor\n---\n\nfeat: a\n").unwrap();
+        fs::write(csdir.join("c.md"), "---\nc: patch\n---\n\nfix: c\n").unwrap();
+
+        let config = Config::load(root).unwrap_or_default();
+        let groups = release_groups(root, &config, ReleasePrGrouping::Package).unwrap();
+        let keys: Vec<_> = groups.iter().map(|g| g.key.as_str()).collect();
+        assert_eq!(keys, vec!["cargo/a", "cargo/c"]);
+
+        let plan = capture_release_plan_for(root, &groups[0].selection).unwrap();
+        assert_eq!(plan.releases.keys().collect::<Vec<_>>(), vec!["cargo/a"]);
+        let body =
+            build_release_pr_body_for(root, &plan.releases, &config, &groups[0].selection).unwrap();
+        assert!(body.contains("feat: a"));
+        assert!(!body.contains("fix: c"));
+
+        // A changeset touching both `a` and `b` ties them into one group
+        fs::write(
+            csdir.join("ab.md"),
+            "---\na: patch\nb: patch\n---\n\nfix: a and b\n",
+        )
+        .unwrap();
+        let groups = release_groups(root, &config, ReleasePrGrouping::Package).unwrap();
+        let keys: Vec<_> = groups.iter().map(|g| g.key.as_str()).collect();
+        assert_eq!(keys, vec!["cargo/a", "cargo/c"]);
+        assert_eq!(
+            groups[0].selection,
+            ReleaseSelection::packages(["cargo/a", "cargo/b"])
+        );
+    }
+
+    #[test]
+    fn release_groups_keep_cascaded_dependents_in_one_group() {
+        let _branch = EnvVarGuard::set_branch("main");
+        use std::fs;
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        write_grouping_workspace(root);
+        // `b` depends on `a`, so releasing `a` bumps `b` too
+        fs::write(
+            root.join("crates/b/Cargo.toml"),
+            "[package]\nname=\"b\"\nversion=\"0.1.0\"\n\n[dependencies]\na = { path = \"../a\", version = \"0.1.0\" }\n",
+        )
+        .unwrap();
+
+        let csdir = root.join(".sampo/changesets");
+        fs::write(csdir.join("a.md"), "---\na: minor\n---\n\nfeat: a\n").unwrap();
+        fs::write(csdir.join("b.md"), "---\nb: patch\n---\n\nfix: b\n").unwrap();
+        fs::write(csdir.join("c.md"), "---\nc: patch\n---\n\nfix: c\n").unwrap();
+
+        let config = Config::load(root).unwrap_or_default();
+        let groups = release_groups(root, &config, ReleasePrGrouping::Package).unwrap();
+        let keys: Vec<_> = groups.iter().map(|g| g.key.as_str()).collect();
+        assert_eq!(keys, vec!["cargo/a", "cargo/c"]);
+        assert_eq!(
+            groups[0].selection,
+            ReleaseSelection::packages(["cargo/a", "cargo/b"])
+        );
+
+        // Without a pending change of its own, `b` still belongs to `a`'s group only
+        fs::remove_file(csdir.join("b.md")).unwrap();
+        let groups = release_groups(root, &config, ReleasePrGrouping::Package).unwrap();
+        let keys: Vec<_> = groups.iter().map(|g| g.key.as_str()).collect();
+        assert_eq!(keys, vec!["cargo/a", "cargo/c"]);
+        let plan = capture_release_plan_for(root, &groups[0].selection).unwrap();
+        assert_eq!(
+            plan.releases.keys().collect::<Vec<_>>(),
+            vec!["cargo/a", "cargo/b"]
+        );
+    }
+
+    #[test]
+    fn release_groups_follow_fixed_groups_and_ecosystems() {
+        let _branch = EnvVarGuard::set_branch("main");
+        use std::fs;
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        write_grouping_workspace(root);
+        fs::write(
+            root.join(".sampo/config.toml"),
+            "[packages]\nfixed = [[\"a\", \"b\"]]\n",
+        )
+        .unwrap();
+
+        let csdir = root.join(".sampo/changesets");
+        fs::write(csdir.join("b.md"), "---\nb: minor\n---\n\nfeat: b\n").unwrap();
+        fs::write(csdir.join("c.md"), "---\nc: patch\n---\n\nfix: c\n").unwrap();
+
+        let config = Config::load(root).unwrap();
+        let groups = release_groups(root, &config, ReleasePrGrouping::FixedGroup).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].key, "cargo/a");
+        assert_eq!(
+            groups[0].selection,
+            ReleaseSelection::packages(["cargo/a", "cargo/b"])
+        );
+        assert_eq!(groups[1].key, "cargo/c");
+
+        let groups = release_groups(root, &config, ReleasePrGrouping::Ecosystem).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].key, "cargo");
+    }
+
+    #[test]
+    fn release_pr_grouping_parsing() {
+        assert_eq!(
+            ReleasePrGrouping::parse("package"),
+            ReleasePrGrouping::Package
+        );
+        assert_eq!(
+            ReleasePrGrouping::parse("Fixed-Group"),
+            ReleasePrGrouping::FixedGroup
+        );
+        assert_eq!(
+            ReleasePrGrouping::parse("ecosystem"),
+            ReleasePrGrouping::Ecosystem
+        );
+        assert_eq!(
+            ReleasePrGrouping::parse("unknown"),
+            ReleasePrGrouping::Single
+        );
+    }
 }
@@ -0,0 +1,732 @@
+use crate::ResolvedAsset;
+use crate::error::{ActionError, Result};
+use sampo_core::types::{PackageInfo, Workspace};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Default asset name for the checksums file
+pub const DEFAULT_CHECKSUMS_NAME: &str = "SHA256SUMS";
+
+/// How the checksums file is signed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningMethod {
+    None,
+    /// `minisign -S`, producing `<checksums>.minisig`
+    Minisign,
+    /// `ssh-keygen -Y sign`, producing `<checksums>.sig`
+    Ssh,
+}
+
+impl SigningMethod {
+    pub fn parse(s: &str) -> Self {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "minisign" => SigningMethod::Minisign,
+            "ssh" | "ssh-keygen" => SigningMethod::Ssh,
+            _ => SigningMethod::None,
+        }
+    }
+
+    fn signature_extension(self) -> &'static str {
+        match self {
+            SigningMethod::Minisign => "minisig",
+            SigningMethod::Ssh | SigningMethod::None => "sig",
+        }
+    }
+}
+
+/// SBOM document format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbomFormat {
+    None,
+    /// SPDX 2.3 (JSON)
+    Spdx,
+    /// CycloneDX 1.5 (JSON)
+    CycloneDx,
+}
+
+impl SbomFormat {
+    pub fn parse(s: &str) -> Self {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "spdx" => SbomFormat::Spdx,
+            "cyclonedx" | "cdx" => SbomFormat::CycloneDx,
+            _ => SbomFormat::None,
+        }
+    }
+
+    /// Default asset name template for the SBOM of a release
+    pub fn default_asset_name(self) -> &'static str {
+        match self {
+            SbomFormat::CycloneDx => "{{crate}}-{{version}}.cdx.json",
+            SbomFormat::Spdx | SbomFormat::None => "{{crate}}-{{version}}.spdx.json",
+        }
+    }
+}
+
+/// Supply-chain metadata generated alongside the release assets
+#[derive(Debug, Clone)]
+pub struct SupplyChainOptions {
+    /// Generate a checksums file covering every uploaded asset
+    pub checksums: bool,
+    /// Asset name template for the checksums file
+    pub checksums_name: String,
+    pub signing_method: SigningMethod,
+    /// Private key contents used to sign the checksums file
+    pub signing_key: Option<String>,
+    /// Password for the signing key
+    pub signing_key_password: Option<String>,
+    pub sbom: SbomFormat,
+    /// Asset name template for the SBOM
+    pub sbom_name: Option<String>,
+}
+
+impl SupplyChainOptions {
+    pub fn is_enabled(&self) -> bool {
+        self.checksums || self.sbom != SbomFormat::None
+    }
+}
+
+/// Generate the SBOM, checksums and signature for a release in `out_dir`, returning them
+/// as extra assets to upload. `package` is the canonical identifier of the released
+/// package, and `render_name` expands asset name templates (`{{tag}}`, ...).
+pub fn generate_release_metadata(
+    workspace: &Workspace,
+    tag: &str,
+    package: Option<&str>,
+    assets: &[ResolvedAsset],
+    options: &SupplyChainOptions,
+    out_dir: &Path,
+    render_name: impl Fn(&str) -> String,
+) -> Result<Vec<ResolvedAsset>> {
+    let mut generated = Vec::new();
+
+    if options.sbom != SbomFormat::None {
+        let document = build_sbom(workspace, options.sbom, tag, package);
+        let asset_name = render_name(
+            options
+                .sbom_name
+                .as_deref()
+                .unwrap_or(options.sbom.default_asset_name()),
+        );
+        let path = out_dir.join(&asset_name);
+        let rendered = serde_json::to_string_pretty(&document).map_err(|e| {
+            ActionError::SampoCommandFailed {
+                operation: "sbom".to_string(),
+                message: format!("Failed to serialize SBOM: {}", e),
+            }
+        })?;
+        fs::write(&path, rendered)?;
+        generated.push(ResolvedAsset { path, asset_name });
+    }
+
+    if options.checksums {
+        let asset_name = render_name(&options.checksums_name);
+        let mut covered: Vec<&ResolvedAsset> = assets.iter().chain(generated.iter()).collect();
+        covered.sort_by(|a, b| a.asset_name.cmp(&b.asset_name));
+
+        let mut contents = String::new();
+        for asset in covered {
+            contents.push_str(&format!(
+                "{}  {}\n",
+                sha256_hex(&fs::read(&asset.path)?),
+                asset.asset_name
+            ));
+        }
+        let path = out_dir.join(&asset_name);
+        fs::write(&path, contents)?;
+
+        if options.signing_method != SigningMethod::None {
+            let key =
+                options
+                    .signing_key
+                    .as_deref()
+                    .ok_or_else(|| ActionError::SampoCommandFailed {
+                        operation: "sign-checksums".to_string(),
+                        message: "signing-method is set but signing-key is empty".to_string(),
+                    })?;
+            let signature = sign_file(
+                &path,
+                options.signing_method,
+                key,
+                options.signing_key_password.as_deref(),
+                out_dir,
+            )?;
+            generated.push(ResolvedAsset {
+                path: path.clone(),
+                asset_name: asset_name.clone(),
+            });
+            generated.push(ResolvedAsset {
+                path: signature,
+                asset_name: format!(
+                    "{}.{}",
+                    asset_name,
+                    options.signing_method.signature_extension()
+                ),
+            });
+        } else {
+            generated.push(ResolvedAsset { path, asset_name });
+        }
+    }
+
+    Ok(generated)
+}
+
+/// Scratch directory for files generated for `tag` (under `RUNNER_TEMP` on GitHub runners).
+pub fn output_dir(tag: &str) -> Result<PathBuf> {
+    let base = std::env::var_os("RUNNER_TEMP")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir);
+    let dir = base
+        .join("sampo-release-metadata")
+        .join(tag.replace(['/', '\\'], "-"));
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Sign `path` with the given key, returning the path of the detached signature.
+fn sign_file(
+    path: &Path,
+    method: SigningMethod,
+    key: &str,
+    password: Option<&str>,
+    out_dir: &Path,
+) -> Result<PathBuf> {
+    let key_path = out_dir.join(".signing-key");
+    write_private_file(&key_path, key)?;
+
+    let (mut command, signature) = match method {
+        SigningMethod::Minisign => {
+            let signature = path.with_extension("minisig");
+            let mut command = Command::new("minisign");
+            command
+                .arg("-S")
+                .arg("-s")
+                .arg(&key_path)
+                .arg("-m")
+                .arg(path)
+                .arg("-x")
+                .arg(&signature);
+            (command, signature)
+        }
+        SigningMethod::Ssh => {
+            let mut command = Command::new("ssh-keygen");
+            command
+                .args(["-Y", "sign", "-n", "file", "-f"])
+                .arg(&key_path)
+                .arg(path);
+            (command, PathBuf::from(format!("{}.sig", path.display())))
+        }
+        SigningMethod::None => unreachable!("signing is skipped when no method is set"),
+    };
+
+    // ssh-keygen never reads the passphrase from stdin: it asks the `SSH_ASKPASS`
+    // program, which echoes it back from the environment.
+    let askpass_path = out_dir.join(".signing-askpass");
+    let askpass = match (method, password) {
+        (SigningMethod::Ssh, Some(password)) => {
+            write_askpass_script(&askpass_path)?;
+            command
+                .env("SSH_ASKPASS", &askpass_path)
+                .env("SSH_ASKPASS_REQUIRE", "force")
+                .env("SAMPO_SIGNING_KEY_PASSWORD", password);
+            if std::env::var_os("DISPLAY").is_none() {
+                command.env("DISPLAY", ":0");
+            }
+            true
+        }
+        _ => false,
+    };
+
+    let result = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take()
+                && method == SigningMethod::Minisign
+            {
+                stdin.write_all(format!("{}\n", password.unwrap_or_default()).as_bytes())?;
+            }
+            child.wait_with_output()
+        });
+    let _ = fs::remove_file(&key_path);
+    if askpass {
+        let _ = fs::remove_file(&askpass_path);
+    }
+
+    let output = result.map_err(|e| ActionError::SampoCommandFailed {
+        operation: "sign-checksums".to_string(),
+        message: format!("Failed to run signing tool: {}", e),
+    })?;
+    if !output.status.success() {
+        return Err(ActionError::SampoCommandFailed {
+            operation: "sign-checksums".to_string(),
+            message: format!(
+                "Signing {} failed: {}",
+                path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+
+    Ok(signature)
+}
+
+/// Write the `SSH_ASKPASS` helper printing `SAMPO_SIGNING_KEY_PASSWORD`, so the
+/// passphrase itself never lands on disk.
+fn write_askpass_script(path: &Path) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o700);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(b"#!/bin/sh\nprintf '%s\\n' \"$SAMPO_SIGNING_KEY_PASSWORD\"\n")?;
+    Ok(())
+}
+
+fn write_private_file(path: &Path, contents: &str) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(contents.trim_end().as_bytes())?;
+    file.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Build an SBOM for a release from the workspace package graph.
+///
+/// When `package` is the canonical identifier of a workspace member, the document covers
+/// that package and its internal runtime dependencies; otherwise it covers every member.
+pub fn build_sbom(
+    workspace: &Workspace,
+    format: SbomFormat,
+    tag: &str,
+    package: Option<&str>,
+) -> Value {
+    let root = package.and_then(|identifier| workspace.find_by_identifier(identifier));
+    let members = sbom_members(workspace, root);
+    let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let tool = format!("sampo-github-action-{}", env!("CARGO_PKG_VERSION"));
+
+    match format {
+        SbomFormat::CycloneDx => {
+            let components: Vec<Value> = members
+                .iter()
+                .map(|info| {
+                    json!({
+                        "type": "library",
+                        "bom-ref": info.identifier,
+                        "name": info.name,
+                        "version": info.version,
+                        "purl": info.package_url(),
+                    })
+                })
+                .collect();
+            let dependencies: Vec<Value> = members
+                .iter()
+                .map(|info| {
+                    json!({
+                        "ref": info.identifier,
+                        "dependsOn": info.internal_deps.iter().collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            let mut metadata = json!({
+                "timestamp": timestamp,
+                "tools": { "components": [{ "type": "application", "name": tool }] },
+            });
+            if let Some(info) = root {
+                metadata["component"] = json!({
+                    "type": "library",
+                    "bom-ref": info.identifier,
+                    "name": info.name,
+                    "version": info.version,
+                    "purl": info.package_url(),
+                });
+            }
+            json!({
+                "bomFormat": "CycloneDX",
+                "specVersion": "1.5",
+                "version": 1,
+                "metadata": metadata,
+                "components": components,
+                "dependencies": dependencies,
+            })
+        }
+        SbomFormat::Spdx | SbomFormat::None => {
+            let packages: Vec<Value> = members
+                .iter()
+                .map(|info| {
+                    json!({
+                        "SPDXID": spdx_id(info),
+                        "name": info.name,
+                        "versionInfo": info.version,
+                        "downloadLocation": "NOASSERTION",
+                        "filesAnalyzed": false,
+                        "externalRefs": [{
+                            "referenceCategory": "PACKAGE-MANAGER",
+                            "referenceType": "purl",
+                            "referenceLocator": info.package_url(),
+                        }],
+                    })
+                })
+                .collect();
+            let by_identifier: BTreeMap<&str, &PackageInfo> = members
+                .iter()
+                .map(|info| (info.identifier.as_str(), *info))
+                .collect();
+            let described: Vec<&PackageInfo> = match root {
+                Some(info) => vec![info],
+                None => members.clone(),
+            };
+            let mut relationships: Vec<Value> = described
+                .iter()
+                .map(|info| {
+                    json!({
+                        "spdxElementId": "SPDXRef-DOCUMENT",
+                        "relationshipType": "DESCRIBES",
+                        "relatedSpdxElement": spdx_id(info),
+                    })
+                })
+                .collect();
+            for info in &members {
+                for dep in &info.internal_deps {
+                    if let Some(dep_info) = by_identifier.get(dep.as_str()) {
+                        relationships.push(json!({
+                            "spdxElementId": spdx_id(info),
+                            "relationshipType": "DEPENDS_ON",
+                            "relatedSpdxElement": spdx_id(dep_info),
+                        }));
+                    }
+                }
+            }
+            json!({
+                "spdxVersion": "SPDX-2.3",
+                "dataLicense": "CC0-1.0",
+                "SPDXID": "SPDXRef-DOCUMENT",
+                "name": tag,
+                "documentNamespace": format!("https://sampo.dev/spdx/{}", tag),
+                "creationInfo": {
+                    "created": timestamp,
+                    "creators": [format!("Tool: {}", tool)],
+                },
+                "packages": packages,
+                "relationships": relationships,
+            })
+        }
+    }
+}
+
+/// Members covered by the SBOM: `root` and its transitive internal runtime
+/// dependencies, or the whole workspace when there is no root.
+fn sbom_members<'a>(
+    workspace: &'a Workspace,
+    root: Option<&'a PackageInfo>,
+) -> Vec<&'a PackageInfo> {
+    let Some(root) = root else {
+        let mut members: Vec<&PackageInfo> = workspace.members.iter().collect();
+        members.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+        return members;
+    };
+
+    let mut seen: BTreeSet<&str> = BTreeSet::new();
+    let mut queue = vec![root];
+    let mut members = Vec::new();
+    while let Some(info) = queue.pop() {
+        if !seen.insert(info.identifier.as_str()) {
+            continue;
+        }
+        members.push(info);
+        for dep in &info.internal_deps {
+            if let Some(dep_info) = workspace.find_by_identifier(dep) {
+                queue.push(dep_info);
+            }
+        }
+    }
+    members.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    members
+}
+
+fn spdx_id(info: &PackageInfo) -> String {
+    let sanitized: String = info
+        .identifier
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("SPDXRef-Package-{}", sanitized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sampo_core::types::PackageKind;
+
+    fn member(kind: PackageKind, name: &str, version: &str, deps: &[&str]) -> PackageInfo {
+        PackageInfo {
+            name: name.to_string(),
+            identifier: PackageInfo::dependency_identifier(kind, name),
+            version: version.to_string(),
+            path: PathBuf::from(name),
+            internal_deps: deps.iter().map(|d| d.to_string()).collect(),
+            internal_dev_deps: BTreeSet::new(),
+            kind,
+        }
+    }
+
+    fn sample_workspace() -> Workspace {
+        Workspace {
+            root: PathBuf::from("/repo"),
+            members: vec![
+                member(PackageKind::Cargo, "app", "1.2.0", &["cargo/core"]),
+                member(PackageKind::Cargo, "core", "0.4.1", &[]),
+                member(PackageKind::Npm, "@acme/ui", "2.0.0", &[]),
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_signing_and_sbom_inputs() {
+        assert_eq!(SigningMethod::parse("minisign"), SigningMethod::Minisign);
+        assert_eq!(SigningMethod::parse("SSH"), SigningMethod::Ssh);
+        assert_eq!(SigningMethod::parse(""), SigningMethod::None);
+        assert_eq!(SbomFormat::parse("spdx"), SbomFormat::Spdx);
+        assert_eq!(SbomFormat::parse("CycloneDX"), SbomFormat::CycloneDx);
+        assert_eq!(SbomFormat::parse("false"), SbomFormat::None);
+    }
+
+    #[test]
+    fn spdx_sbom_covers_package_and_internal_deps() {
+        let ws = sample_workspace();
+        let sbom = build_sbom(&ws, SbomFormat::Spdx, "cargo-app-v1.2.0", Some("cargo/app"));
+
+        assert_eq!(sbom["spdxVersion"], "SPDX-2.3");
+        let names: Vec<&str> = sbom["packages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["app", "core"]);
+
+        let relationships = sbom["relationships"].as_array().unwrap();
+        assert!(
+            relationships
+                .iter()
+                .any(|r| r["relationshipType"] == "DESCRIBES"
+                    && r["relatedSpdxElement"] == "SPDXRef-Package-cargo-app")
+        );
+        assert!(
+            relationships
+                .iter()
+                .any(|r| r["relationshipType"] == "DEPENDS_ON"
+                    && r["spdxElementId"] == "SPDXRef-Package-cargo-app"
+                    && r["relatedSpdxElement"] == "SPDXRef-Package-cargo-core")
+        );
+    }
+
+    #[test]
+    fn sbom_root_is_the_package_with_that_identifier() {
+        let ws = Workspace {
+            root: PathBuf::from("/repo"),
+            members: vec![
+                member(PackageKind::Npm, "app", "3.0.0", &[]),
+                member(PackageKind::Cargo, "app", "1.2.0", &["cargo/core"]),
+                member(PackageKind::Cargo, "core", "0.4.1", &[]),
+            ],
+        };
+        let sbom = build_sbom(
+            &ws,
+            SbomFormat::CycloneDx,
+            "cargo-app-v1.2.0",
+            Some("cargo/app"),
+        );
+
+        assert_eq!(sbom["metadata"]["component"]["bom-ref"], "cargo/app");
+        let refs: Vec<&str> = sbom["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["bom-ref"].as_str().unwrap())
+            .collect();
+        assert_eq!(refs, vec!["cargo/app", "cargo/core"]);
+    }
+
+    #[test]
+    fn cyclonedx_sbom_lists_dependencies() {
+        let ws = sample_workspace();
+        let sbom = build_sbom(&ws, SbomFormat::CycloneDx, "v1.0.0", None);
+
+        assert_eq!(sbom["bomFormat"], "CycloneDX");
+        assert!(sbom["metadata"].get("component").is_none());
+        assert_eq!(sbom["components"].as_array().unwrap().len(), 3);
+        let app = sbom["dependencies"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|d| d["ref"] == "cargo/app")
+            .unwrap();
+        assert_eq!(app["dependsOn"], json!(["cargo/core"]));
+    }
+
+    #[test]
+    fn checksums_cover_assets_and_sbom() {
+        let temp = tempfile::tempdir().unwrap();
+        let binary = temp.path().join("tool");
+        fs::write(&binary, b"hello").unwrap();
+        let assets = vec![ResolvedAsset {
+            path: binary,
+            asset_name: "tool-linux".to_string(),
+        }];
+        let options = SupplyChainOptions {
+            checksums: true,
+            checksums_name: DEFAULT_CHECKSUMS_NAME.to_string(),
+            signing_method: SigningMethod::None,
+            signing_key: None,
+            signing_key_password: None,
+            sbom: SbomFormat::Spdx,
+            sbom_name: Some("{{tag}}.spdx.json".to_string()),
+        };
+
+        let generated = generate_release_metadata(
+            &sample_workspace(),
+            "test-checksums-v1.0.0",
+            Some("cargo/app"),
+            &assets,
+            &options,
+            temp.path(),
+            |template| template.replace("{{tag}}", "test-checksums-v1.0.0"),
+        )
+        .unwrap();
+
+        let names: Vec<&str> = generated.iter().map(|a| a.asset_name.as_str()).collect();
+        assert_eq!(names, vec!["test-checksums-v1.0.0.spdx.json", "SHA256SUMS"]);
+
+        let sums = fs::read_to_string(&generated[1].path).unwrap();
+        let lines: Vec<&str> = sums.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("  test-checksums-v1.0.0.spdx.json"));
+        assert_eq!(
+            lines[1],
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824  tool-linux"
+        );
+    }
+
+    #[test]
+    fn signing_requires_a_key() {
+        let temp = tempfile::tempdir().unwrap();
+        let options = SupplyChainOptions {
+            checksums: true,
+            checksums_name: DEFAULT_CHECKSUMS_NAME.to_string(),
+            signing_method: SigningMethod::Ssh,
+            signing_key: None,
+            signing_key_password: None,
+            sbom: SbomFormat::None,
+            sbom_name: None,
+        };
+
+        let err = generate_release_metadata(
+            &sample_workspace(),
+            "test-signing-v1.0.0",
+            None,
+            &[],
+            &options,
+            temp.path(),
+            str::to_string,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("signing-key"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn ssh_signing_unlocks_encrypted_keys() {
+        let temp = tempfile::tempdir().unwrap();
+        let key_path = temp.path().join("key");
+        let generated = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "hunter2", "-f"])
+            .arg(&key_path)
+            .status();
+        if !generated.is_ok_and(|status| status.success()) {
+            eprintln!("ssh-keygen unavailable, not testing encrypted keys");
+            return;
+        }
+        let out_dir = temp.path().join("out");
+        fs::create_dir_all(&out_dir).unwrap();
+        let options = SupplyChainOptions {
+            checksums: true,
+            checksums_name: DEFAULT_CHECKSUMS_NAME.to_string(),
+            signing_method: SigningMethod::Ssh,
+            signing_key: Some(fs::read_to_string(&key_path).unwrap()),
+            signing_key_password: Some("hunter2".to_string()),
+            sbom: SbomFormat::None,
+            sbom_name: None,
+        };
+
+        let generated = generate_release_metadata(
+            &sample_workspace(),
+            "test-ssh-v1.0.0",
+            None,
+            &[],
+            &options,
+            &out_dir,
+            str::to_string,
+        )
+        .unwrap();
+        assert_eq!(generated[1].asset_name, "SHA256SUMS.sig");
+        assert!(generated[1].path.exists());
+        assert!(!out_dir.join(".signing-askpass").exists());
+    }
+
+    #[test]
+    fn signing_failure_is_an_error() {
+        let temp = tempfile::tempdir().unwrap();
+        let options = SupplyChainOptions {
+            checksums: true,
+            checksums_name: DEFAULT_CHECKSUMS_NAME.to_string(),
+            signing_method: SigningMethod::Ssh,
+            signing_key: Some("not a private key".to_string()),
+            signing_key_password: None,
+            sbom: SbomFormat::None,
+            sbom_name: None,
+        };
+
+        let err = generate_release_metadata(
+            &sample_workspace(),
+            "test-signing-v1.0.0",
+            None,
+            &[],
+            &options,
+            temp.path(),
+            str::to_string,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("sign"), "{err}");
+        assert!(!temp.path().join(".signing-key").exists());
+    }
+}
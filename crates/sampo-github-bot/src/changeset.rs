@@ -30,6 +30,8 @@ struct ChangesetIssue {
 struct PackagePreview {
     spec: PackageSpecifier,
     highest_bump: Bump,
+    /// Exact version requested with `=<version>`, which overrides the bump level
+    target_version: Option<String>,
     changes_by_category: BTreeMap<ChangelogCategory, Vec<String>>,
 }
 
@@ -37,7 +39,8 @@ impl PackagePreview {
     fn new(spec: PackageSpecifier) -> Self {
         Self {
             spec,
-            highest_bump: Bump::Patch,
+            highest_bump: Bump::None,
+            target_version: None,
             changes_by_category: BTreeMap::new(),
         }
     }
@@ -129,10 +132,12 @@ fn build_present_changeset_comment(
 fn append_package_preview(out: &mut String, package: &PackagePreview) {
     let include_kind = package.spec.kind.is_some();
     let display_name = package.spec.display_name(include_kind);
-    let bump_label = match package.highest_bump {
-        Bump::Major => "major version bump",
-        Bump::Minor => "minor version bump",
-        Bump::Patch => "patch version bump",
+    let bump_label = match (&package.target_version, package.highest_bump) {
+        (Some(version), _) => format!("release as {version}"),
+        (None, Bump::Major) => "major version bump".to_string(),
+        (None, Bump::Minor) => "minor version bump".to_string(),
+        (None, Bump::Patch) => "patch version bump".to_string(),
+        (None, Bump::None) => "changelog note, no version bump".to_string(),
     };
     out.push_str(&format!("## {display_name} — {bump_label}\n\n"));
 
@@ -176,6 +181,9 @@ fn summarize_packages(changesets: &[ChangesetInfo]) -> BTreeMap<String, PackageP
             let preview = packages
                 .entry(key)
                 .or_insert_with(|| PackagePreview::new(spec.clone()));
+            if let Some(version) = cs.target_versions.get(spec) {
+                preview.target_version = Some(version.clone());
+            }
             preview.register_change(*bump, tag.as_deref(), &cs.message);
         }
     }
@@ -311,9 +319,10 @@ fn parse_changeset_files(files: &[ChangesetFile]) -> ParsedChangesets {
 
 fn bump_priority(bump: Bump) -> u8 {
     match bump {
-        Bump::Patch => 0,
-        Bump::Minor => 1,
-        Bump::Major => 2,
+        Bump::None => 0,
+        Bump::Patch => 1,
+        Bump::Minor => 2,
+        Bump::Major => 3,
     }
 }
 
@@ -351,6 +360,7 @@ index 0000000..f1c2d3e
         let info = ChangesetInfo {
             path: Path::new(".sampo/changesets/example.md").to_path_buf(),
             entries: vec![(spec.clone(), Bump::Minor, None)],
+            target_versions: BTreeMap::new(),
             message: "feat: add new feature".to_string(),
         };
         let packages = summarize_packages(&[info.clone(), info]);
@@ -377,6 +387,35 @@ index 0000000..f1c2d3e
         assert!(out.contains("- fix: bug"));
     }
 
+    #[test]
+    fn append_package_preview_shows_target_version_and_notes() {
+        let mut out = String::new();
+        let mut package =
+            PackagePreview::new(PackageSpecifier::parse("cargo/example").expect("valid specifier"));
+        package.register_change(Bump::None, None, "docs: clarify");
+        append_package_preview(&mut out, &package);
+        assert!(out.contains("changelog note, no version bump"));
+        assert!(out.contains("### Notes"));
+
+        let info = ChangesetInfo {
+            path: Path::new(".sampo/changesets/graduate.md").to_path_buf(),
+            entries: vec![(
+                PackageSpecifier::parse("cargo/example").expect("valid specifier"),
+                Bump::Patch,
+                None,
+            )],
+            target_versions: [(
+                PackageSpecifier::parse("cargo/example").expect("valid specifier"),
+                "1.0.0".to_string(),
+            )]
+            .into(),
+            message: "feat: stable API".to_string(),
+        };
+        let packages = summarize_packages(&[info]);
+        let comment = build_present_changeset_comment(&packages, &[]);
+        assert!(comment.contains("## example (Cargo) — release as 1.0.0"));
+    }
+
     #[test]
     fn missing_changeset_comment_includes_instructions() {
         let comment = build_missing_changeset_comment(&[]);
@@ -394,6 +433,7 @@ index 0000000..f1c2d3e
                 Bump::Minor,
                 None,
             )],
+            target_versions: BTreeMap::new(),
             message: "feat: add new capability".to_string(),
         };
         let packages = summarize_packages(&[info]);
@@ -426,6 +466,7 @@ index 0000000..f1c2d3e
                 Bump::Minor,
                 Some("Added".to_string()),
             )],
+            target_versions: BTreeMap::new(),
             message: "feat: add new capability".to_string(),
         };
         let packages = summarize_packages(&[info]);
@@ -448,6 +489,7 @@ index 0000000..f1c2d3e
                 Bump::Minor,
                 Some("Added".to_string()),
             )],
+            target_versions: BTreeMap::new(),
             message: "feat: new feature".to_string(),
         };
         let info2 = ChangesetInfo {
@@ -457,6 +499,7 @@ index 0000000..f1c2d3e
                 Bump::Patch,
                 None,
             )],
+            target_versions: BTreeMap::new(),
             message: "fix: bug fix".to_string(),
         };
         let packages = summarize_packages(&[info1, info2]);
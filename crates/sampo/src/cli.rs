@@ -27,6 +27,9 @@ pub enum Commands {
     /// Manage pre-release versions for workspace packages
     Pre(PreArgs),
 
+    /// Yank or deprecate a published version on its registry
+    Yank(YankArgs),
+
     /// Update Sampo CLI to the latest version
     #[cfg(feature = "self-update")]
     Update(UpdateArgs),
@@ -38,7 +41,7 @@ pub struct AddArgs {
     #[arg(short, long, num_args = 1.., value_name = "PACKAGE")]
     pub package: Vec<String>,
 
-    /// Bump level to apply to all selected packages (patch, minor, major)
+    /// Bump level to apply to all selected packages (none, patch, minor, major)
     #[arg(short, long, value_name = "LEVEL")]
     pub bump: Option<Bump>,
 
@@ -53,12 +56,24 @@ pub struct AddArgs {
 
 #[derive(Debug, Args, Default)]
 #[command(after_long_help = "\
-Examples:\n  sampo publish --dry-run -- --access restricted\n  sampo publish --cargo-args --allow-dirty -- --tag beta\n\nBehavior:\n  - Skips packages whose current version already exists on their registry.\n  - Creates git tags after publishing (<name>-v<version>, or v<version> with git.short_tags).\n\nAll arguments after `--` are forwarded to ALL underlying publish commands (separator required).\nUse --cargo-args, --npm-args, --hex-args, --pypi-args, --packagist-args, or --maven-args to forward\narguments to a specific ecosystem only.")]
+Examples:\n  sampo publish --dry-run -- --access restricted\n  sampo publish --offline-check\n  sampo publish --repair-tags --dry-run\n  sampo publish --cargo-args --allow-dirty -- --tag beta\n\nBehavior:\n  - Skips packages whose current version already exists on their registry.\n  - With --offline-check, a version counts as published when its git tag exists; no registry is queried.\n  - With --repair-tags, only creates the missing tags of versions their registry already serves, on their release commits.\n  - Creates git tags after publishing (<name>-v<version>, or v<version> with git.short_tags).\n\nAll arguments after `--` are forwarded to ALL underlying publish commands (separator required).\nUse --cargo-args, --npm-args, --hex-args, --pypi-args, --packagist-args, or --maven-args to forward\narguments to a specific ecosystem only.")]
 pub struct PublishArgs {
     /// Dry-run: simulate publish without pushing artifacts
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Treat an existing git tag as proof of publication instead of querying registries
+    #[arg(long)]
+    pub offline_check: bool,
+
+    /// Create the missing git tags of versions already published, without publishing
+    #[arg(long, conflicts_with = "offline_check")]
+    pub repair_tags: bool,
+
+    /// Write a JSON report of the publish (tags, retries, registries...) to this file
+    #[arg(long, value_name = "PATH")]
+    pub report: Option<std::path::PathBuf>,
+
     /// Extra arguments forwarded only to Cargo (e.g. --cargo-args --allow-dirty)
     #[arg(long, num_args = 1.., value_delimiter = ' ', allow_hyphen_values = true)]
     pub cargo_args: Option<Vec<String>>,
@@ -88,11 +103,62 @@ pub struct PublishArgs {
     pub publish_args: Vec<String>,
 }
 
+#[derive(Debug, Args)]
+#[command(after_long_help = "\
+Examples:\n  sampo yank my-crate@1.2.0 --reason \"Breaks builds on Windows\"\n  sampo yank npm/web@2.0.0 --unpublish\n  sampo yank my-crate@1.2.0 --changelog --github-release --dry-run\n\nBehavior:\n  - Cargo: cargo yank. npm: npm deprecate, or npm unpublish with --unpublish (within 72 hours of the publish).\n  - Hex: retires the release with mix, gleam or rebar3.\n  - PyPI, Packagist and Maven Central cannot yank a version from the command line.\n  - --github-release needs GITHUB_TOKEN or GH_TOKEN.")]
+pub struct YankArgs {
+    /// Version to yank, as <package>@<version>
+    #[arg(value_name = "PACKAGE@VERSION")]
+    pub target: String,
+
+    /// Why the version is yanked
+    #[arg(short, long)]
+    pub reason: Option<String>,
+
+    /// Unpublish the npm version instead of deprecating it
+    #[arg(long)]
+    pub unpublish: bool,
+
+    /// Add a yank note to the version's changelog section
+    #[arg(long)]
+    pub changelog: bool,
+
+    /// Add a yank note to the version's GitHub release
+    #[arg(long)]
+    pub github_release: bool,
+
+    /// Dry-run: show the commands without running them
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
 #[derive(Debug, Args, Default)]
+#[command(after_long_help = "\
+Examples:\n  sampo release --package my-crate\n  sampo release --exclude npm/web --dry-run\n  sampo release -p my-crate --split\n  sampo release --commit --sign\n\nBehavior:\n  - With --package or --exclude, only changesets whose entries all target selected packages are consumed.\n  - Dependents and fixed/linked group members of released packages are still bumped.\n  - Changesets that also target other packages stay pending, unless --split is set.\n  - With --commit, the release refuses to run when files other than pending changesets have uncommitted changes.")]
 pub struct ReleaseArgs {
     /// Dry-run: compute and show changes without modifying files
     #[arg(long)]
     pub dry_run: bool,
+
+    /// Only release the changesets of these packages
+    #[arg(short, long, num_args = 1.., value_name = "PACKAGE")]
+    pub package: Vec<String>,
+
+    /// Leave the changesets of these packages pending
+    #[arg(short = 'x', long, num_args = 1.., value_name = "PACKAGE")]
+    pub exclude: Vec<String>,
+
+    /// Split changesets that also target unselected packages, keeping their other entries pending
+    #[arg(long)]
+    pub split: bool,
+
+    /// Commit the release, using `git.release_commit_message` as the message
+    #[arg(long)]
+    pub commit: bool,
+
+    /// Sign the release commit with git's configured key
+    #[arg(long, requires = "commit")]
+    pub sign: bool,
 }
 
 #[derive(Debug, Args, Default)]
@@ -261,6 +327,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_publish_existence_check_flags() {
+        let cli = Cli::try_parse_from(["sampo", "publish", "--offline-check"]).unwrap();
+        match cli.command {
+            Commands::Publish(args) => assert!(args.offline_check && !args.repair_tags),
+            _ => panic!("wrong variant"),
+        }
+        let cli = Cli::try_parse_from(["sampo", "publish", "--repair-tags"]).unwrap();
+        match cli.command {
+            Commands::Publish(args) => assert!(args.repair_tags),
+            _ => panic!("wrong variant"),
+        }
+        assert!(
+            Cli::try_parse_from(["sampo", "publish", "--offline-check", "--repair-tags"]).is_err()
+        );
+    }
+
+    #[test]
+    fn parses_yank() {
+        let cli = Cli::try_parse_from([
+            "sampo",
+            "yank",
+            "npm/@scope/web@2.0.0",
+            "--reason",
+            "broken build",
+            "--changelog",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Yank(args) => {
+                assert_eq!(args.target, "npm/@scope/web@2.0.0");
+                assert_eq!(args.reason.as_deref(), Some("broken build"));
+                assert!(args.changelog && !args.github_release && !args.unpublish);
+            }
+            _ => panic!("wrong variant"),
+        }
+        assert!(Cli::try_parse_from(["sampo", "yank"]).is_err());
+    }
+
     #[test]
     fn parses_publish_passthrough_flags() {
         let cli = Cli::try_parse_from([
@@ -296,6 +401,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_release_package_filters() {
+        let cli = Cli::try_parse_from([
+            "sampo",
+            "release",
+            "-p",
+            "foo",
+            "bar",
+            "--exclude",
+            "npm/web",
+            "--split",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Release(args) => {
+                assert_eq!(args.package, vec!["foo", "bar"]);
+                assert_eq!(args.exclude, vec!["npm/web"]);
+                assert!(args.split);
+                assert!(!args.dry_run);
+            }
+            _ => panic!("wrong variant"),
+        }
+    }
+
+    #[test]
+    fn parses_release_commit_flags() {
+        let cli = Cli::try_parse_from(["sampo", "release", "--commit", "--sign"]).unwrap();
+        match cli.command {
+            Commands::Release(args) => {
+                assert!(args.commit);
+                assert!(args.sign);
+            }
+            _ => panic!("wrong variant"),
+        }
+
+        let res = Cli::try_parse_from(["sampo", "release", "--sign"]);
+        assert!(res.is_err(), "--sign should require --commit");
+    }
+
     #[test]
     fn parses_pre_enter_with_label_and_packages() {
         let cli = Cli::try_parse_from(["sampo", "pre", "enter", "alpha", "-p", "foo"]).unwrap();
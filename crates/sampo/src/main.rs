@@ -9,6 +9,7 @@ mod ui;
 #[cfg(feature = "self-update")]
 mod update;
 mod version_check;
+mod yank;
 
 use clap::Parser;
 use cli::{Cli, Commands};
@@ -99,6 +100,12 @@ fn main() -> ExitCode {
                 return exit::ERROR;
             }
         },
+        Commands::Yank(args) => {
+            if let Err(e) = yank::run(&args) {
+                eprintln!("Failed to yank package: {e}");
+                return exit::ERROR;
+            }
+        }
         #[cfg(feature = "self-update")]
         Commands::Update(args) => match update::run(&args) {
             Ok(true) => {}
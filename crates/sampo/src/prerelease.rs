@@ -106,7 +106,8 @@ fn run_enter(args: &PreEnterArgs) -> Result<bool> {
         .map(|spec| spec.to_canonical_string())
         .collect();
 
-    let label = resolve_label(args.label.as_deref())?;
+    let configured = configured_label(&workspace, &canonical)?;
+    let label = resolve_label(args.label.as_deref().or(configured.as_deref()))?;
 
     let mut any_changes = false;
 
@@ -288,6 +289,26 @@ fn visible_packages(
     Ok(out)
 }
 
+/// The `[packages."<package>"] prerelease` label shared by every selected package, if any.
+fn configured_label(
+    workspace: &sampo_core::Workspace,
+    identifiers: &[String],
+) -> Result<Option<String>> {
+    let config = Config::load(&workspace.root)?;
+    let labels: BTreeSet<Option<String>> = identifiers
+        .iter()
+        .map(|id| {
+            workspace
+                .find_by_identifier(id)
+                .and_then(|info| config.for_package(info).prerelease_label)
+        })
+        .collect();
+    Ok(match labels.into_iter().collect::<Vec<_>>().as_slice() {
+        [Some(label)] => Some(label.clone()),
+        _ => None,
+    })
+}
+
 fn resolve_label(existing: Option<&str>) -> Result<String> {
     if let Some(value) = normalize_nonempty_string(existing) {
         log_success_value("Pre-release label", &value);
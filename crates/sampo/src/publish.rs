@@ -1,7 +1,9 @@
 use crate::cli::PublishArgs;
-use sampo_core::PublishExtraArgs;
-use sampo_core::errors::Result;
-use sampo_core::run_publish;
+use sampo_core::errors::{Result, SampoError, io_error_with_path};
+use sampo_core::{
+    ExistenceCheck, PublishExtraArgs, PublishOptions, PublishOutput, run_publish_with_options,
+};
+use std::path::Path;
 
 /// Runs the publish command.
 ///
@@ -20,7 +22,108 @@ pub fn run(args: &PublishArgs) -> Result<bool> {
         maven: args.maven_args.clone().unwrap_or_default(),
     };
 
-    let output = run_publish(&cwd, args.dry_run, &extra_args)?;
+    let options = PublishOptions {
+        existence_check: args.offline_check.then_some(ExistenceCheck::Tags),
+        repair_tags: args.repair_tags,
+        ..Default::default()
+    };
+
+    let output = match run_publish_with_options(&cwd, args.dry_run, &extra_args, &options) {
+        Ok(output) => output,
+        // Report what the run did before failing, e.g. which registries accepted a package.
+        Err(SampoError::PublishIncomplete { output, source }) => {
+            if let Some(path) = &args.report {
+                write_report(path, &output)?;
+            }
+            return Err(*source);
+        }
+        Err(e) => return Err(e),
+    };
+    if let Some(path) = &args.report {
+        write_report(path, &output)?;
+    }
 
     Ok(!output.tags.is_empty())
 }
+
+/// Writes `output` as the JSON publish report.
+fn write_report(path: &Path, output: &PublishOutput) -> Result<()> {
+    let mut report = serde_json::to_string_pretty(output)
+        .map_err(|e| SampoError::Publish(format!("failed to serialize the publish report: {e}")))?;
+    report.push('\n');
+    std::fs::write(path, report).map_err(|e| SampoError::Io(io_error_with_path(e, path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sampo_core::{PublishRetry, RegistryPublish, RegistryStatus, RetriedOperation};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn report_lists_retries() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("publish.json");
+        let output = PublishOutput {
+            tags: vec!["foo-v1.0.0".to_string()],
+            floating_tags: Vec::new(),
+            dry_run: false,
+            retries: vec![PublishRetry {
+                package: "cargo/foo".to_string(),
+                operation: RetriedOperation::RegistryCheck,
+                attempt: 1,
+                error: "crates.io returned 503".to_string(),
+            }],
+            provenance: BTreeMap::new(),
+            registries: Vec::new(),
+        };
+
+        write_report(&path, &output).unwrap();
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(report["tags"][0], "foo-v1.0.0");
+        assert_eq!(
+            report["retries"],
+            serde_json::json!([{
+                "package": "cargo/foo",
+                "operation": "registry-check",
+                "attempt": 1,
+                "error": "crates.io returned 503",
+            }])
+        );
+    }
+
+    #[test]
+    fn report_lists_registry_statuses() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("publish.json");
+        let output = PublishOutput {
+            registries: vec![
+                RegistryPublish {
+                    package: "cargo/foo".to_string(),
+                    registry: "crates-io".to_string(),
+                    status: RegistryStatus::Published,
+                },
+                RegistryPublish {
+                    package: "cargo/foo".to_string(),
+                    registry: "internal".to_string(),
+                    status: RegistryStatus::Failed {
+                        error: "403 Forbidden".to_string(),
+                    },
+                },
+            ],
+            ..Default::default()
+        };
+
+        write_report(&path, &output).unwrap();
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            report["registries"],
+            serde_json::json!([
+                {"package": "cargo/foo", "registry": "crates-io", "status": "published"},
+                {"package": "cargo/foo", "registry": "internal", "status": "failed", "error": "403 Forbidden"},
+            ])
+        );
+    }
+}
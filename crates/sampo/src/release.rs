@@ -1,6 +1,15 @@
 use crate::cli::ReleaseArgs;
-use sampo_core::errors::Result;
-use sampo_core::run_release;
+use sampo_core::errors::{Result, SampoError};
+use sampo_core::types::{
+    PackageSpecifier, ReleaseOutput, SpecResolution, format_ambiguity_options,
+};
+use sampo_core::{
+    Config, ReleaseSelection, Workspace, commit_paths, discover_workspace,
+    render_release_commit_message, repository_path, run_release_with_selection,
+    working_tree_changes,
+};
+use std::collections::BTreeSet;
+use std::path::Path;
 
 /// Runs the release command.
 ///
@@ -8,7 +17,126 @@ use sampo_core::run_release;
 /// `Ok(false)` if there were no changesets to process.
 pub fn run(args: &ReleaseArgs) -> Result<bool> {
     let cwd = std::env::current_dir()?;
-    let output = run_release(&cwd, args.dry_run)?;
+    let selection = if args.package.is_empty() && args.exclude.is_empty() {
+        ReleaseSelection::default()
+    } else {
+        let workspace = discover_workspace(&cwd)?;
+        ReleaseSelection {
+            packages: resolve_identifiers(&workspace, &args.package)?,
+            excluded: resolve_identifiers(&workspace, &args.exclude)?,
+            split_mixed: args.split,
+        }
+    };
+    let changes_before = if args.commit {
+        Some(changes_before_release(&cwd)?)
+    } else {
+        None
+    };
+    let output = run_release_with_selection(&cwd, args.dry_run, &selection)?;
+
+    if let Some(changes_before) = changes_before
+        && !output.released_packages.is_empty()
+    {
+        commit_release(&cwd, args, &output, &changes_before)?;
+    }
 
     Ok(!output.released_packages.is_empty())
 }
+
+/// Uncommitted changes present before the release. Pending changesets are the release's
+/// input; any other change would end up mixed into the release commit.
+fn changes_before_release(cwd: &Path) -> Result<BTreeSet<String>> {
+    let changes = working_tree_changes(cwd)?;
+    let unrelated: Vec<&str> = changes
+        .iter()
+        .map(String::as_str)
+        .filter(|path| {
+            !Path::new(path)
+                .parent()
+                .is_some_and(|dir| dir.ends_with(".sampo/changesets"))
+        })
+        .collect();
+    if !unrelated.is_empty() {
+        return Err(SampoError::Release(format!(
+            "Refusing to commit the release: the working tree has changes unrelated to it ({}). Commit or stash them first.",
+            unrelated.join(", ")
+        )));
+    }
+    Ok(changes)
+}
+
+/// Commit the files the release touched: those `git status` reports now but not before,
+/// and the changesets it consumed, even those already changed before the release.
+fn commit_release(
+    cwd: &Path,
+    args: &ReleaseArgs,
+    output: &ReleaseOutput,
+    changes_before: &BTreeSet<String>,
+) -> Result<()> {
+    let workspace = discover_workspace(cwd)?;
+    let config = Config::load(&workspace.root)?;
+    let message = render_release_commit_message(
+        &config.git_release_commit_message,
+        output.released_packages.iter().map(|package| {
+            (
+                package.name.as_str(),
+                package.old_version.as_str(),
+                package.new_version.as_str(),
+            )
+        }),
+    );
+
+    if args.dry_run {
+        println!("Would commit the release:\n{message}");
+        return Ok(());
+    }
+
+    let mut touched: BTreeSet<String> = working_tree_changes(cwd)?
+        .difference(changes_before)
+        .cloned()
+        .collect();
+    for path in &output.consumed_changesets {
+        touched.insert(repository_path(cwd, path)?);
+    }
+    if touched.is_empty() {
+        println!("No file changes after release. Skipping commit.");
+        return Ok(());
+    }
+    commit_paths(cwd, &touched, &message, args.sign)?;
+    println!(
+        "Committed the release ({} files): {}",
+        touched.len(),
+        message.lines().next().unwrap_or_default()
+    );
+    Ok(())
+}
+
+/// Resolve package references from the command line to canonical identifiers.
+fn resolve_identifiers(workspace: &Workspace, inputs: &[String]) -> Result<BTreeSet<String>> {
+    let mut identifiers = BTreeSet::new();
+    for raw in inputs {
+        let spec = PackageSpecifier::parse(raw).map_err(|reason| {
+            SampoError::Release(format!("Invalid package reference '{}': {}", raw, reason))
+        })?;
+        match workspace.resolve_specifier(&spec) {
+            SpecResolution::Match(info) => {
+                identifiers.insert(info.canonical_identifier().to_string());
+            }
+            SpecResolution::NotFound { query } => {
+                return Err(SampoError::Release(format!(
+                    "Package '{}' not found in workspace",
+                    query.display()
+                )));
+            }
+            SpecResolution::Ambiguous { query, matches } => {
+                let options = format_ambiguity_options(&matches);
+                return Err(SampoError::Release(format!(
+                    "Package '{}' is ambiguous. Disambiguate using one of: {}.",
+                    query.base_name(),
+                    options
+                )));
+            }
+        }
+    }
+    Ok(identifiers)
+}
@@ -0,0 +1,25 @@
+use crate::cli::YankArgs;
+use sampo_core::errors::Result;
+use sampo_core::{YankOptions, run_yank};
+
+/// Runs the yank command.
+pub fn run(args: &YankArgs) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+
+    let options = YankOptions {
+        reason: args.reason.clone(),
+        unpublish: args.unpublish,
+        changelog: args.changelog,
+        github_release: args.github_release,
+        dry_run: args.dry_run,
+    };
+
+    let output = run_yank(&cwd, &args.target, &options)?;
+    if output.dry_run {
+        println!("Dry-run complete.");
+    } else {
+        println!("Yanked {}@{}.", output.package, output.version);
+    }
+
+    Ok(())
+}